@@ -0,0 +1,191 @@
+use dm2xcod::{ConvertOptions, DocxToMarkdown, Error, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Debug, Clone)]
+struct BenchConfig {
+    paragraphs: usize,
+    iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            paragraphs: 20_000,
+            iterations: 5,
+        }
+    }
+}
+
+fn parse_args() -> BenchConfig {
+    let mut cfg = BenchConfig::default();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--paragraphs" => {
+                if let Some(value) = args.next() {
+                    if let Ok(parsed) = value.parse::<usize>() {
+                        cfg.paragraphs = parsed.max(1);
+                    }
+                }
+            }
+            "--iterations" => {
+                if let Some(value) = args.next() {
+                    if let Ok(parsed) = value.parse::<usize>() {
+                        cfg.iterations = parsed.max(1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    cfg
+}
+
+const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const LOGO_BYTES: usize = 1024 * 1024;
+
+/// Writes a text-only DOCX of headings and paragraphs of styled runs, with
+/// the settings and font table parts Word adds to every document and a
+/// header holding a logo.
+fn write_fixture(path: &Path, paragraphs: usize) -> Result<()> {
+    let mut body = String::new();
+    for i in 0..paragraphs {
+        if i % 50 == 0 {
+            body.push_str(&format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Section {}</w:t></w:r></w:p>"#,
+                i / 50 + 1
+            ));
+        }
+        body.push_str(&format!(
+            r#"<w:p><w:r><w:t xml:space="preserve">Paragraph {} has </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>bold</w:t></w:r><w:r><w:t xml:space="preserve">, </w:t></w:r><w:r><w:rPr><w:i/></w:rPr><w:t>italic</w:t></w:r><w:r><w:t xml:space="preserve"> and plain text that runs on for a while, like prose does.</w:t></w:r></w:p>"#,
+            i
+        ));
+    }
+
+    let parts = [
+        (
+            "[Content_Types].xml",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/settings.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml"/><Override PartName="/word/fontTable.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.fontTable+xml"/><Override PartName="/word/header1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"/></Types>"#.to_string(),
+        ),
+        (
+            "_rels/.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#.to_string(),
+        ),
+        (
+            "word/_rels/document.xml.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings" Target="settings.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/fontTable" Target="fontTable.xml"/><Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Target="header1.xml"/></Relationships>"#.to_string(),
+        ),
+        (
+            "word/_rels/header1.xml.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png"/></Relationships>"#.to_string(),
+        ),
+        (
+            "word/header1.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:hdr xmlns:w="{}"><w:p><w:r><w:t>Letterhead</w:t></w:r></w:p></w:hdr>"#,
+                W_NS
+            ),
+        ),
+        (
+            "word/document.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:document xmlns:w="{}" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>{}<w:sectPr><w:headerReference w:type="default" r:id="rId4"/></w:sectPr></w:body></w:document>"#,
+                W_NS, body
+            ),
+        ),
+        (
+            "word/styles.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:styles xmlns:w="{}"><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:pPr><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/></w:rPr></w:style></w:styles>"#,
+                W_NS
+            ),
+        ),
+        (
+            "word/settings.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:settings xmlns:w="{}"><w:zoom w:percent="100"/><w:defaultTabStop w:val="720"/><w:characterSpacingControl w:val="doNotCompress"/><w:compat><w:compatSetting w:name="compatibilityMode" w:uri="http://schemas.microsoft.com/office/word" w:val="15"/></w:compat></w:settings>"#,
+                W_NS
+            ),
+        ),
+        (
+            "word/fontTable.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:fonts xmlns:w="{}"><w:font w:name="Calibri"><w:charset w:val="00"/><w:family w:val="swiss"/><w:pitch w:val="variable"/></w:font><w:font w:name="Times New Roman"><w:charset w:val="00"/><w:family w:val="roman"/><w:pitch w:val="variable"/></w:font></w:fonts>"#,
+                W_NS
+            ),
+        ),
+    ];
+
+    let mut zip = zip::ZipWriter::new(File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, xml) in parts {
+        zip.start_file(name, options)?;
+        zip.write_all(xml.as_bytes())?;
+    }
+    // A letterhead logo: pseudo-random bytes, as incompressible as a photo.
+    let mut seed: u32 = 0x2545_f491;
+    let logo: Vec<u8> = (0..LOGO_BYTES)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as u8
+        })
+        .collect();
+    zip.start_file("word/media/image1.png", options)?;
+    zip.write_all(&logo)?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// Converts through the general path: the full rs_docx parse, then the raw
+/// XML read, as `convert` does for documents that are not text-only.
+fn convert_general(converter: &DocxToMarkdown, path: &Path) -> Result<String> {
+    let docx_file =
+        rs_docx::DocxFile::from_file(path).map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+    let docx = docx_file
+        .parse()
+        .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+    converter.convert_parsed(&docx, Some(path))
+}
+
+fn average_ms(iterations: usize, mut run: impl FnMut() -> Result<String>) -> Result<f64> {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run()?;
+    }
+    Ok(start.elapsed().as_secs_f64() * 1000.0 / iterations as f64)
+}
+
+fn main() -> Result<()> {
+    let cfg = parse_args();
+    let path: PathBuf = std::env::temp_dir().join("dm2xcod_text_only_benchmark.docx");
+    write_fixture(&path, cfg.paragraphs)?;
+
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    if converter.convert(&path)? != convert_general(&converter, &path)? {
+        return Err(Error::Conversion(
+            "text-only and general paths disagree".to_string(),
+        ));
+    }
+
+    let fast_ms = average_ms(cfg.iterations, || converter.convert(&path))?;
+    let general_ms = average_ms(cfg.iterations, || convert_general(&converter, &path))?;
+    std::fs::remove_file(&path)?;
+
+    println!(
+        "{{\"paragraphs\":{},\"iterations\":{},\"text_only_avg_ms\":{:.3},\"general_avg_ms\":{:.3},\"speedup\":{:.3}}}",
+        cfg.paragraphs,
+        cfg.iterations,
+        fast_ms,
+        general_ms,
+        general_ms / fast_ms
+    );
+
+    Ok(())
+}
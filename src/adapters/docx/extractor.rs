@@ -1,9 +1,14 @@
 use super::AstExtractor;
-use crate::converter::{ConversionContext, ParagraphConverter, RunConverter, TableConverter};
+use crate::converter::{
+    convert_tab_tables, CodeBlock, ConversionContext, ParagraphConverter, RunConverter,
+    TableConverter,
+};
 use crate::core::ast::{BlockNode, DocumentAst};
 use crate::render::escape_html_attr;
-use crate::Result;
-use rs_docx::document::{BodyContent, TableCell, TableCellContent};
+use crate::{Callout, CodeBlocks, Result, SingleCellTables, TextFramePosition, TextFrames};
+use rs_docx::document::{
+    BodyContent, Paragraph, Table, TableCell, TableCellContent, TableRowContent,
+};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DocxExtractor;
@@ -14,15 +19,131 @@ impl AstExtractor for DocxExtractor {
         body: &[BodyContent<'a>],
         context: &mut ConversionContext<'a>,
     ) -> Result<DocumentAst> {
+        let mut doc = self.extract_body(body, context)?;
+        if let Some(settings) = context.tab_tables() {
+            doc.blocks = convert_tab_tables(
+                doc.blocks,
+                settings,
+                context.empty_table_cell(),
+                context.no_html(),
+            );
+        }
+        Ok(doc)
+    }
+
+    /// Emits the blocks of each top-level body item once it is converted.
+    ///
+    /// Tab table detection and column annotations look across items, so with
+    /// either enabled the whole body is converted first.
+    fn extract_blocks<'a>(
+        &self,
+        body: &[BodyContent<'a>],
+        context: &mut ConversionContext<'a>,
+        emit: &mut dyn FnMut(BlockNode) -> Result<()>,
+    ) -> Result<()> {
+        if context.tab_tables().is_some() || context.annotate_columns() {
+            for block in self.extract(body, context)?.blocks {
+                emit(block)?;
+            }
+            return Ok(());
+        }
+
         let mut doc = DocumentAst::default();
         for content in body {
             self.extract_content(content, context, &mut doc)?;
+            // A trailing callout, tight list or code block may still be
+            // continued by the next item.
+            let last = doc.blocks.len().checked_sub(1);
+            let callout = last.and_then(|last| context.callout_at(last));
+            let list_item = last.and_then(|last| context.list_item_at(last).cloned());
+            let code_block = last.and_then(|last| context.code_block_at(last).cloned());
+            let held = callout.is_some() || list_item.is_some() || code_block.is_some();
+            let ready = doc.blocks.len() - usize::from(held);
+            for block in doc.blocks.drain(..ready) {
+                emit(block)?;
+            }
+            match callout {
+                Some(callout) => context.set_callout_at(0, callout),
+                None => context.clear_callout(),
+            }
+            match list_item {
+                Some(spacing) => context.set_list_item_at(0, spacing),
+                None => context.clear_list_item(),
+            }
+            match code_block {
+                Some(block) => context.set_code_block_at(0, block),
+                None => context.clear_code_block(),
+            }
         }
-        Ok(doc)
+        Self::push_text_frame(context, &mut doc);
+        for block in doc.blocks {
+            emit(block)?;
+        }
+        Ok(())
     }
 }
 
 impl DocxExtractor {
+    fn extract_body<'a>(
+        &self,
+        body: &[BodyContent<'a>],
+        context: &mut ConversionContext<'a>,
+    ) -> Result<DocumentAst> {
+        let mut doc = DocumentAst::default();
+        if !context.annotate_columns() {
+            for content in body {
+                self.extract_content(content, context, &mut doc)?;
+            }
+            Self::push_text_frame(context, &mut doc);
+            return Ok(doc);
+        }
+
+        let section_columns = Self::section_columns(body, context);
+        let mut open_columns = 1;
+        for (content, columns) in body.iter().zip(section_columns) {
+            if columns != open_columns {
+                if open_columns > 1 {
+                    doc.blocks
+                        .push(BlockNode::RawHtml("<!-- /columns -->".to_string()));
+                }
+                if columns > 1 {
+                    doc.blocks
+                        .push(BlockNode::RawHtml(format!("<!-- columns: {} -->", columns)));
+                }
+                open_columns = columns;
+            }
+            self.extract_content(content, context, &mut doc)?;
+        }
+        Self::push_text_frame(context, &mut doc);
+        if open_columns > 1 {
+            doc.blocks
+                .push(BlockNode::RawHtml("<!-- /columns -->".to_string()));
+        }
+        Ok(doc)
+    }
+
+    /// Returns the column count of the section each body item belongs to.
+    ///
+    /// A paragraph carrying `w:sectPr` ends its section; items after the last
+    /// such paragraph belong to the body's final section.
+    fn section_columns(body: &[BodyContent<'_>], context: &ConversionContext<'_>) -> Vec<u32> {
+        let mut columns = context.final_section_columns().unwrap_or(1);
+        let mut section_columns: Vec<u32> = body
+            .iter()
+            .rev()
+            .map(|content| {
+                if let BodyContent::Paragraph(para) = content {
+                    if let Some(ends) = context.section_columns(para) {
+                        columns = ends;
+                    }
+                }
+                columns
+            })
+            .collect();
+        section_columns.reverse();
+        section_columns
+    }
+
     fn extract_table_cell<'a>(
         &self,
         cell: &TableCell<'a>,
@@ -32,8 +153,14 @@ impl DocxExtractor {
         for item in &cell.content {
             match item {
                 TableCellContent::Paragraph(para) => {
+                    if let Some(code) = ParagraphConverter::code_text(para, context) {
+                        Self::push_code(Some(code), context, output);
+                        continue;
+                    }
                     let converted = ParagraphConverter::convert(para, context)?;
-                    if !converted.is_empty() {
+                    if converted.is_empty() {
+                        Self::push_code(None, context, output);
+                    } else {
                         output.blocks.push(BlockNode::Paragraph(converted));
                     }
                 }
@@ -46,6 +173,179 @@ impl DocxExtractor {
         Ok(())
     }
 
+    /// Returns the cell of a table with one row holding one cell.
+    fn single_cell<'t, 'a>(table: &'t Table<'a>) -> Option<&'t TableCell<'a>> {
+        let [row] = table.rows.as_slice() else {
+            return None;
+        };
+        match row.cells.as_slice() {
+            [TableRowContent::TableCell(cell)] => Some(cell),
+            _ => None,
+        }
+    }
+
+    /// Writes a callout paragraph as a blockquote, continuing the previous
+    /// block's blockquote when it has the same callout.
+    fn push_callout(
+        callout: Callout,
+        text: &str,
+        context: &mut ConversionContext<'_>,
+        output: &mut DocumentAst,
+    ) {
+        let quoted = quote(text);
+
+        let last = output.blocks.len().checked_sub(1);
+        if let (Some(BlockNode::Paragraph(block)), Some(index)) = (output.blocks.last_mut(), last) {
+            if context.callout_at(index) == Some(callout) {
+                block.push_str("\n>\n");
+                block.push_str(&quoted);
+                return;
+            }
+        }
+
+        let block = match callout {
+            Callout::Alert(kind) => format!("> [!{}]\n{}", kind.as_str(), quoted),
+            Callout::Quote => quoted,
+        };
+        output.blocks.push(BlockNode::Paragraph(block));
+        context.set_callout_at(output.blocks.len() - 1, callout);
+    }
+
+    /// Writes a paragraph. A list item directly after another one without
+    /// paragraph spacing between them joins its block, so the list is tight.
+    fn push_paragraph<'a>(
+        para: &Paragraph<'a>,
+        converted: String,
+        context: &mut ConversionContext<'a>,
+        output: &mut DocumentAst,
+    ) {
+        let Some(spacing) = context.list_item_spacing(para) else {
+            output.blocks.push(BlockNode::Paragraph(converted));
+            return;
+        };
+
+        let last = output.blocks.len().checked_sub(1);
+        if let (Some(BlockNode::Paragraph(block)), Some(index)) = (output.blocks.last_mut(), last) {
+            if context
+                .list_item_at(index)
+                .is_some_and(|previous| previous.is_tight_with(&spacing))
+            {
+                block.push('\n');
+                block.push_str(&converted);
+                context.set_list_item_at(index, spacing);
+                return;
+            }
+        }
+
+        output.blocks.push(BlockNode::Paragraph(converted));
+        context.set_list_item_at(output.blocks.len() - 1, spacing);
+    }
+
+    /// Writes the lines of a code paragraph, continuing the previous block
+    /// when it is a code block fenced the same way. A blank paragraph
+    /// (`None`) only adds an empty line to a code block it follows.
+    fn push_code(
+        code: Option<(String, CodeBlocks)>,
+        context: &mut ConversionContext<'_>,
+        output: &mut DocumentAst,
+    ) {
+        let last = output.blocks.len().checked_sub(1);
+        let previous = last.and_then(|index| context.code_block_at(index).cloned());
+        let (index, mut block, text) = match (previous, last, code) {
+            (Some(block), Some(index), None) => (index, block, String::new()),
+            (_, _, None) => return,
+            (Some(block), Some(index), Some((text, settings))) if block.settings == settings => {
+                (index, block, text)
+            }
+            (_, _, Some((text, settings))) => {
+                output.blocks.push(BlockNode::Paragraph(String::new()));
+                let block = CodeBlock {
+                    settings,
+                    lines: Vec::new(),
+                };
+                (output.blocks.len() - 1, block, text)
+            }
+        };
+        block.lines.extend(text.split('\n').map(str::to_string));
+        let text = match context.plain_text() {
+            true => block.lines.join("\n"),
+            false => block.render(),
+        };
+        output.blocks[index] = BlockNode::Paragraph(text);
+        context.set_code_block_at(index, block);
+    }
+
+    fn extract_paragraph<'a>(
+        para: &Paragraph<'a>,
+        context: &mut ConversionContext<'a>,
+        output: &mut DocumentAst,
+    ) -> Result<()> {
+        if context.page_boundaries() && ParagraphConverter::has_rendered_page_break(para) {
+            output.blocks.push(BlockNode::PageBreak);
+        }
+        if let Some(code) = ParagraphConverter::code_text(para, context) {
+            Self::push_code(Some(code), context, output);
+            return Ok(());
+        }
+        let converted = ParagraphConverter::convert(para, context)?;
+        if converted.is_empty() {
+            Self::push_code(None, context, output);
+            return Ok(());
+        }
+        match context.paragraph_callout(para) {
+            Some(callout) => Self::push_callout(callout, &converted, context, output),
+            None => Self::push_paragraph(para, converted, context, output),
+        }
+        Ok(())
+    }
+
+    fn extract_table<'a>(
+        &self,
+        table: &Table<'a>,
+        context: &mut ConversionContext<'a>,
+        output: &mut DocumentAst,
+    ) -> Result<()> {
+        let single_cell = context.single_cell_tables();
+        match Self::single_cell(table).filter(|_| single_cell != SingleCellTables::Table) {
+            Some(cell) if single_cell == SingleCellTables::Quote => {
+                let mut unwrapped = DocumentAst::default();
+                self.extract_table_cell(cell, context, &mut unwrapped)?;
+                let blocks: Vec<&str> = unwrapped.blocks.iter().map(block_text).collect();
+                if !blocks.is_empty() {
+                    let text = blocks.join("\n\n");
+                    let text = match context.plain_text() {
+                        true => text,
+                        false => quote(&text),
+                    };
+                    output.blocks.push(BlockNode::Paragraph(text));
+                }
+            }
+            Some(cell) => self.extract_table_cell(cell, context, output)?,
+            None => {
+                let converted = TableConverter::convert(table, context)?;
+                output.blocks.push(BlockNode::TableHtml(converted));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the collected text frame, if any, as a blockquote or an
+    /// `<aside>`.
+    fn push_text_frame(context: &mut ConversionContext<'_>, output: &mut DocumentAst) {
+        let Some(paragraphs) = context.take_text_frame() else {
+            return;
+        };
+        let text = paragraphs.join("\n\n");
+        let block = match context.text_frames() {
+            TextFrames::Aside if !context.no_html() => {
+                format!("<aside>\n\n{}\n\n</aside>", text)
+            }
+            _ if context.plain_text() => text,
+            _ => quote(&text),
+        };
+        output.blocks.push(BlockNode::Paragraph(block));
+    }
+
     fn extract_content<'a>(
         &self,
         content: &BodyContent<'a>,
@@ -53,15 +353,25 @@ impl DocxExtractor {
         output: &mut DocumentAst,
     ) -> Result<()> {
         match content {
-            BodyContent::Paragraph(para) => {
+            BodyContent::Paragraph(para) if context.is_text_frame(para) => {
                 let converted = ParagraphConverter::convert(para, context)?;
                 if !converted.is_empty() {
-                    output.blocks.push(BlockNode::Paragraph(converted));
+                    context.push_text_frame(converted);
                 }
             }
+            BodyContent::Paragraph(para) => {
+                if context.text_frame_position() == TextFramePosition::InPlace {
+                    Self::push_text_frame(context, output);
+                }
+                Self::extract_paragraph(para, context, output)?;
+                Self::push_text_frame(context, output);
+            }
             BodyContent::Table(table) => {
-                let converted = TableConverter::convert(table, context)?;
-                output.blocks.push(BlockNode::TableHtml(converted));
+                if context.text_frame_position() == TextFramePosition::InPlace {
+                    Self::push_text_frame(context, output);
+                }
+                self.extract_table(table, context, output)?;
+                Self::push_text_frame(context, output);
             }
             BodyContent::Run(run) => {
                 let converted = RunConverter::convert(run, context, None)?;
@@ -73,14 +383,24 @@ impl DocxExtractor {
                 self.extract_table_cell(cell, context, output)?;
             }
             BodyContent::Sdt(sdt) => {
-                if let Some(sdt_content) = &sdt.content {
+                // An empty control shows the building block it refers to.
+                if let Some(block) = context.building_block(sdt)? {
+                    for child in &block.content {
+                        self.extract_content(child, context, output)?;
+                    }
+                } else if let Some(sdt_content) = &sdt.content {
                     for child in &sdt_content.content {
                         self.extract_content(child, context, output)?;
                     }
                 }
             }
-            BodyContent::BookmarkStart(bookmark) => {
-                if let Some(name) = &bookmark.name {
+            BodyContent::BookmarkStart(bookmark) if !context.no_html() => {
+                // Bookmarks on a slugged heading are replaced by its slug anchor.
+                if let Some(name) = bookmark
+                    .name
+                    .as_ref()
+                    .filter(|name| context.bookmark_slug(name).is_none())
+                {
                     output.blocks.push(BlockNode::RawHtml(format!(
                         "<a id=\"{}\"></a>",
                         escape_html_attr(name)
@@ -93,3 +413,21 @@ impl DocxExtractor {
         Ok(())
     }
 }
+
+/// Prefixes every line of `text` with `> `.
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| match line {
+            "" => ">".to_string(),
+            line => format!("> {}", line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn block_text(block: &BlockNode) -> &str {
+    match block {
+        BlockNode::Paragraph(text) | BlockNode::TableHtml(text) | BlockNode::RawHtml(text) => text,
+        BlockNode::PageBreak => "",
+    }
+}
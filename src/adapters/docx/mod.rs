@@ -1,16 +1,47 @@
 mod extractor;
 
 use crate::converter::ConversionContext;
-use crate::core::ast::DocumentAst;
+use crate::core::ast::{BlockNode, DocumentAst};
 use crate::Result;
 use rs_docx::document::BodyContent;
 
+/// Extracts the body of a parsed DOCX into a [`DocumentAst`].
+///
+/// This is the customization point of the DOCX pipeline, used through
+/// [`DocxToMarkdown::with_components`](crate::DocxToMarkdown::with_components).
+/// The pipeline parses the package and builds the [`ConversionContext`]
+/// (relationships, numbering, styles, notes, images) before calling
+/// `extract`, so implementations only walk `body`, typically delegating to
+/// [`ParagraphConverter`](crate::converter::ParagraphConverter) and
+/// [`TableConverter`](crate::converter::TableConverter).
+///
+/// `references` in the returned AST is overwritten with the notes the context
+/// collected. Inputs other than DOCX implement
+/// [`DocumentExtractor`](crate::adapters::DocumentExtractor) instead.
 pub trait AstExtractor {
     fn extract<'a>(
         &self,
         body: &[BodyContent<'a>],
         context: &mut ConversionContext<'a>,
     ) -> Result<DocumentAst>;
+
+    /// Extracts `body` block by block, passing each finished block to `emit`.
+    ///
+    /// Used by [`DocxToMarkdown::blocks_iter`](crate::DocxToMarkdown::blocks_iter).
+    /// The default runs [`extract`](Self::extract) and emits its blocks, so
+    /// nothing is emitted before the whole body is converted. An error from
+    /// `emit` must be returned as is.
+    fn extract_blocks<'a>(
+        &self,
+        body: &[BodyContent<'a>],
+        context: &mut ConversionContext<'a>,
+        emit: &mut dyn FnMut(BlockNode) -> Result<()>,
+    ) -> Result<()> {
+        for block in self.extract(body, context)?.blocks {
+            emit(block)?;
+        }
+        Ok(())
+    }
 }
 
 pub use extractor::DocxExtractor;
@@ -1 +1,46 @@
+//! Input adapters that turn source documents into a [`DocumentAst`].
+//!
+//! Every input format ends in the same [`DocumentAst`], so any
+//! [`Renderer`](crate::render::Renderer) can render any format. A format is
+//! plugged in by implementing [`DocumentExtractor`] and registering it in an
+//! [`ExtractorRegistry`] under its file extension.
+
 pub mod docx;
+mod registry;
+
+use crate::core::ast::DocumentAst;
+use crate::Result;
+
+pub use registry::ExtractorRegistry;
+
+/// Extracts a whole document of one input format into a [`DocumentAst`].
+///
+/// Contract:
+/// - `blocks` are in reading order; each [`BlockNode`](crate::core::ast::BlockNode)
+///   holds finished Markdown (`Paragraph`) or HTML (`TableHtml`, `RawHtml`).
+///   Renderers only join blocks, they do not escape or format them.
+/// - For [`extract_plain_text`](DocumentExtractor::extract_plain_text),
+///   `Paragraph` and `TableHtml` blocks hold plain text instead and `RawHtml`
+///   blocks are left out of the output, with notes marked `[n]`.
+/// - Notes referenced as `[^n]`, `[^enn]`, and `[^c<id>]` from block text are
+///   listed in `references` (1-based for footnotes and endnotes), whose
+///   `markers` describe these marker formats.
+/// - Errors are reported as [`Error`](crate::Error); `Error::Conversion` suits
+///   format-specific failures.
+///
+/// Implementations are self-contained: unlike
+/// [`docx::AstExtractor`], they do not receive a
+/// [`ConversionContext`](crate::converter::ConversionContext), which models
+/// DOCX relationships, numbering, and styles.
+pub trait DocumentExtractor {
+    /// Extracts the document from its file contents.
+    fn extract(&self, bytes: &[u8]) -> Result<DocumentAst>;
+
+    /// Extracts the document for a plain text
+    /// [`Renderer`](crate::render::Renderer), without any Markdown or HTML
+    /// markup. Defaults to [`extract`](DocumentExtractor::extract), for
+    /// formats whose blocks carry no markup.
+    fn extract_plain_text(&self, bytes: &[u8]) -> Result<DocumentAst> {
+        self.extract(bytes)
+    }
+}
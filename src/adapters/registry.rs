@@ -0,0 +1,80 @@
+//! Registry of document extractors keyed by file extension.
+
+use super::DocumentExtractor;
+use crate::core::ast::DocumentAst;
+use crate::render::Renderer;
+use crate::{ConvertOptions, DocxToMarkdown, Error, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Selects a [`DocumentExtractor`] by file extension (case-insensitive).
+///
+/// ```no_run
+/// use dm2xcod::adapters::ExtractorRegistry;
+/// use dm2xcod::render::MarkdownRenderer;
+/// use dm2xcod::ConvertOptions;
+///
+/// let registry = ExtractorRegistry::with_docx(ConvertOptions::default());
+/// let markdown = registry.convert("document.docx", &MarkdownRenderer).unwrap();
+/// ```
+#[derive(Default)]
+pub struct ExtractorRegistry {
+    extractors: HashMap<String, Box<dyn DocumentExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Creates a registry with the built-in DOCX pipeline registered for `docx`.
+    pub fn with_docx(options: ConvertOptions) -> Self {
+        let mut registry = Self::default();
+        registry.register("docx", DocxToMarkdown::new(options));
+        registry
+    }
+
+    /// Registers `extractor` for `extension` (without the leading dot),
+    /// replacing any extractor already registered for it.
+    pub fn register<X>(&mut self, extension: &str, extractor: X) -> &mut Self
+    where
+        X: DocumentExtractor + 'static,
+    {
+        self.extractors
+            .insert(normalize_extension(extension), Box::new(extractor));
+        self
+    }
+
+    /// Returns the extractor registered for `extension`, if any.
+    pub fn get(&self, extension: &str) -> Option<&dyn DocumentExtractor> {
+        self.extractors
+            .get(&normalize_extension(extension))
+            .map(|extractor| extractor.as_ref())
+    }
+
+    /// Extracts the file at `path` with the extractor for its extension.
+    pub fn extract<P: AsRef<Path>>(&self, path: P) -> Result<DocumentAst> {
+        self.extract_for(path.as_ref(), false)
+    }
+
+    /// Extracts the file at `path` and renders it with `renderer`.
+    pub fn convert<P: AsRef<Path>, R: Renderer>(&self, path: P, renderer: &R) -> Result<String> {
+        renderer.render(&self.extract_for(path.as_ref(), renderer.plain_text())?)
+    }
+
+    /// Extracts the file at `path`, as plain text with `plain_text`.
+    fn extract_for(&self, path: &Path, plain_text: bool) -> Result<DocumentAst> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        let extractor = self.get(extension).ok_or_else(|| {
+            Error::Conversion(format!("no extractor registered for '{}'", path.display()))
+        })?;
+        let bytes = std::fs::read(path)?;
+        match plain_text {
+            true => extractor.extract_plain_text(&bytes),
+            false => extractor.extract(&bytes),
+        }
+    }
+}
+
+fn normalize_extension(extension: &str) -> String {
+    extension.trim_start_matches('.').to_ascii_lowercase()
+}
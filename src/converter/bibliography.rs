@@ -0,0 +1,96 @@
+//! Bibliographies - the paragraphs of a `BIBLIOGRAPHY` field's result.
+//!
+//! Word writes the sources of a bibliography as the cached result of a
+//! `BIBLIOGRAPHY` field, one paragraph per source, formatted in the
+//! document's citation style. The field starts in the first entry and ends
+//! in a later paragraph, so the entries are found before conversion by
+//! following the complex fields across paragraphs.
+
+use super::raw_xml::element_key;
+use rs_docx::document::{BodyContent, CharType, Paragraph, ParagraphContent, RunContent};
+use std::collections::HashSet;
+
+/// Paragraphs holding the entries of a bibliography.
+#[derive(Debug, Default)]
+pub(crate) struct BibliographyEntries {
+    paragraphs: HashSet<usize>,
+}
+
+/// Complex field open at a point of the document: whether its instruction is
+/// `BIBLIOGRAPHY`, and whether its result has started.
+struct Field {
+    instruction: String,
+    result: bool,
+}
+
+impl Field {
+    fn is_bibliography_result(&self) -> bool {
+        self.result && self.instruction.split_whitespace().next() == Some("BIBLIOGRAPHY")
+    }
+}
+
+impl BibliographyEntries {
+    /// Finds the paragraphs in the result of a `BIBLIOGRAPHY` field, in the
+    /// body and content controls.
+    pub fn build(body: &[BodyContent<'_>]) -> Self {
+        let mut entries = Self::default();
+        entries.visit(body, &mut Vec::new());
+        entries
+    }
+
+    pub fn contains(&self, para: &Paragraph<'_>) -> bool {
+        self.paragraphs.contains(&element_key(para))
+    }
+
+    fn visit(&mut self, body: &[BodyContent<'_>], fields: &mut Vec<Field>) {
+        for content in body {
+            match content {
+                BodyContent::Paragraph(para) => self.add_paragraph(para, fields),
+                BodyContent::Sdt(sdt) => {
+                    if let Some(sdt_content) = &sdt.content {
+                        self.visit(&sdt_content.content, fields);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_paragraph(&mut self, para: &Paragraph<'_>, fields: &mut Vec<Field>) {
+        let mut in_result = fields.iter().any(Field::is_bibliography_result);
+        for content in &para.content {
+            let ParagraphContent::Run(run) = content else {
+                continue;
+            };
+            for item in &run.content {
+                match item {
+                    RunContent::FieldChar(field_char) => match field_char.ty {
+                        Some(CharType::Begin) => fields.push(Field {
+                            instruction: String::new(),
+                            result: false,
+                        }),
+                        Some(CharType::Separate) => {
+                            if let Some(field) = fields.last_mut() {
+                                field.result = true;
+                                in_result |= field.is_bibliography_result();
+                            }
+                        }
+                        Some(CharType::End) => {
+                            fields.pop();
+                        }
+                        None => {}
+                    },
+                    RunContent::InstrText(instr) => {
+                        if let Some(field) = fields.last_mut().filter(|field| !field.result) {
+                            field.instruction.push_str(&instr.text);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if in_result {
+            self.paragraphs.insert(element_key(para));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Block iterator - converted blocks streamed from a conversion thread.
+
+use crate::core::ast::{BlockNode, ReferenceDefinitions};
+use crate::{Error, Result};
+use std::sync::mpsc::{Receiver, SyncSender};
+
+/// Number of converted blocks the conversion thread may run ahead.
+pub(super) const BLOCK_BUFFER: usize = 16;
+
+/// A message from the conversion thread.
+pub(super) enum BlockEvent {
+    Block(BlockNode),
+    /// Conversion finished; carries the notes the blocks reference.
+    End(ReferenceDefinitions),
+}
+
+pub(super) type BlockSender = SyncSender<Result<BlockEvent>>;
+
+/// Iterator over the converted blocks of a document, returned by
+/// [`DocxToMarkdown::blocks_iter`](crate::DocxToMarkdown::blocks_iter).
+///
+/// The first error ends the iteration. Dropping the iterator early stops the
+/// conversion.
+pub struct BlockIter {
+    receiver: Receiver<Result<BlockEvent>>,
+    references: Option<ReferenceDefinitions>,
+    finished: bool,
+}
+
+impl BlockIter {
+    pub(super) fn new(receiver: Receiver<Result<BlockEvent>>) -> Self {
+        Self {
+            receiver,
+            references: None,
+            finished: false,
+        }
+    }
+
+    /// Returns the footnotes, endnotes and comments referenced by the blocks.
+    ///
+    /// Available once the iterator has returned `None`; `None` before that or
+    /// after an error.
+    pub fn references(&self) -> Option<&ReferenceDefinitions> {
+        self.references.as_ref()
+    }
+
+    /// Takes the referenced notes, like [`references`](Self::references).
+    pub fn into_references(self) -> Option<ReferenceDefinitions> {
+        self.references
+    }
+}
+
+impl Iterator for BlockIter {
+    type Item = Result<BlockNode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let event = self.receiver.recv().unwrap_or_else(|_| {
+            Err(Error::Conversion(
+                "conversion thread stopped unexpectedly".to_string(),
+            ))
+        });
+        match event {
+            Ok(BlockEvent::Block(block)) => Some(Ok(block)),
+            Ok(BlockEvent::End(references)) => {
+                self.references = Some(references);
+                self.finished = true;
+                None
+            }
+            Err(error) => {
+                self.finished = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Chart reader - recovers the cached data of a chart part (`c:chartSpace`).
+//!
+//! Word stores the last computed values of every series next to the formula
+//! references, so the numbers can be tabulated without the embedded workbook.
+
+use super::raw_xml::unescape_xml;
+use super::table_grid::{render_grid, render_pipe_table, render_text_table, CellStatus};
+use crate::render::escape_html_text;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// Cached data of a chart, one series per column.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ChartData {
+    pub title: Option<String>,
+    /// Category labels by point index.
+    pub categories: Vec<String>,
+    pub series: Vec<ChartSeries>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ChartSeries {
+    pub name: String,
+    /// Values by point index; missing points are empty.
+    pub values: Vec<String>,
+}
+
+/// Part of a `c:ser` a cached value belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesPart {
+    Name,
+    Category,
+    Value,
+}
+
+impl ChartData {
+    /// Parses a chart part, or returns `None` when it holds no series.
+    pub fn parse(xml: &str) -> Option<Self> {
+        let mut chart = Self::default();
+        let mut stack: Vec<&str> = Vec::new();
+        let mut pending: Option<&str> = None;
+        let mut point = 0;
+        let mut series_categories = Vec::new();
+
+        for token in Tokenizer::from(xml) {
+            match token.ok()? {
+                Token::ElementStart { local, .. } => {
+                    let name = local.as_str();
+                    if name == "ser" {
+                        chart.take_categories(&mut series_categories);
+                        chart.series.push(ChartSeries::default());
+                    }
+                    if name == "pt" {
+                        point = 0;
+                    }
+                    pending = Some(name);
+                }
+                Token::Attribute { local, value, .. }
+                    if pending == Some("pt") && local.as_str() == "idx" =>
+                {
+                    point = value.as_str().parse().unwrap_or(0);
+                }
+                Token::ElementEnd { end, .. } => match end {
+                    ElementEnd::Open => stack.extend(pending.take()),
+                    ElementEnd::Empty => pending = None,
+                    ElementEnd::Close(..) => {
+                        stack.pop();
+                    }
+                },
+                Token::Text { text } => {
+                    let text = unescape_xml(text.as_str());
+                    match stack.last() {
+                        Some(&"v") => match Self::series_part(&stack) {
+                            Some(SeriesPart::Name) => {
+                                if let Some(series) = chart.series.last_mut() {
+                                    series.name.push_str(&text);
+                                }
+                            }
+                            Some(SeriesPart::Category) => {
+                                set_point(&mut series_categories, point, text);
+                            }
+                            Some(SeriesPart::Value) => {
+                                if let Some(series) = chart.series.last_mut() {
+                                    set_point(&mut series.values, point, text);
+                                }
+                            }
+                            None => {}
+                        },
+                        Some(&"t") if Self::is_chart_title(&stack) => {
+                            chart.title.get_or_insert_with(String::new).push_str(&text);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        chart.take_categories(&mut series_categories);
+
+        (!chart.series.is_empty()).then_some(chart)
+    }
+
+    /// Keeps the first series' category labels as the shared categories.
+    fn take_categories(&mut self, series_categories: &mut Vec<String>) {
+        let categories = std::mem::take(series_categories);
+        if self.categories.is_empty() {
+            self.categories = categories;
+        }
+    }
+
+    /// Returns which part of a series the innermost element belongs to.
+    fn series_part(stack: &[&str]) -> Option<SeriesPart> {
+        let ser = stack.iter().rposition(|name| *name == "ser")?;
+        match *stack.get(ser + 1)? {
+            "tx" => Some(SeriesPart::Name),
+            "cat" | "xVal" => Some(SeriesPart::Category),
+            "val" | "yVal" => Some(SeriesPart::Value),
+            _ => None,
+        }
+    }
+
+    /// Returns true inside the chart's own title (not an axis title).
+    fn is_chart_title(stack: &[&str]) -> bool {
+        stack.windows(2).any(|pair| pair == ["chart", "title"])
+    }
+
+    /// Renders the data as an HTML table: categories as rows, series as columns.
+    pub fn to_html_table(&self, empty_cell: &str) -> String {
+        render_grid(
+            self.grid(escape_html_text),
+            self.title.as_deref(),
+            None,
+            &[],
+            empty_cell,
+            false,
+            false,
+        )
+    }
+
+    /// Renders the data as a Markdown pipe table, laid out like [`Self::to_html_table`].
+    pub fn to_pipe_table(&self) -> String {
+        render_pipe_table(self.grid(escape_html_text), self.title.as_deref())
+    }
+
+    /// Renders the data as plain text, tab-separated rows laid out like
+    /// [`Self::to_html_table`].
+    pub fn to_text_table(&self) -> String {
+        render_text_table(self.grid(str::to_string), self.title.as_deref())
+    }
+
+    /// Lays the data out on a grid, writing each value with `text`.
+    fn grid(&self, text: fn(&str) -> String) -> Vec<Vec<CellStatus>> {
+        let rows = self
+            .series
+            .iter()
+            .map(|series| series.values.len())
+            .chain([self.categories.len()])
+            .max()
+            .unwrap_or(0);
+
+        let mut header = vec![CellStatus::Empty];
+        header.extend(
+            self.series
+                .iter()
+                .map(|series| occupied(text(&series.name))),
+        );
+
+        let mut grid = vec![header];
+        for idx in 0..rows {
+            let label = match self.categories.get(idx) {
+                Some(label) if !label.is_empty() => label.clone(),
+                _ => (idx + 1).to_string(),
+            };
+            let mut row = vec![occupied(text(&label))];
+            row.extend(
+                self.series.iter().map(|series| {
+                    occupied(text(series.values.get(idx).map_or("", String::as_str)))
+                }),
+            );
+            grid.push(row);
+        }
+        grid
+    }
+}
+
+fn set_point(points: &mut Vec<String>, idx: usize, value: String) {
+    if points.len() <= idx {
+        points.resize(idx + 1, String::new());
+    }
+    if points[idx].is_empty() {
+        points[idx] = value;
+    }
+}
+
+fn occupied(content: String) -> CellStatus {
+    CellStatus::Occupied {
+        content,
+        style: None,
+        rowspan: 1,
+        colspan: 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHART: &str = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><c:chart>
+        <c:title><c:tx><c:rich><a:p><a:r><a:t>Sales &amp; costs</a:t></a:r></a:p></c:rich></c:tx></c:title>
+        <c:plotArea><c:barChart>
+            <c:ser>
+                <c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>Sales</c:v></c:pt></c:strCache></c:strRef></c:tx>
+                <c:dLbls><c:dLbl><c:tx><c:rich><a:p><a:r><a:t>label</a:t></a:r></a:p></c:rich></c:tx></c:dLbl></c:dLbls>
+                <c:cat><c:strRef><c:strCache><c:ptCount val="2"/><c:pt idx="0"><c:v>Q1</c:v></c:pt><c:pt idx="1"><c:v>Q2</c:v></c:pt></c:strCache></c:strRef></c:cat>
+                <c:val><c:numRef><c:numCache><c:ptCount val="2"/><c:pt idx="0"><c:v>4.3</c:v></c:pt><c:pt idx="1"><c:v>2.5</c:v></c:pt></c:numCache></c:numRef></c:val>
+            </c:ser>
+            <c:ser>
+                <c:tx><c:v>Costs</c:v></c:tx>
+                <c:val><c:numRef><c:numCache><c:pt idx="1"><c:v>1</c:v></c:pt></c:numCache></c:numRef></c:val>
+            </c:ser>
+            <c:valAx><c:title><c:tx><c:rich><a:p><a:r><a:t>USD</a:t></a:r></a:p></c:rich></c:tx></c:title></c:valAx>
+        </c:barChart></c:plotArea>
+    </c:chart></c:chartSpace>"#;
+
+    #[test]
+    fn test_parse_reads_cached_series_categories_and_title() {
+        let chart = ChartData::parse(CHART).expect("chart should have series");
+        assert_eq!(
+            chart,
+            ChartData {
+                title: Some("Sales & costs".to_string()),
+                categories: vec!["Q1".to_string(), "Q2".to_string()],
+                series: vec![
+                    ChartSeries {
+                        name: "Sales".to_string(),
+                        values: vec!["4.3".to_string(), "2.5".to_string()],
+                    },
+                    ChartSeries {
+                        name: "Costs".to_string(),
+                        values: vec![String::new(), "1".to_string()],
+                    },
+                ],
+            }
+        );
+        assert!(ChartData::parse("<c:chartSpace><c:chart/></c:chartSpace>").is_none());
+    }
+
+    #[test]
+    fn test_to_html_table_puts_categories_in_rows() {
+        let chart = ChartData::parse(CHART).expect("chart should have series");
+        assert_eq!(
+            chart.to_html_table("&nbsp;"),
+            "<table>\n  <caption>Sales &amp; costs</caption>\n\
+             \x20 <tr>\n    <td>&nbsp;</td>\n    <td>Sales</td>\n    <td>Costs</td>\n  </tr>\n\
+             \x20 <tr>\n    <td>Q1</td>\n    <td>4.3</td>\n    <td>&nbsp;</td>\n  </tr>\n\
+             \x20 <tr>\n    <td>Q2</td>\n    <td>2.5</td>\n    <td>1</td>\n  </tr>\n\
+             </table>"
+        );
+    }
+
+    #[test]
+    fn test_to_pipe_table_puts_categories_in_rows() {
+        let chart = ChartData::parse(CHART).expect("chart should have series");
+        assert_eq!(
+            chart.to_pipe_table(),
+            "*Sales & costs*\n\n\
+             |  | Sales | Costs |\n\
+             | --- | --- | --- |\n\
+             | Q1 | 4.3 |  |\n\
+             | Q2 | 2.5 | 1 |"
+        );
+    }
+}
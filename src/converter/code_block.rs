@@ -0,0 +1,102 @@
+//! Code blocks - renders monospace and verbatim paragraphs as fenced blocks.
+
+use crate::{CodeBlocks, CodeLineNumbers};
+
+/// A code block being written: its lines so far and how it is fenced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CodeBlock {
+    pub settings: CodeBlocks,
+    pub lines: Vec<String>,
+}
+
+impl CodeBlock {
+    pub fn render(&self) -> String {
+        render(&self.lines, &self.settings)
+    }
+}
+
+/// Renders lines of code as a fenced code block.
+///
+/// Trailing blank lines are dropped. The fence is three backticks, or one
+/// more than the longest run of backticks in the code.
+fn render(lines: &[String], settings: &CodeBlocks) -> String {
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    let lines = &lines[..end];
+
+    let longest_backticks = lines
+        .iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat((longest_backticks + 1).max(3));
+
+    let mut info = settings.language.clone().unwrap_or_default();
+    if settings.line_numbers == CodeLineNumbers::InfoString {
+        if !info.is_empty() {
+            info.push(' ');
+        }
+        info.push_str("{.numberLines}");
+    }
+
+    let mut block = format!("{}{}\n", fence, info);
+    let width = lines.len().to_string().len();
+    for (n, line) in lines.iter().enumerate() {
+        if settings.line_numbers == CodeLineNumbers::Prefix {
+            block.push_str(&format!("{:>width$}  ", n + 1, width = width));
+        }
+        block.push_str(line);
+        block.push('\n');
+    }
+    block.push_str(&fence);
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(code: &[&str]) -> Vec<String> {
+        code.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_code_lines_are_fenced_and_numbered() {
+        let code = lines(&["fn main() {", "    run();", "}", "", ""]);
+        let settings = CodeBlocks {
+            language: Some("rust".to_string()),
+            line_numbers: CodeLineNumbers::None,
+        };
+        assert_eq!(
+            render(&code, &settings),
+            "```rust\nfn main() {\n    run();\n}\n```"
+        );
+
+        let settings = CodeBlocks {
+            line_numbers: CodeLineNumbers::InfoString,
+            ..settings
+        };
+        assert!(render(&code, &settings).starts_with("```rust {.numberLines}\n"));
+
+        let code: Vec<String> = (1..=10).map(|n| format!("line {n}")).collect();
+        let settings = CodeBlocks {
+            language: None,
+            line_numbers: CodeLineNumbers::Prefix,
+        };
+        let block = render(&code, &settings);
+        assert!(block.starts_with("```\n 1  line 1\n 2  line 2\n"));
+        assert!(block.ends_with("\n10  line 10\n```"));
+    }
+
+    #[test]
+    fn test_fence_is_longer_than_backticks_in_the_code() {
+        let code = lines(&["```", "quoted"]);
+        assert_eq!(
+            render(&code, &CodeBlocks::default()),
+            "````\n```\nquoted\n````"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Comment status - finds the comments Word marked resolved.
+//!
+//! rs_docx parses neither `w14:paraId` nor `word/commentsExtended.xml`, where
+//! Word marks a comment done (`w15:commentEx w15:done="1"`). Each
+//! `w15:commentEx` names the `w14:paraId` of a comment's last paragraph; a
+//! reply (`w15:paraIdParent`) is resolved with the comment it answers.
+
+use std::collections::{HashMap, HashSet};
+use xmlparser::{Token, Tokenizer};
+
+/// Returns the ids of the resolved comments, given the XML of
+/// `word/comments.xml` and `word/commentsExtended.xml`.
+///
+/// Malformed XML ends the scan; what was read up to there is kept.
+pub(crate) fn resolved_comment_ids(comments_xml: &str, extended_xml: &str) -> HashSet<String> {
+    // `w15:paraId` -> (`w15:paraIdParent`, `w15:done`)
+    let mut status: HashMap<&str, (Option<&str>, bool)> = HashMap::new();
+    for (element, attributes) in elements(extended_xml) {
+        if element != "commentEx" {
+            continue;
+        }
+        let attribute = |name| {
+            attributes
+                .iter()
+                .find(|(attribute, _)| *attribute == name)
+                .map(|(_, value)| *value)
+        };
+        if let Some(para) = attribute("paraId") {
+            let done = attribute("done").is_some_and(|done| matches!(done, "1" | "true" | "on"));
+            status.insert(para, (attribute("paraIdParent"), done));
+        }
+    }
+    let resolved = |para: &str| {
+        status.get(para).is_some_and(|(parent, done)| {
+            *done
+                || parent
+                    .and_then(|parent| status.get(parent))
+                    .is_some_and(|(_, done)| *done)
+        })
+    };
+
+    // Comment id -> `w14:paraId` of its last paragraph.
+    let mut last_paragraphs: HashMap<&str, &str> = HashMap::new();
+    let mut comment = None;
+    for (element, attributes) in elements(comments_xml) {
+        for (name, value) in attributes {
+            match (element, name) {
+                ("comment", "id") => comment = Some(value),
+                ("p", "paraId") => {
+                    if let Some(id) = comment {
+                        last_paragraphs.insert(id, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    last_paragraphs
+        .into_iter()
+        .filter(|(_, para)| resolved(para))
+        .map(|(id, _)| id.to_string())
+        .collect()
+}
+
+/// Returns the local names of the elements in `xml` with the local names and
+/// values of their attributes, up to the first malformed token.
+fn elements(xml: &str) -> Vec<(&str, Vec<(&str, &str)>)> {
+    let mut elements: Vec<(&str, Vec<(&str, &str)>)> = Vec::new();
+    for token in Tokenizer::from(xml).map_while(Result::ok) {
+        match token {
+            Token::ElementStart { local, .. } => elements.push((local.as_str(), Vec::new())),
+            Token::Attribute { local, value, .. } => {
+                if let Some((_, attributes)) = elements.last_mut() {
+                    attributes.push((local.as_str(), value.as_str()));
+                }
+            }
+            _ => {}
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_threads_are_found_by_paragraph_id() {
+        let comments = r#"<w:comments xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml">
+            <w:comment w:id="0" w:author="A"><w:p w14:paraId="0A000001"><w:r><w:t>First line</w:t></w:r></w:p><w:p w14:paraId="0A000002"><w:r><w:t>Fixed?</w:t></w:r></w:p></w:comment>
+            <w:comment w:id="1" w:author="B"><w:p w14:paraId="0B000001"><w:r><w:t>Yes</w:t></w:r></w:p></w:comment>
+            <w:comment w:id="2" w:author="A"><w:p w14:paraId="0C000001"><w:r><w:t>Still open</w:t></w:r></w:p></w:comment>
+        </w:comments>"#;
+        let extended = r#"<w15:commentsEx xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml">
+            <w15:commentEx w15:paraId="0A000002" w15:done="1"/>
+            <w15:commentEx w15:paraId="0B000001" w15:paraIdParent="0A000002" w15:done="0"/>
+            <w15:commentEx w15:paraId="0C000001" w15:done="0"/>
+        </w15:commentsEx>"#;
+
+        let resolved = resolved_comment_ids(comments, extended);
+        assert_eq!(resolved, HashSet::from(["0".to_string(), "1".to_string()]));
+    }
+}
@@ -1,6 +1,37 @@
-use super::{ImageExtractor, NumberingResolver, StyleResolver};
+use super::bibliography::BibliographyEntries;
+use super::chart::ChartData;
+use super::figure::{self, Figures};
+use super::glossary;
+use super::index::IndexEntries;
+use super::nesting;
+use super::raw_xml::{
+    RawCellProperties, RawChart, RawDocumentIndex, RawEquation, RawFormField, RawRuby, RawSmartTag,
+    RawTableProperties, RawVmlImage,
+};
+use super::repeated_headings::RepeatedHeadingRuns;
+use super::slug::HeadingSlugs;
+use super::symbols::SymbolOverrides;
+use super::{
+    CodeBlock, ConversionWarning, ImageExtractor, ListMarker, NumberingResolver,
+    ParagraphConverter, StyleResolver, TableConverter,
+};
 use crate::core::ast::ReferenceDefinitions;
-use crate::{ConvertOptions, Result};
+use crate::render::{
+    escape_html_attr, escape_html_text, escape_markdown_extended, escape_markdown_inline,
+};
+use crate::{
+    Callout, ChartHandling, ConvertOptions, ExtendedSyntaxEscaping, FigureSyntax, FootnoteStyle,
+    HeadingIdStyle, HiddenText, IndexHandling, LeadingTab, LineBreak, ListLabels, MathFallback,
+    OrderedListDelimiter, PositionalTabs, RepeatedHeadings, ResolvedComments, Result, RubyText,
+    SingleCellTables, SlugFn, SmartTags, TextFramePosition, TextFrames, TextTransform, TocHandling,
+    UnderlineHandling,
+};
+use hard_xml::XmlRead;
+use rs_docx::document::{
+    Body, BodyContent, PTab, PTabAlignment, Paragraph, Run, Table, TableCell, SDT,
+};
+use rs_docx::formatting::{ParagraphProperty, UnderlineStyle};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 /// Context passed through conversion for shared mutable state.
@@ -11,15 +42,105 @@ pub struct ConversionContext<'a> {
     options: &'a ConvertOptions,
     style_resolver: &'a StyleResolver<'a>,
     footnotes: Vec<String>,
-    footnote_index_by_id: HashMap<isize, usize>,
-    footnote_text_by_id: HashMap<isize, String>,
+    /// Marker number of each footnote, in the order of `footnotes`.
+    footnote_numbers: Vec<usize>,
+    footnote_number_by_id: HashMap<isize, usize>,
+    /// Marker number of the first footnote with each text, with
+    /// `merge_identical_footnotes`.
+    footnote_number_by_text: HashMap<String, usize>,
+    footnote_content_by_id: HashMap<isize, &'a [BodyContent<'a>]>,
     endnotes: Vec<String>,
-    endnote_index_by_id: HashMap<isize, usize>,
-    endnote_text_by_id: HashMap<isize, String>,
+    endnote_numbers: Vec<usize>,
+    endnote_number_by_id: HashMap<isize, usize>,
+    /// Ids of the separator notes, `(is_endnote, id)`; references to them
+    /// are skipped.
+    separator_note_ids: HashSet<(bool, isize)>,
+    endnote_content_by_id: HashMap<isize, &'a [BodyContent<'a>]>,
+    /// Registered notes whose content is still to be converted: whether the
+    /// note is in `endnotes`, its index there, and its content.
+    pending_notes: Vec<(bool, usize, &'a [BodyContent<'a>])>,
     comments: Vec<(String, String)>,
     seen_comment_ids: HashSet<String>,
     comment_text_by_id: HashMap<String, String>,
+    resolved_comments: HashSet<String>,
     missing_references: Vec<String>,
+    default_lang: Option<rs_docx::formatting::Lang<'a>>,
+    raw_index: RawDocumentIndex,
+    /// The raw main document part, which smart tag runs are parsed from.
+    raw_document: &'a str,
+    /// Building blocks of the glossary document keyed by name.
+    glossary: Option<&'a HashMap<String, String>>,
+    chart_parts: HashMap<String, String>,
+    pending_drop_cap: Option<String>,
+    /// Paragraphs of the text frame being collected, written once it ends.
+    pending_text_frame: Vec<String>,
+    /// Whether the paragraph being converted is a list item.
+    in_list_item: bool,
+    heading_slugs: HeadingSlugs,
+    repeated_headings: RepeatedHeadingRuns,
+    /// Number of HTML table cells being converted (nested tables nest cells).
+    html_cell_depth: usize,
+    /// Whether the output is plain text, without any Markdown or HTML markup.
+    plain_text: bool,
+    figures: Figures,
+    index_entries: IndexEntries,
+    bibliography: BibliographyEntries,
+    /// Whether the regenerated index has been written.
+    index_written: bool,
+    /// Images of a captioned figure, waiting for the caption paragraph.
+    pending_figure: Option<String>,
+    /// Number of Pandoc figures written, for their `#fig:n` ids.
+    figure_count: usize,
+    /// Index of the last block written as a callout, and its callout.
+    last_callout: Option<(usize, Callout)>,
+    /// Index of the last block ending with a list item, and that item's spacing.
+    last_list_item: Option<(usize, ListItemSpacing)>,
+    /// Index of the last block written as a code block, and that block.
+    last_code_block: Option<(usize, CodeBlock)>,
+    /// Level, counter value (`None` for bullets) and written prefix
+    /// (indentation and marker) of the last list item converted.
+    last_list_marker: Option<(usize, Option<i32>, String)>,
+}
+
+/// Paragraph spacing of a list item, deciding whether it is separated from
+/// the neighbouring items (a loose list) or not (a tight list).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ListItemSpacing {
+    style_id: Option<String>,
+    /// `w:contextualSpacing`: no spacing next to paragraphs of the same style.
+    contextual: bool,
+    /// Whether the item has space before it.
+    before: bool,
+    /// Whether the item has space after it.
+    after: bool,
+}
+
+impl ListItemSpacing {
+    fn new(props: &rs_docx::formatting::ParagraphProperty<'_>) -> Self {
+        let spacing = props.spacing.clone().unwrap_or_default();
+        let positive = |value: Option<isize>| value.is_some_and(|value| value > 0);
+        Self {
+            style_id: props.style_id.as_ref().map(|s| s.value.to_string()),
+            contextual: props
+                .contextual_spacing
+                .as_ref()
+                .is_some_and(|c| c.value != Some(false)),
+            before: spacing.before_auto_spacing == Some(true)
+                || positive(spacing.before)
+                || positive(spacing.before_lines),
+            after: spacing.after_auto_spacing == Some(true)
+                || positive(spacing.after)
+                || positive(spacing.after_lines),
+        }
+    }
+
+    /// Whether no space separates this item from the `next` one.
+    pub fn is_tight_with(&self, next: &Self) -> bool {
+        let same_style = self.style_id == next.style_id;
+        let after = self.after && !(self.contextual && same_style);
+        let before = next.before && !(next.contextual && same_style);
+        !after && !before
+    }
 }
 
 impl<'a> ConversionContext<'a> {
@@ -48,55 +169,33 @@ impl<'a> ConversionContext<'a> {
             })
             .unwrap_or_default();
 
-        let footnote_text_by_id = docx_footnotes
-            .map(|footnotes| {
-                footnotes
-                    .content
-                    .iter()
-                    .filter_map(|footnote| {
-                        footnote.id.map(|id| {
-                            let text = footnote
-                                .content
-                                .iter()
-                                .filter_map(|bc| match bc {
-                                    rs_docx::document::BodyContent::Paragraph(p) => {
-                                        Some(p.text().to_string())
-                                    }
-                                    _ => None,
-                                })
-                                .collect::<Vec<_>>()
-                                .join(" ");
-                            (id, text)
-                        })
-                    })
-                    .collect::<HashMap<_, _>>()
-            })
-            .unwrap_or_default();
+        // Separators (`w:type`) are page furniture rather than notes.
+        let footnote_separators = docx_footnotes
+            .into_iter()
+            .flat_map(|footnotes| footnotes.content.iter())
+            .filter(|footnote| footnote.ty.is_some())
+            .filter_map(|footnote| footnote.id.map(|id| (false, id)));
+        let endnote_separators = docx_endnotes
+            .into_iter()
+            .flat_map(|endnotes| endnotes.content.iter())
+            .filter(|endnote| endnote.ty.is_some())
+            .filter_map(|endnote| endnote.id.map(|id| (true, id)));
+        let separator_note_ids = footnote_separators.chain(endnote_separators).collect();
 
-        let endnote_text_by_id = docx_endnotes
-            .map(|endnotes| {
-                endnotes
-                    .content
-                    .iter()
-                    .filter_map(|endnote| {
-                        endnote.id.map(|id| {
-                            let text = endnote
-                                .content
-                                .iter()
-                                .filter_map(|bc| match bc {
-                                    rs_docx::document::BodyContent::Paragraph(p) => {
-                                        Some(p.text().to_string())
-                                    }
-                                    _ => None,
-                                })
-                                .collect::<Vec<_>>()
-                                .join(" ");
-                            (id, text)
-                        })
-                    })
-                    .collect::<HashMap<_, _>>()
-            })
-            .unwrap_or_default();
+        let footnote_content_by_id = docx_footnotes
+            .into_iter()
+            .flat_map(|footnotes| footnotes.content.iter())
+            .filter(|footnote| footnote.ty.is_none())
+            .filter_map(|footnote| footnote.id.map(|id| (id, footnote.content.as_slice())))
+            .collect();
+        let endnote_content_by_id = docx_endnotes
+            .into_iter()
+            .flat_map(|endnotes| endnotes.content.iter())
+            .filter(|endnote| endnote.ty.is_none())
+            .filter_map(|endnote| endnote.id.map(|id| (id, endnote.content.as_slice())))
+            .collect();
+
+        let default_lang = style_resolver.resolve_run_property(None, None, None).lang;
 
         Self {
             rels,
@@ -105,72 +204,448 @@ impl<'a> ConversionContext<'a> {
             options,
             style_resolver,
             footnotes: Vec::new(),
-            footnote_index_by_id: HashMap::new(),
-            footnote_text_by_id,
+            footnote_numbers: Vec::new(),
+            footnote_number_by_id: HashMap::new(),
+            footnote_number_by_text: HashMap::new(),
+            footnote_content_by_id,
             endnotes: Vec::new(),
-            endnote_index_by_id: HashMap::new(),
-            endnote_text_by_id,
+            endnote_numbers: Vec::new(),
+            endnote_number_by_id: HashMap::new(),
+            endnote_content_by_id,
+            pending_notes: Vec::new(),
+            separator_note_ids,
             comments: Vec::new(),
             seen_comment_ids: HashSet::new(),
             comment_text_by_id,
+            resolved_comments: HashSet::new(),
             missing_references: Vec::new(),
+            default_lang,
+            raw_index: RawDocumentIndex::default(),
+            raw_document: "",
+            glossary: None,
+            chart_parts: HashMap::new(),
+            pending_drop_cap: None,
+            pending_text_frame: Vec::new(),
+            in_list_item: false,
+            heading_slugs: HeadingSlugs::default(),
+            repeated_headings: RepeatedHeadingRuns::default(),
+            html_cell_depth: 0,
+            plain_text: false,
+            figures: Figures::default(),
+            index_entries: IndexEntries::default(),
+            bibliography: BibliographyEntries::default(),
+            index_written: false,
+            pending_figure: None,
+            figure_count: 0,
+            last_callout: None,
+            last_list_item: None,
+            last_code_block: None,
+            last_list_marker: None,
+        }
+    }
+
+    /// Attaches properties recovered from the raw document part.
+    pub(crate) fn set_raw_index(&mut self, raw_index: RawDocumentIndex) {
+        self.image_extractor
+            .set_svg_blips(raw_index.svg_blips().clone());
+        self.raw_index = raw_index;
+    }
+
+    /// Attaches the raw main document part, for the runs rs_docx drops with
+    /// the smart tags wrapping them.
+    pub(crate) fn set_raw_document(&mut self, xml: &'a str) {
+        self.raw_document = xml;
+    }
+
+    /// Converts to plain text: document text is written as it is, without
+    /// escapes, formatting, link targets or any other markup.
+    pub(crate) fn set_plain_text(&mut self, plain_text: bool) {
+        self.plain_text = plain_text;
+        self.image_extractor.set_plain_text(plain_text);
+    }
+
+    /// Returns true when the output is plain text, see [`Self::set_plain_text`].
+    pub fn plain_text(&self) -> bool {
+        self.plain_text
+    }
+
+    pub(crate) fn set_glossary(&mut self, glossary: &'a HashMap<String, String>) {
+        self.glossary = Some(glossary);
+    }
+
+    /// Parses the building block an empty content control takes its
+    /// placeholder content from (`w:placeholder/w:docPart`), if any.
+    ///
+    /// Building blocks are not part of the body measured before conversion,
+    /// so each is held to `max_nesting_depth` on its own.
+    pub(crate) fn building_block(&self, sdt: &SDT<'_>) -> Result<Option<Body<'a>>> {
+        if !glossary::is_empty(sdt) {
+            return Ok(None);
+        }
+        let block = self
+            .raw_index
+            .sdt(sdt)
+            .and_then(|raw| raw.doc_part.as_deref())
+            .and_then(|name| self.glossary?.get(name))
+            .and_then(|xml| Body::from_str(xml).ok());
+        if let Some(block) = &block {
+            nesting::check(
+                std::iter::once(block.content.as_slice()),
+                self.options.max_nesting_depth,
+            )?;
+        }
+        Ok(block)
+    }
+
+    /// Attaches the raw chart parts (`word/charts/*.xml`) keyed by archive path.
+    pub(crate) fn set_chart_parts(&mut self, chart_parts: HashMap<String, String>) {
+        self.chart_parts = chart_parts;
+    }
+
+    /// Assigns heading slugs for the body when `slug_fn` is set or heading
+    /// ids are written as attributes.
+    pub fn index_heading_slugs(&mut self, body: &[BodyContent<'a>]) {
+        if let Some(slug_fn) = &self.options.slug_fn {
+            self.heading_slugs = HeadingSlugs::build(body, self, slug_fn);
+        } else if self.options.heading_id_style == HeadingIdStyle::Attribute {
+            self.heading_slugs = HeadingSlugs::build(body, self, &SlugFn::default());
+        }
+    }
+
+    /// Finds the runs of repeated headings unless `repeated_headings` is
+    /// `RepeatedHeadings::Keep`. Runs before `index_heading_slugs`, so merged
+    /// headings take the slug of the heading they merge into.
+    pub fn index_repeated_headings(&mut self, body: &[BodyContent<'a>]) {
+        if self.options.repeated_headings != RepeatedHeadings::Keep {
+            self.repeated_headings = RepeatedHeadingRuns::build(body, self);
         }
     }
 
+    /// Returns true when a heading is dropped because it repeats the heading
+    /// before it, with `RepeatedHeadings::Merge`.
+    pub(crate) fn is_merged_heading(&self, para: &Paragraph<'_>) -> bool {
+        self.options.repeated_headings == RepeatedHeadings::Merge
+            && self.repeated_headings.is_repeat(para)
+    }
+
+    /// Reports the run of repeated headings a heading starts, if any.
+    pub(crate) fn warn_repeated_heading(&mut self, para: &Paragraph<'_>) {
+        if let Some(warning) = self.repeated_headings.run(para) {
+            self.image_extractor.warn(warning.clone());
+        }
+    }
+
+    /// Pairs image-only paragraphs with their captions when `figure_captions`
+    /// is set.
+    pub fn index_figures(&mut self, body: &[BodyContent<'a>]) {
+        let pandoc = self.options.figure_syntax == FigureSyntax::Pandoc;
+        if self.options.figure_captions && (!self.no_html() || pandoc) {
+            self.figures = Figures::build(body, self);
+        }
+    }
+
+    /// Numbers the index entry (`XE`) fields for `IndexHandling::Links`.
+    pub fn index_index_entries(&mut self, body: &[BodyContent<'a>]) {
+        if self.options.index == IndexHandling::Links {
+            self.index_entries = IndexEntries::build(body);
+        }
+    }
+
+    /// Finds the bibliography entries when `bibliography_list` is set.
+    pub fn index_bibliography(&mut self, body: &[BodyContent<'a>]) {
+        if self.options.bibliography_list {
+            self.bibliography = BibliographyEntries::build(body);
+        }
+    }
+
+    /// Returns true when a paragraph is a source of a bibliography written
+    /// as a list item.
+    pub(crate) fn is_bibliography_entry(&self, para: &Paragraph<'_>) -> bool {
+        self.bibliography.contains(para)
+    }
+
+    /// Returns the anchor written for the `n`th index entry of a paragraph.
+    pub fn index_entry_anchor(&self, para: &Paragraph<'_>, n: usize) -> Option<&str> {
+        if self.no_html() {
+            return None;
+        }
+        self.index_entries.anchor(para, n)
+    }
+
+    /// Returns true when a paragraph belongs to a generated index (`INDEX`
+    /// field result), which `index` replaces.
+    pub fn is_generated_index(&self, para: &Paragraph<'_>) -> bool {
+        if self.options.index == IndexHandling::Keep {
+            return false;
+        }
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        self.style_resolver.is_index_style(style_id)
+    }
+
+    /// Returns the regenerated index for the first paragraph of a generated
+    /// index with `IndexHandling::Links`, and `None` for the others.
+    pub fn take_generated_index(&mut self) -> Option<String> {
+        if self.options.index != IndexHandling::Links || self.index_written {
+            return None;
+        }
+        self.index_written = true;
+        Some(self.index_entries.render(!self.no_html()))
+    }
+
+    /// Returns true when an image-only paragraph is followed by its caption.
+    pub fn has_figure_caption(&self, para: &Paragraph<'_>) -> bool {
+        self.figures.has_caption(para)
+    }
+
+    /// Returns true when a paragraph is the caption of the image paragraph
+    /// before it.
+    pub fn is_figure_caption(&self, para: &Paragraph<'_>) -> bool {
+        self.figures.is_caption(para)
+    }
+
+    /// Returns true when a paragraph's style, or a style it is based on, is
+    /// the built-in `Caption` style.
+    pub fn is_caption(&self, para: &Paragraph<'_>) -> bool {
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        self.style_resolver.has_style(style_id, "Caption")
+    }
+
+    pub fn push_figure(&mut self, images: String) {
+        self.pending_figure = Some(images);
+    }
+
+    pub fn take_figure(&mut self) -> Option<String> {
+        self.pending_figure.take()
+    }
+
+    /// Writes a figure's image with its caption as a Pandoc implicit figure,
+    /// numbered for `pandoc-crossref`. Returns `None` without
+    /// `FigureSyntax::Pandoc`, in plain text, or when the figure is not a
+    /// single image.
+    pub fn pandoc_figure(&mut self, images: &str, caption: &str) -> Option<String> {
+        if self.options.figure_syntax != FigureSyntax::Pandoc || self.plain_text {
+            return None;
+        }
+        let source = figure::image_source(images)?;
+        self.figure_count += 1;
+        Some(format!(
+            "![{}]({}){{#fig:{}}}",
+            caption, source, self.figure_count
+        ))
+    }
+
+    /// Returns the slug of a heading paragraph.
+    pub fn heading_slug(&self, para: &Paragraph<'_>) -> Option<&str> {
+        self.heading_slugs.paragraph(para)
+    }
+
+    /// Returns the slug of the heading a bookmark belongs to.
+    pub fn bookmark_slug(&self, name: &str) -> Option<&str> {
+        self.heading_slugs.bookmark(name)
+    }
+
+    /// Records the ids of the comments marked resolved in Word.
+    pub(crate) fn set_resolved_comments(&mut self, ids: HashSet<String>) {
+        self.resolved_comments = ids;
+    }
+
+    /// Registers a comment reference and returns its marker; empty for a
+    /// resolved comment with `ResolvedComments::Skip`.
     pub fn register_comment_reference(&mut self, id: &str) -> String {
+        let resolved = self.resolved_comments.contains(id);
+        if resolved && self.options.resolved_comments == ResolvedComments::Skip {
+            return String::new();
+        }
         if !self.seen_comment_ids.contains(id) {
-            let comment_text = self.comment_text_by_id.get(id).cloned().unwrap_or_else(|| {
+            let mut comment_text = self.comment_text_by_id.get(id).cloned().unwrap_or_else(|| {
                 self.missing_references.push(format!("comment:{id}"));
                 String::new()
             });
+            if resolved && self.options.resolved_comments == ResolvedComments::Annotate {
+                comment_text.insert_str(0, "(resolved) ");
+            }
 
             self.comments.push((id.to_string(), comment_text));
             self.seen_comment_ids.insert(id.to_string());
         }
 
-        format!("[^c{}]", id)
+        self.note_marker(self.options.note_markers.comment(id))
     }
 
     pub fn register_footnote_reference(&mut self, id: isize) -> String {
-        if let Some(idx) = self.footnote_index_by_id.get(&id).copied() {
-            return format!("[^{}]", idx);
+        if self.options.footnote_style == FootnoteStyle::Inline {
+            return self.inline_footnote(id);
+        }
+
+        if let Some(n) = self.footnote_number_by_id.get(&id).copied() {
+            return self.note_marker(self.options.note_markers.footnote(n));
+        }
+        if self.separator_note_ids.contains(&(false, id)) {
+            return String::new();
         }
 
-        let footnote_text = self
-            .footnote_text_by_id
-            .get(&id)
-            .cloned()
-            .unwrap_or_else(|| {
+        let content = self.footnote_content_by_id.get(&id).copied();
+        let text = content.map(note_text).unwrap_or_default();
+        if self.options.merge_identical_footnotes {
+            if let Some(n) = self.footnote_number_by_text.get(&text).copied() {
+                self.footnote_number_by_id.insert(id, n);
+                return self.note_marker(self.options.note_markers.footnote(n));
+            }
+        }
+        match content {
+            Some(content) => {
+                self.footnotes.push(text.clone());
+                self.pending_notes
+                    .push((false, self.footnotes.len() - 1, content));
+            }
+            None => {
                 self.missing_references.push(format!("footnote:{id}"));
-                String::new()
-            });
+                self.footnotes.push(String::new());
+            }
+        }
+        let n = self.note_number(id, self.footnotes.len());
+        self.footnote_numbers.push(n);
+        self.footnote_number_by_id.insert(id, n);
+        if self.options.merge_identical_footnotes && !text.is_empty() {
+            self.footnote_number_by_text.insert(text, n);
+        }
+
+        self.note_marker(self.options.note_markers.footnote(n))
+    }
 
-        self.footnotes.push(footnote_text);
-        let idx = self.footnotes.len();
-        self.footnote_index_by_id.insert(id, idx);
+    /// Returns a note marker as written in the text: in plain text without
+    /// the `^` of Markdown footnote syntax, `[1]` for `[^1]`.
+    fn note_marker(&self, marker: String) -> String {
+        if self.plain_text {
+            marker.replacen("[^", "[", 1)
+        } else {
+            marker
+        }
+    }
 
-        format!("[^{}]", idx)
+    /// Returns the marker number of a note: its id with `preserve_note_ids`,
+    /// else its position in the sequence.
+    fn note_number(&self, id: isize, position: usize) -> usize {
+        if self.options.preserve_note_ids {
+            usize::try_from(id).unwrap_or(position)
+        } else {
+            position
+        }
     }
 
+    /// Renders a footnote in place as ` (note text)`.
+    fn inline_footnote(&mut self, id: isize) -> String {
+        let Some(content) = self.footnote_content_by_id.get(&id) else {
+            let missing = format!("footnote:{id}");
+            if !self.missing_references.contains(&missing) {
+                self.missing_references.push(missing);
+            }
+            return String::new();
+        };
+
+        let text = note_text(content);
+        let text = if self.plain_text {
+            text
+        } else {
+            escape_markdown_inline(&text)
+        };
+        if text.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", text)
+        }
+    }
+
+    /// Registers an endnote reference. With `unify_notes` the endnote is
+    /// numbered in the footnote sequence and gets a footnote marker.
     pub fn register_endnote_reference(&mut self, id: isize) -> String {
-        if let Some(idx) = self.endnote_index_by_id.get(&id).copied() {
-            return format!("[^en{}]", idx);
+        let unify = self.options.unify_notes;
+        if let Some(n) = self.endnote_number_by_id.get(&id).copied() {
+            return self.endnote_marker(n, unify);
+        }
+        if self.separator_note_ids.contains(&(true, id)) {
+            return String::new();
         }
 
-        let endnote_text = self
-            .endnote_text_by_id
-            .get(&id)
-            .cloned()
-            .unwrap_or_else(|| {
-                self.missing_references.push(format!("endnote:{id}"));
-                String::new()
-            });
+        let content = self.endnote_content_by_id.get(&id).copied();
+        if content.is_none() {
+            self.missing_references.push(format!("endnote:{id}"));
+        }
+
+        let notes = if unify {
+            &mut self.footnotes
+        } else {
+            &mut self.endnotes
+        };
+        notes.push(content.map(note_text).unwrap_or_default());
+        let position = notes.len();
+        if let Some(content) = content {
+            self.pending_notes.push((!unify, position - 1, content));
+        }
+        let n = self.note_number(id, position);
+        let numbers = if unify {
+            &mut self.footnote_numbers
+        } else {
+            &mut self.endnote_numbers
+        };
+        numbers.push(n);
+        self.endnote_number_by_id.insert(id, n);
 
-        self.endnotes.push(endnote_text);
-        let idx = self.endnotes.len();
-        self.endnote_index_by_id.insert(id, idx);
+        self.endnote_marker(n, unify)
+    }
 
-        format!("[^en{}]", idx)
+    fn endnote_marker(&self, n: usize, unify: bool) -> String {
+        self.note_marker(if unify {
+            self.options.note_markers.footnote(n)
+        } else {
+            self.options.note_markers.endnote(n)
+        })
+    }
+
+    /// Converts the content of the registered notes with the paragraph and
+    /// table converters, replacing the plain text they were registered with.
+    /// Notes referenced from within notes are converted too.
+    pub fn convert_notes(&mut self) -> Result<()> {
+        let mut next = 0;
+        while let Some(&(endnote, index, content)) = self.pending_notes.get(next) {
+            next += 1;
+            let converted = self.convert_note(content)?;
+            let notes = if endnote {
+                &mut self.endnotes
+            } else {
+                &mut self.footnotes
+            };
+            notes[index] = converted;
+        }
+        self.pending_notes.clear();
+        Ok(())
+    }
+
+    /// Converts a note's content. Paragraphs and tables are blocks of their
+    /// own, separated by blank lines; the note definition indents the blocks
+    /// after the first, the multi-paragraph footnote form of GFM and Pandoc.
+    fn convert_note(&mut self, content: &'a [BodyContent<'a>]) -> Result<String> {
+        let mut blocks = Vec::new();
+        for item in content {
+            let converted = match item {
+                BodyContent::Paragraph(para) => ParagraphConverter::convert(para, self)?,
+                BodyContent::Table(table) => TableConverter::convert(table, self)?,
+                _ => continue,
+            };
+            let converted = converted.trim();
+            if !converted.is_empty() {
+                blocks.push(converted.to_string());
+            }
+        }
+        Ok(blocks.join("\n\n"))
     }
 
     pub fn reference_definitions(&self) -> ReferenceDefinitions {
@@ -178,6 +653,9 @@ impl<'a> ConversionContext<'a> {
             footnotes: self.footnotes.clone(),
             endnotes: self.endnotes.clone(),
             comments: self.comments.clone(),
+            markers: self.options.note_markers.clone(),
+            footnote_numbers: self.footnote_numbers.clone(),
+            endnote_numbers: self.endnote_numbers.clone(),
         }
     }
 
@@ -193,6 +671,11 @@ impl<'a> ConversionContext<'a> {
             .extract_from_drawing(drawing, self.rels)
     }
 
+    /// Returns the targets of the unreadable media parts found so far.
+    pub(crate) fn corrupt_media(&self) -> Vec<&str> {
+        self.image_extractor.corrupt_media()
+    }
+
     pub fn extract_image_from_pict(
         &mut self,
         pict: &rs_docx::document::Pict,
@@ -210,6 +693,15 @@ impl<'a> ConversionContext<'a> {
             .resolve_run_property(direct_props, run_style_id, para_style_id)
     }
 
+    /// Returns true when the run's effective font (direct, style, or default) is monospace.
+    pub fn effective_monospace(
+        &self,
+        run: &rs_docx::document::Run<'a>,
+        para_style_id: Option<&str>,
+    ) -> bool {
+        self.style_resolver.effective_monospace(run, para_style_id)
+    }
+
     pub fn resolve_paragraph_property(
         &self,
         direct_props: Option<&rs_docx::formatting::ParagraphProperty<'a>>,
@@ -219,10 +711,627 @@ impl<'a> ConversionContext<'a> {
             .resolve_paragraph_property(direct_props, para_style_id)
     }
 
+    pub(crate) fn raw_table_properties(&self, table: &Table<'_>) -> Option<&RawTableProperties> {
+        self.raw_index.table(table)
+    }
+
+    /// Returns the properties rs_docx drops from a table cell's `w:tcPr`.
+    pub(crate) fn raw_cell_properties(&self, cell: &TableCell<'_>) -> Option<&RawCellProperties> {
+        self.raw_index.cell(cell)
+    }
+
+    /// Returns true when the paragraph is a drop-cap frame (`w:framePr w:dropCap`).
+    pub fn is_drop_cap(&self, para: &Paragraph<'_>) -> bool {
+        self.raw_index.paragraph(para).is_some_and(|p| p.drop_cap)
+    }
+
+    /// Returns the column count of the section the paragraph ends, if it
+    /// carries a `w:sectPr`.
+    pub fn section_columns(&self, para: &Paragraph<'_>) -> Option<u32> {
+        self.raw_index
+            .paragraph(para)
+            .and_then(|p| p.section_columns)
+    }
+
+    /// Returns the column count of the last section of the body.
+    pub fn final_section_columns(&self) -> Option<u32> {
+        self.raw_index.final_section_columns()
+    }
+
+    pub fn annotate_columns(&self) -> bool {
+        self.options.annotate_columns && !self.no_html()
+    }
+
+    pub fn page_boundaries(&self) -> bool {
+        self.options.page_boundaries
+    }
+
+    /// Returns the equations rs_docx dropped from the paragraph.
+    pub(crate) fn paragraph_equations(&self, para: &Paragraph<'_>) -> Vec<RawEquation> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.equations.clone())
+            .unwrap_or_default()
+    }
+
+    /// Renders an equation according to `math_fallback`.
+    pub(crate) fn render_equation(&mut self, equation: &RawEquation) -> String {
+        if self.options.warn_unsupported_math {
+            for construct in &equation.constructs {
+                self.image_extractor
+                    .warn(ConversionWarning::UnsupportedMath {
+                        construct: construct.clone(),
+                    });
+            }
+        }
+        let text = equation.text.trim();
+        let alt = equation.alt.as_deref().map(str::trim);
+        let placeholder = match alt {
+            Some(alt) if self.plain_text => format!("[equation: {}]", alt),
+            Some(alt) => format!("[equation: {}]", escape_markdown_inline(alt)),
+            None => "[equation]".to_string(),
+        };
+        match self.options.math_fallback {
+            MathFallback::Image => {
+                if let Some(rel_id) = &equation.fallback_image {
+                    if let Ok(Some(image)) = self
+                        .image_extractor
+                        .extract_described(rel_id, self.rels, alt)
+                    {
+                        return image;
+                    }
+                }
+            }
+            MathFallback::RawText => {}
+            MathFallback::Placeholder => return placeholder,
+        }
+        if text.is_empty() {
+            placeholder
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Returns the text emitted for a line break within a paragraph: a space
+    /// within list items when `collapse_list_item_breaks` is set, and a
+    /// newline in plain text.
+    pub fn line_break(&self) -> &'static str {
+        if self.in_list_item && self.options.collapse_list_item_breaks {
+            return " ";
+        }
+        if self.plain_text {
+            return "\n";
+        }
+        match self.options.line_break {
+            LineBreak::Html if self.no_html() => LineBreak::Backslash.as_str(),
+            line_break => line_break.as_str(),
+        }
+    }
+
+    /// Returns the text for a line break that must not render as a space:
+    /// [`Self::line_break`], with `LineBreak::Newline`, a soft break, written
+    /// as `LineBreak::Backslash`.
+    pub fn hard_line_break(&self) -> &'static str {
+        match self.line_break() {
+            "\n" if !self.plain_text => LineBreak::Backslash.as_str(),
+            line_break => line_break,
+        }
+    }
+
+    /// Returns true when a run's text must be uppercased: `text_transform` is
+    /// `Apply` and the run's effective formatting is all caps or small caps.
+    pub fn uppercase_run(&self, run: &Run<'a>, para_style_id: Option<&str>) -> bool {
+        if self.options.text_transform == TextTransform::Preserve {
+            return false;
+        }
+        let run_style_id = run
+            .property
+            .as_ref()
+            .and_then(|props| props.style_id.as_ref())
+            .map(|style| style.value.as_ref());
+        let props = self.resolve_run_property(run.property.as_ref(), run_style_id, para_style_id);
+        props.caps.is_some_and(|caps| caps.value.unwrap_or(true))
+            || props
+                .small_caps
+                .is_some_and(|small_caps| small_caps.value.unwrap_or(true))
+    }
+
+    /// Returns the output heading level (after `heading_offset`) of a paragraph
+    /// whose effective style is a heading style.
+    pub fn paragraph_heading_level(&self, para: &Paragraph<'a>) -> Option<usize> {
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        let props = self.resolve_paragraph_property(para.property.as_ref(), style_id);
+        let level = self.heading_style_level(&props)?;
+        Some(self.heading_level(level))
+    }
+
+    /// Returns the heading level, before `heading_offset`, of a paragraph
+    /// with the resolved properties `props`: the level of its heading style
+    /// or, with `outline_headings`, its outline level plus one.
+    pub(crate) fn heading_style_level(&self, props: &ParagraphProperty<'a>) -> Option<usize> {
+        let style_level = props
+            .style_id
+            .as_ref()
+            .and_then(|style| crate::localization::parse_heading_style(&style.value));
+        style_level.or_else(|| {
+            let outline = props.outline_lvl.as_ref()?.value;
+            (self.options.outline_headings && (0..9).contains(&outline))
+                .then(|| outline as usize + 1)
+        })
+    }
+
+    /// Returns the TOC level of a table of contents entry to be rewritten as a
+    /// link (`TocHandling::Links`).
+    pub fn toc_entry_level(&self, para: &Paragraph<'a>) -> Option<usize> {
+        if self.options.toc != TocHandling::Links {
+            return None;
+        }
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        self.style_resolver.toc_level(style_id)
+    }
+
+    /// Returns the callout of a paragraph whose style is listed in
+    /// `callout_styles`; none in plain text, which has no blockquotes.
+    pub fn paragraph_callout(&self, para: &Paragraph<'a>) -> Option<Callout> {
+        if self.plain_text {
+            return None;
+        }
+        let styles = self.options.callout_styles.as_ref()?;
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        let localized = self
+            .options
+            .localization
+            .as_deref()
+            .map_or(crate::localization::CALLOUT_STYLE_NAMES, |localization| {
+                localization.callout_style_names()
+            });
+        self.style_resolver.callout(style_id, styles, localized)
+    }
+
+    /// Returns the callout of the block at `index` when that block was written
+    /// as a callout.
+    pub(crate) fn callout_at(&self, index: usize) -> Option<Callout> {
+        self.last_callout
+            .filter(|(last, _)| *last == index)
+            .map(|(_, callout)| callout)
+    }
+
+    /// Records that the block at `index` was written as a callout.
+    pub(crate) fn set_callout_at(&mut self, index: usize, callout: Callout) {
+        self.last_callout = Some((index, callout));
+    }
+
+    /// Forgets the last callout block, e.g. once it has been emitted.
+    pub(crate) fn clear_callout(&mut self) {
+        self.last_callout = None;
+    }
+
+    /// Returns the spacing of a paragraph that is a list item (numbered and
+    /// not a heading).
+    pub(crate) fn list_item_spacing(&self, para: &Paragraph<'a>) -> Option<ListItemSpacing> {
+        if self.paragraph_heading_level(para).is_some() {
+            return None;
+        }
+        let style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        let props = self.resolve_paragraph_property(para.property.as_ref(), style_id);
+        let numbering = props.numbering.as_ref()?;
+        (numbering.id.is_some() && numbering.level.is_some()).then(|| ListItemSpacing::new(&props))
+    }
+
+    /// Returns the spacing of the list item the block at `index` ends with.
+    pub(crate) fn list_item_at(&self, index: usize) -> Option<&ListItemSpacing> {
+        self.last_list_item
+            .as_ref()
+            .filter(|(last, _)| *last == index)
+            .map(|(_, spacing)| spacing)
+    }
+
+    /// Records that the block at `index` ends with a list item.
+    pub(crate) fn set_list_item_at(&mut self, index: usize, spacing: ListItemSpacing) {
+        self.last_list_item = Some((index, spacing));
+    }
+
+    /// Forgets the last list item block, e.g. once it has been emitted.
+    pub(crate) fn clear_list_item(&mut self) {
+        self.last_list_item = None;
+    }
+
+    /// Returns the code block at `index` when that block was written as one.
+    pub(crate) fn code_block_at(&self, index: usize) -> Option<&CodeBlock> {
+        self.last_code_block
+            .as_ref()
+            .filter(|(last, _)| *last == index)
+            .map(|(_, block)| block)
+    }
+
+    /// Records that the block at `index` was written as a code block.
+    pub(crate) fn set_code_block_at(&mut self, index: usize, block: CodeBlock) {
+        self.last_code_block = Some((index, block));
+    }
+
+    /// Forgets the last code block, e.g. once it has been emitted.
+    pub(crate) fn clear_code_block(&mut self) {
+        self.last_code_block = None;
+    }
+
+    /// Records the level, counter value and prefix of a converted list item.
+    pub(crate) fn set_list_marker(&mut self, level: usize, number: Option<i32>, prefix: String) {
+        self.last_list_marker = Some((level, number, prefix));
+    }
+
+    /// Returns and forgets the marker of the last list item converted.
+    pub(crate) fn take_list_marker(&mut self) -> Option<(usize, Option<i32>, String)> {
+        self.last_list_marker.take()
+    }
+
+    /// Applies `heading_offset` to a heading level, keeping it within 1..=6.
+    pub fn heading_level(&self, level: usize) -> usize {
+        (level + usize::from(self.options.heading_offset)).clamp(1, 6)
+    }
+
+    /// Returns true when the output must not contain raw HTML: with
+    /// `no_html`, and in plain text.
+    pub fn no_html(&self) -> bool {
+        self.options.no_html || self.plain_text
+    }
+
+    /// Returns the space the localization inserts between touching CJK and
+    /// Latin text, if any.
+    pub fn cjk_latin_space(&self) -> Option<&'a str> {
+        self.options
+            .localization
+            .as_deref()
+            .and_then(|localization| localization.cjk_latin_space())
+    }
+
+    /// Returns document text for the output. With `no_html`, `<` is escaped so
+    /// the text cannot open an HTML tag; inside an HTML table cell, `&`, `<`
+    /// and `>` become entities so the text cannot break the table markup.
+    /// Outside HTML table cells, the characters `escape_extended_syntax`
+    /// names are escaped as well. Plain text is never escaped.
+    pub fn document_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.plain_text {
+            return Cow::Borrowed(text);
+        }
+        if self.html_cell_depth > 0 && !self.no_html() {
+            if text.contains(['&', '<', '>']) {
+                return Cow::Owned(escape_html_text(text));
+            }
+            return Cow::Borrowed(text);
+        }
+        let text = match self.options.escape_extended_syntax {
+            ExtendedSyntaxEscaping::None => Cow::Borrowed(text),
+            ExtendedSyntaxEscaping::Tilde if text.contains('~') => {
+                Cow::Owned(escape_markdown_extended(text, false))
+            }
+            ExtendedSyntaxEscaping::All if text.contains(['~', '^', '=']) => {
+                Cow::Owned(escape_markdown_extended(text, true))
+            }
+            _ => Cow::Borrowed(text),
+        };
+        if self.no_html() && text.contains('<') {
+            return Cow::Owned(text.replace('<', "\\<"));
+        }
+        text
+    }
+
+    /// Returns true when the nested tables of a table being converted are
+    /// indented: `pretty_html` is set and the table is not nested itself.
+    pub fn pretty_html(&self) -> bool {
+        self.options.pretty_html && self.html_cell_depth == 0 && !self.no_html()
+    }
+
+    /// Marks the start of an HTML table cell's content.
+    pub fn enter_html_cell(&mut self) {
+        self.html_cell_depth += 1;
+        self.image_extractor.set_html_links(!self.no_html());
+    }
+
+    /// Marks the end of an HTML table cell's content.
+    pub fn leave_html_cell(&mut self) {
+        self.html_cell_depth = self.html_cell_depth.saturating_sub(1);
+        self.image_extractor
+            .set_html_links(self.html_cell_depth > 0 && !self.no_html());
+    }
+
+    pub fn heading_id_style(&self) -> HeadingIdStyle {
+        self.options.heading_id_style
+    }
+
+    pub fn trim_table_cells(&self) -> bool {
+        self.options.trim_table_cells
+    }
+
+    pub fn table_cell_blocks(&self) -> bool {
+        self.options.table_cell_blocks && !self.no_html()
+    }
+
+    pub fn single_cell_tables(&self) -> SingleCellTables {
+        self.options.single_cell_tables
+    }
+
+    /// Returns the tab table settings; `None` in plain text, where tabs are
+    /// kept as they are.
+    pub fn tab_tables(&self) -> Option<crate::TabTables> {
+        self.options.tab_tables.filter(|_| !self.plain_text)
+    }
+
+    pub fn code_blocks(&self) -> Option<&crate::CodeBlocks> {
+        self.options.code_blocks.as_ref()
+    }
+
+    /// Returns true when a paragraph style, or a style it is based on, is
+    /// listed in `verbatim_styles` by name or ID.
+    pub fn is_verbatim_style(&self, para_style_id: Option<&str>) -> bool {
+        self.options
+            .verbatim_styles
+            .iter()
+            .any(|style| self.style_resolver.has_style(para_style_id, style))
+    }
+
+    /// Returns true when a character style, or a style it is based on, is
+    /// listed in `kbd_styles`. Always false with `no_html`, where the runs
+    /// are written as plain text.
+    pub fn is_kbd_style(&self, run_style_id: Option<&str>) -> bool {
+        !self.no_html()
+            && self
+                .options
+                .kbd_styles
+                .iter()
+                .any(|style| self.style_resolver.has_style(run_style_id, style))
+    }
+
+    pub fn table_row_headers(&self) -> bool {
+        self.options.table_row_headers
+    }
+
+    pub fn pipe_table_row_headers(&self) -> bool {
+        self.options.pipe_table_row_headers
+    }
+
+    pub fn table_column_widths(&self) -> bool {
+        self.options.table_column_widths
+    }
+
+    pub fn table_cell_padding(&self) -> bool {
+        self.options.table_cell_padding
+    }
+
+    pub fn rtl_table_order(&self) -> crate::RtlTableOrder {
+        self.options.rtl_table_order
+    }
+
+    /// Returns the content rendered for a blank table cell.
+    pub fn empty_table_cell(&self) -> &'static str {
+        if self.options.preserve_empty_table_cells {
+            ""
+        } else {
+            "&nbsp;"
+        }
+    }
+
+    /// Returns the legacy form field data of the paragraph, in document order.
+    pub(crate) fn paragraph_form_fields(&self, para: &Paragraph<'_>) -> Vec<RawFormField> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.form_fields.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the phonetic guides of the paragraph, which rs_docx drops.
+    pub(crate) fn paragraph_rubies(&self, para: &Paragraph<'_>) -> Vec<RawRuby> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.rubies.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the smart tags of the paragraph, which rs_docx drops.
+    pub(crate) fn paragraph_smart_tags(&self, para: &Paragraph<'_>) -> Vec<RawSmartTag> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.smart_tags.clone())
+            .unwrap_or_default()
+    }
+
+    /// Parses a run of a smart tag from its byte range in the raw document.
+    pub(crate) fn smart_tag_run(&self, range: &std::ops::Range<usize>) -> Option<Run<'a>> {
+        let xml = self.raw_document.get(range.clone())?;
+        Run::from_str(xml).ok()
+    }
+
+    /// Returns the `<span>` opening a smart tag's text with
+    /// `SmartTags::Annotate`.
+    pub(crate) fn smart_tag_open(&self, tag: &RawSmartTag) -> Option<String> {
+        if self.options.smart_tags != SmartTags::Annotate || self.no_html() {
+            return None;
+        }
+        Some(format!(
+            "<span data-smart-tag=\"{}\">",
+            escape_html_attr(&tag.element)
+        ))
+    }
+
+    /// Returns the tooltip of the hyperlink at `position` in the paragraph's
+    /// content, which rs_docx drops; `None` without `link_titles`.
+    pub(crate) fn link_tooltip(&self, para: &Paragraph<'_>, position: usize) -> Option<String> {
+        if !self.options.link_titles {
+            return None;
+        }
+        self.raw_index.paragraph(para).and_then(|p| {
+            p.link_tooltips
+                .iter()
+                .find(|(link, _)| *link == position)
+                .map(|(_, tooltip)| tooltip.clone())
+        })
+    }
+
+    /// Renders a phonetic guide according to `ruby`: `<ruby>` markup, or the
+    /// annotation in parentheses after the base text.
+    pub(crate) fn render_ruby(&self, ruby: &RawRuby) -> String {
+        if ruby.annotation.is_empty() {
+            return self.document_text(&ruby.base).into_owned();
+        }
+        if self.options.ruby == RubyText::Html && !self.no_html() {
+            return format!(
+                "<ruby>{}<rt>{}</rt></ruby>",
+                escape_html_text(&ruby.base),
+                escape_html_text(&ruby.annotation)
+            );
+        }
+        format!(
+            "{}({})",
+            self.document_text(&ruby.base),
+            self.document_text(&ruby.annotation)
+        )
+    }
+
+    /// Returns the VML images of the paragraph that rs_docx dropped.
+    pub(crate) fn paragraph_vml_images(&self, para: &Paragraph<'_>) -> Vec<RawVmlImage> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.vml_images.clone())
+            .unwrap_or_default()
+    }
+
+    /// Extracts a VML image; empty when it has no image or images are skipped.
+    ///
+    /// The preview of an equation object is rendered like an equation without
+    /// text, according to `math_fallback`.
+    pub(crate) fn render_vml_image(&mut self, image: &RawVmlImage) -> String {
+        if image.equation {
+            return self.render_equation(&RawEquation {
+                fallback_image: Some(image.rel_id.clone()).filter(|id| !id.is_empty()),
+                alt: image.alt.clone(),
+                ..Default::default()
+            });
+        }
+        if image.rel_id.is_empty() {
+            return String::new();
+        }
+        self.image_extractor
+            .extract_from_rel_id(&image.rel_id, self.rels)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Returns the charts drawn in the paragraph.
+    pub(crate) fn paragraph_charts(&self, para: &Paragraph<'_>) -> Vec<RawChart> {
+        self.raw_index
+            .paragraph(para)
+            .map(|p| p.charts.clone())
+            .unwrap_or_default()
+    }
+
+    /// Renders a chart according to `chart_handling`.
+    pub(crate) fn render_chart(&mut self, chart: &RawChart) -> String {
+        if self.options.chart_handling == ChartHandling::Image {
+            if let Some(rel_id) = &chart.fallback_image {
+                if let Ok(Some(image)) = self.image_extractor.extract_from_rel_id(rel_id, self.rels)
+                {
+                    return image;
+                }
+            }
+        }
+
+        let data = self
+            .rels
+            .get(&chart.rel_id)
+            .and_then(|target| self.chart_parts.get(&part_path(target)))
+            .and_then(|xml| ChartData::parse(xml));
+        match data {
+            Some(data) if self.plain_text => format!("\n\n{}\n\n", data.to_text_table()),
+            Some(data) if self.no_html() => format!("\n\n{}\n\n", data.to_pipe_table()),
+            Some(data) => format!("\n\n{}\n\n", data.to_html_table(self.empty_table_cell())),
+            None => "[chart]".to_string(),
+        }
+    }
+
+    /// Marks whether the paragraph being converted is a list item.
+    pub(crate) fn set_in_list_item(&mut self, in_list_item: bool) {
+        self.in_list_item = in_list_item;
+    }
+
+    /// Holds drop-cap letters until the paragraph they belong to is converted.
+    pub fn push_drop_cap(&mut self, letters: &str) {
+        self.pending_drop_cap
+            .get_or_insert_with(String::new)
+            .push_str(letters);
+    }
+
+    pub fn take_drop_cap(&mut self) -> Option<String> {
+        self.pending_drop_cap.take()
+    }
+
+    /// Returns the glyph replacements of `symbol_overrides`.
+    pub(crate) fn symbol_overrides(&self) -> SymbolOverrides<'a> {
+        SymbolOverrides::new(&self.options.symbol_overrides)
+    }
+
+    /// Returns true when a paragraph is in a text frame that `text_frames`
+    /// writes apart from the regular paragraphs.
+    pub fn is_text_frame(&self, para: &Paragraph<'_>) -> bool {
+        self.options.text_frames != TextFrames::Inline
+            && self
+                .raw_index
+                .paragraph(para)
+                .is_some_and(|p| p.framed && !p.drop_cap)
+    }
+
+    pub fn text_frames(&self) -> TextFrames {
+        self.options.text_frames
+    }
+
+    pub fn text_frame_position(&self) -> TextFramePosition {
+        self.options.text_frame_position
+    }
+
+    /// Adds a converted paragraph to the text frame being collected.
+    pub fn push_text_frame(&mut self, paragraph: String) {
+        self.pending_text_frame.push(paragraph);
+    }
+
+    /// Returns the paragraphs of the collected text frame, if any.
+    pub fn take_text_frame(&mut self) -> Option<Vec<String>> {
+        if self.pending_text_frame.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.pending_text_frame))
+    }
+
     pub fn next_list_marker(&mut self, num_id: i32, ilvl: i32) -> String {
         self.numbering.next_marker(num_id, ilvl)
     }
 
+    pub fn next_list_item(&mut self, num_id: i32, ilvl: i32) -> ListMarker {
+        self.numbering.next_item(num_id, ilvl)
+    }
+
+    pub fn ordered_list_delimiter(&self) -> OrderedListDelimiter {
+        self.options.ordered_list_delimiter
+    }
+
+    pub fn list_labels(&self) -> ListLabels {
+        self.options.list_labels
+    }
+
     pub fn list_indent_level(&self, num_id: i32, ilvl: i32) -> usize {
         self.numbering.get_indent(num_id, ilvl)
     }
@@ -231,12 +1340,86 @@ impl<'a> ConversionContext<'a> {
         self.options.preserve_whitespace
     }
 
+    pub fn leading_tab(&self) -> &LeadingTab {
+        &self.options.leading_tab
+    }
+
+    /// Returns the text written for a positional tab: the separator, or with
+    /// `PositionalTabs::Columns` and `columns` set, the placeholder that
+    /// [`ParagraphConverter`] turns into a cell boundary.
+    ///
+    /// [`ParagraphConverter`]: super::ParagraphConverter
+    pub(crate) fn positional_tab(&self, ptab: &PTab, columns: bool) -> &str {
+        match &self.options.positional_tabs {
+            PositionalTabs::Separator(separator) => separator,
+            PositionalTabs::Columns if !columns || self.no_html() => " ",
+            PositionalTabs::Columns => match ptab.alignment {
+                Some(PTabAlignment::Center) => super::paragraph::PTAB_CENTER,
+                Some(PTabAlignment::Right) => super::paragraph::PTAB_RIGHT,
+                _ => super::paragraph::PTAB_LEFT,
+            },
+        }
+    }
+
     pub fn html_underline_enabled(&self) -> bool {
-        self.options.html_underline
+        self.underline_handling() == UnderlineHandling::Html
+    }
+
+    /// Returns how underlined text is written: as `underline_handling` says,
+    /// else as the deprecated `html_underline` says, and `Ignore` for `Html`
+    /// with `no_html`.
+    #[allow(deprecated)]
+    pub fn underline_handling(&self) -> UnderlineHandling {
+        let handling =
+            self.options
+                .underline_handling
+                .unwrap_or(match self.options.html_underline {
+                    true => UnderlineHandling::Html,
+                    false => UnderlineHandling::Ignore,
+                });
+        match handling {
+            UnderlineHandling::Html if self.no_html() => UnderlineHandling::Ignore,
+            handling => handling,
+        }
     }
 
     pub fn html_strikethrough_enabled(&self) -> bool {
-        self.options.html_strikethrough
+        self.options.html_strikethrough && !self.no_html()
+    }
+
+    pub fn tracked_changes(&self) -> crate::TrackedChanges {
+        self.options.tracked_changes
+    }
+
+    /// Returns what hidden text (`w:vanish`, index entries) renders as, or `None`
+    /// when it should be rendered like visible text.
+    pub fn hidden_text_replacement(&self, text: &str) -> Option<String> {
+        match self.options.hidden_text {
+            HiddenText::Drop => Some(String::new()),
+            HiddenText::Include => None,
+            HiddenText::AsComment if self.no_html() => Some(String::new()),
+            HiddenText::AsComment => Some(format!("<!-- {} -->", comment_text(text))),
+        }
+    }
+
+    /// Returns the run language when `preserve_lang` is enabled and it differs
+    /// from the document default language for the same script.
+    pub fn run_language(
+        &self,
+        props: &rs_docx::formatting::CharacterProperty<'_>,
+        text: &str,
+    ) -> Option<String> {
+        if !self.options.preserve_lang || self.no_html() {
+            return None;
+        }
+
+        let script = LangScript::detect(props, text);
+        let lang = script.pick(props.lang.as_ref()?)?;
+        let default = self.default_lang.as_ref().and_then(|l| script.pick(l));
+        if default.is_some_and(|d| d.eq_ignore_ascii_case(lang)) {
+            return None;
+        }
+        Some(lang.to_string())
     }
 
     pub fn footnote_count(&self) -> usize {
@@ -261,3 +1444,120 @@ impl<'a> ConversionContext<'a> {
         std::mem::take(&mut self.missing_references)
     }
 }
+
+/// Returns the plain text of a note's paragraphs, separated by spaces.
+fn note_text(content: &[BodyContent<'_>]) -> String {
+    content
+        .iter()
+        .filter_map(|bc| match bc {
+            BodyContent::Paragraph(p) => Some(p.text().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns true when resolved run properties mark the text as hidden (`w:vanish`).
+pub(crate) fn is_hidden(props: &rs_docx::formatting::CharacterProperty<'_>) -> bool {
+    props
+        .vanish
+        .as_ref()
+        .is_some_and(|v| v.value.unwrap_or(true))
+}
+
+/// Returns true when resolved run properties underline the text (`w:u`
+/// other than `w:val="none"`).
+pub(crate) fn is_underlined(props: &rs_docx::formatting::CharacterProperty<'_>) -> bool {
+    props
+        .underline
+        .as_ref()
+        .is_some_and(|u| !matches!(u.val, Some(UnderlineStyle::None)))
+}
+
+/// Resolves a document relationship target to its archive path.
+pub(super) fn part_path(target: &str) -> String {
+    let target = target.trim_start_matches('/');
+    let path = if target.starts_with("word/") {
+        target.to_string()
+    } else {
+        format!("word/{}", target)
+    };
+    if !path.contains("./") {
+        return path;
+    }
+    // Targets outside `word/` are written like `../customXml/item1.xml`.
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Script slot of `w:lang` that applies to a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LangScript {
+    Latin,
+    EastAsian,
+    Bidi,
+}
+
+impl LangScript {
+    fn detect(props: &rs_docx::formatting::CharacterProperty<'_>, text: &str) -> Self {
+        let is_rtl = props
+            .rtl
+            .as_ref()
+            .map(|r| r.value.unwrap_or(true))
+            .unwrap_or(false);
+        if is_rtl || text.chars().any(is_rtl_char) {
+            Self::Bidi
+        } else if text.chars().any(is_east_asian_char) {
+            Self::EastAsian
+        } else {
+            Self::Latin
+        }
+    }
+
+    fn pick<'l>(self, lang: &'l rs_docx::formatting::Lang<'_>) -> Option<&'l str> {
+        let value = match self {
+            Self::Latin => lang.val.as_deref(),
+            Self::EastAsian => lang.east_asia.as_deref().or(lang.val.as_deref()),
+            Self::Bidi => lang.bidi.as_deref().or(lang.val.as_deref()),
+        };
+        value.filter(|v| !v.is_empty())
+    }
+}
+
+fn is_rtl_char(c: char) -> bool {
+    matches!(c as u32, 0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF)
+}
+
+fn is_east_asian_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x11FF
+            | 0x3000..=0x303F
+            | 0x3040..=0x30FF
+            | 0x3130..=0x318F
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0xFF00..=0xFFEF
+    )
+}
+
+/// Splits the runs of hyphens in `text`, which may not hold `--` inside an
+/// HTML comment.
+fn comment_text(text: &str) -> String {
+    let mut text = text.to_string();
+    while text.contains("--") {
+        text = text.replace("--", "- -");
+    }
+    text
+}
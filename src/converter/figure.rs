@@ -0,0 +1,103 @@
+//! Figures - paragraphs holding only images, and the captions after them.
+//!
+//! Captions are paired with their images before conversion; the image
+//! paragraph is then held back until its caption paragraph, which emits both
+//! as one `<figure>`, or as a Pandoc implicit figure with
+//! `FigureSyntax::Pandoc`.
+
+use super::raw_xml::element_key;
+use super::ConversionContext;
+use rs_docx::document::{BodyContent, Paragraph, ParagraphContent, RunContent};
+use std::collections::HashSet;
+
+/// Image paragraphs followed by a caption paragraph, and those captions.
+#[derive(Debug, Default)]
+pub(crate) struct Figures {
+    images: HashSet<usize>,
+    captions: HashSet<usize>,
+}
+
+impl Figures {
+    /// Pairs every image-only paragraph with a `Caption` paragraph directly
+    /// after it.
+    pub fn build(body: &[BodyContent<'_>], context: &ConversionContext<'_>) -> Self {
+        let mut figures = Self::default();
+        figures.visit(body, context);
+        figures
+    }
+
+    pub fn has_caption(&self, para: &Paragraph<'_>) -> bool {
+        self.images.contains(&element_key(para))
+    }
+
+    pub fn is_caption(&self, para: &Paragraph<'_>) -> bool {
+        self.captions.contains(&element_key(para))
+    }
+
+    fn visit(&mut self, body: &[BodyContent<'_>], context: &ConversionContext<'_>) {
+        let mut previous_image = None;
+        for content in body {
+            match content {
+                BodyContent::Paragraph(para) => {
+                    if previous_image.is_some() && context.is_caption(para) {
+                        self.images.extend(previous_image.take());
+                        self.captions.insert(element_key(para));
+                    }
+                    previous_image = is_image_only(para).then(|| element_key(para));
+                }
+                BodyContent::Sdt(sdt) => {
+                    previous_image = None;
+                    if let Some(sdt_content) = &sdt.content {
+                        self.visit(&sdt_content.content, context);
+                    }
+                }
+                _ => previous_image = None,
+            }
+        }
+    }
+}
+
+/// Returns true when a paragraph shows at least one image (`w:drawing` or
+/// `w:pict`) and otherwise only whitespace and bookmarks.
+pub(crate) fn is_image_only(para: &Paragraph<'_>) -> bool {
+    image_count(para).is_some_and(|count| count > 0)
+}
+
+/// Returns the number of images (`w:drawing` or `w:pict`) of a paragraph
+/// that otherwise shows only whitespace and bookmarks, and `None` when it
+/// shows anything else.
+pub(crate) fn image_count(para: &Paragraph<'_>) -> Option<usize> {
+    let mut count = 0;
+    for content in &para.content {
+        match content {
+            ParagraphContent::Run(run) => {
+                for item in &run.content {
+                    match item {
+                        RunContent::Drawing(_) | RunContent::Pict(_) => count += 1,
+                        RunContent::Text(text) if text.text.trim().is_empty() => {}
+                        RunContent::Tab(_) => {}
+                        _ => return None,
+                    }
+                }
+            }
+            ParagraphContent::BookmarkStart(_) | ParagraphContent::BookmarkEnd(_) => {}
+            _ => return None,
+        }
+    }
+    Some(count)
+}
+
+/// Returns the source of an image written as `![alt](src)` or
+/// `<img src="..." />`, and `None` for anything else, such as several images
+/// or inline `<svg>` markup.
+pub(crate) fn image_source(image: &str) -> Option<&str> {
+    if image.contains('\n') {
+        return None;
+    }
+    if image.starts_with("![") && image.ends_with(')') {
+        let start = image.rfind("](")? + 2;
+        return Some(&image[start..image.len() - 1]);
+    }
+    let rest = image.strip_prefix("<img src=\"")?;
+    rest.split('"').next()
+}
@@ -0,0 +1,93 @@
+//! Fragments - document parts for converting body content without a package.
+
+use rs_docx::document::{Comments, EndNotes, FootNotes, Numbering};
+use rs_docx::rels::{Relationship, Relationships};
+use rs_docx::styles::Styles;
+use rs_docx::Docx;
+use std::borrow::Cow;
+
+/// The document parts a fragment of body content is converted against, for
+/// [`DocxToMarkdown::convert_fragment`](crate::DocxToMarkdown::convert_fragment).
+///
+/// Every part starts out empty: without styles only direct formatting
+/// applies, without numbering list paragraphs have no markers, and links
+/// to unknown relationships point at `#`.
+///
+/// ```
+/// use dm2xcod::FragmentContext;
+///
+/// let context = FragmentContext::new().with_relationship("rId1", "https://example.com");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct FragmentContext<'a> {
+    pub(crate) docx: Docx<'a>,
+}
+
+impl<'a> FragmentContext<'a> {
+    /// Creates a context with no styles, numbering, relationships or notes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves paragraph and character styles from `styles`.
+    pub fn with_styles(mut self, styles: Styles<'a>) -> Self {
+        self.docx.styles = styles;
+        self
+    }
+
+    /// Numbers list paragraphs with the definitions in `numbering`.
+    pub fn with_numbering(mut self, numbering: Numbering<'a>) -> Self {
+        self.docx.numbering = Some(numbering);
+        self
+    }
+
+    /// Resolves hyperlink targets with the main document's relationships.
+    pub fn with_relationships(mut self, relationships: Relationships<'a>) -> Self {
+        self.docx.document_rels = Some(relationships);
+        self
+    }
+
+    /// Adds one relationship, such as the target of a hyperlink `r:id`.
+    pub fn with_relationship(
+        mut self,
+        id: impl Into<Cow<'a, str>>,
+        target: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.docx
+            .document_rels
+            .get_or_insert_with(Relationships::default)
+            .relationships
+            .push(Relationship {
+                id: id.into(),
+                target: target.into(),
+                ..Default::default()
+            });
+        self
+    }
+
+    /// Resolves footnote references with `footnotes`.
+    pub fn with_footnotes(mut self, footnotes: FootNotes<'a>) -> Self {
+        self.docx.footnotes = Some(footnotes);
+        self
+    }
+
+    /// Resolves endnote references with `endnotes`.
+    pub fn with_endnotes(mut self, endnotes: EndNotes<'a>) -> Self {
+        self.docx.endnotes = Some(endnotes);
+        self
+    }
+
+    /// Resolves comment references with `comments`.
+    pub fn with_comments(mut self, comments: Comments<'a>) -> Self {
+        self.docx.comments = Some(comments);
+        self
+    }
+}
+
+impl<'a> From<Docx<'a>> for FragmentContext<'a> {
+    /// Uses the styles, numbering, relationships and notes of a parsed
+    /// document; its body is ignored.
+    fn from(docx: Docx<'a>) -> Self {
+        Self { docx }
+    }
+}
@@ -0,0 +1,119 @@
+//! Building blocks - the reusable content of the glossary document
+//! (`word/glossary/document.xml`).
+//!
+//! A content control can take its placeholder content from a building block
+//! (`w:sdtPr/w:placeholder/w:docPart`) instead of holding it. rs_docx does
+//! not read the glossary document, so its building blocks are split out of
+//! the raw XML here, each as a `w:body` that rs_docx can parse.
+
+use super::figure::is_image_only;
+use super::raw_xml::unescape_xml;
+use rs_docx::document::{BodyContent, SDT};
+use std::collections::HashMap;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// Path of the glossary document in the package.
+pub(crate) const GLOSSARY_DOCUMENT: &str = "word/glossary/document.xml";
+
+/// Returns the content of each building block (`w:docPart`) keyed by its
+/// name (`w:docPartPr/w:name`), wrapped in a `w:body` element.
+///
+/// Building blocks without a name or a `w:docPartBody` are left out, and so
+/// is everything when the XML cannot be tokenized.
+pub(crate) fn building_blocks(xml: &str) -> HashMap<String, String> {
+    let mut blocks = HashMap::new();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut pending = "";
+    let mut name = None;
+    let mut body_start = None;
+    let mut body = None;
+
+    for token in Tokenizer::from(xml) {
+        let Ok(token) = token else {
+            return HashMap::new();
+        };
+        match token {
+            Token::ElementStart { prefix, local, .. } => {
+                pending = if prefix.as_str() == "w" {
+                    local.as_str()
+                } else {
+                    ""
+                };
+                if pending == "docPart" {
+                    name = None;
+                    body = None;
+                }
+            }
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => {
+                let in_properties = stack.last() == Some(&"docPartPr");
+                if in_properties
+                    && pending == "name"
+                    && (prefix.as_str(), local.as_str()) == ("w", "val")
+                {
+                    name = Some(unescape_xml(value.as_str()));
+                }
+            }
+            Token::ElementEnd { end, span } => match end {
+                ElementEnd::Open => {
+                    if pending == "docPartBody" && stack.last() == Some(&"docPart") {
+                        body_start = Some(span.end());
+                    }
+                    stack.push(pending);
+                }
+                ElementEnd::Empty => {}
+                ElementEnd::Close(..) => match stack.pop() {
+                    Some("docPartBody") => {
+                        body = body_start.take().map(|start| &xml[start..span.start()]);
+                    }
+                    Some("docPart") => {
+                        if let (Some(name), Some(body)) = (name.take(), body.take()) {
+                            blocks.insert(name, format!("<w:body>{}</w:body>", body));
+                        }
+                    }
+                    _ => {}
+                },
+            },
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Returns true when a content control shows nothing of its own: no text,
+/// images or tables, only blank paragraphs and runs, and bookmarks.
+pub(crate) fn is_empty(sdt: &SDT<'_>) -> bool {
+    let Some(content) = &sdt.content else {
+        return true;
+    };
+    content.content.iter().all(|item| match item {
+        BodyContent::Paragraph(para) => para.text().trim().is_empty() && !is_image_only(para),
+        BodyContent::Run(run) => run.text().trim().is_empty(),
+        BodyContent::BookmarkStart(_) | BodyContent::BookmarkEnd(_) => true,
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_building_blocks_are_keyed_by_name() {
+        let xml = r#"<w:glossaryDocument xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docParts>
+            <w:docPart><w:docPartPr><w:name w:val="Signature &amp; Date"/><w:category><w:name w:val="General"/></w:category></w:docPartPr><w:docPartBody><w:p><w:r><w:t>Signed</w:t></w:r></w:p></w:docPartBody></w:docPart>
+            <w:docPart><w:docPartPr><w:name w:val="Empty"/></w:docPartPr></w:docPart>
+        </w:docParts></w:glossaryDocument>"#;
+
+        let blocks = building_blocks(xml);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            blocks["Signature & Date"],
+            "<w:body><w:p><w:r><w:t>Signed</w:t></w:r></w:p></w:body>"
+        );
+    }
+}
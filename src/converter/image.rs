@@ -1,5 +1,9 @@
 //! Image extractor - handles image extraction from DOCX.
 
+use super::logging;
+use super::manifest::{ConversionWarning, ManifestImage};
+use super::media::{self, ExtractedImage};
+use crate::render::{escape_html_attr, escape_markdown_link_text};
 use crate::{error::Error, Result};
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
@@ -7,13 +11,33 @@ use rs_docx::document::Drawing;
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Cursor, Read, Seek};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 /// Extractor for images embedded in DOCX.
 pub struct ImageExtractor {
     mode: ImageMode,
     source: ImageSource,
     counter: usize,
+    /// Directory the Markdown will be written to; saved images are linked relative to it.
+    markdown_dir: Option<PathBuf>,
+    /// Whether inline images use Markdown image syntax instead of `<img>`.
+    no_html: bool,
+    /// Whether saved images are linked with `<img>` instead of Markdown image
+    /// syntax, for HTML table cells, where Markdown is not read.
+    html_links: bool,
+    /// Whether images are written as their alt text, for plain text output.
+    plain_text: bool,
+    /// Images skipped because their relationship or media part is missing or
+    /// unreadable.
+    warnings: Vec<ConversionWarning>,
+    /// Images written so far, for the conversion manifest.
+    images: Vec<ManifestImage>,
+    /// SVG relationship ids keyed by the raster image they replace.
+    svg_blips: HashMap<String, String>,
+    /// Whether inline SVG images are written as `<svg>` markup.
+    svg_markup: bool,
+    /// Size limit of inline images and the directory larger ones are saved to.
+    spill: Option<(usize, PathBuf)>,
 }
 
 enum ImageMode {
@@ -38,6 +62,15 @@ impl ImageExtractor {
             mode: ImageMode::SaveToDir(output_dir),
             source: ImageSource::Path(docx_path.as_ref().to_path_buf()),
             counter: 0,
+            markdown_dir: None,
+            no_html: false,
+            html_links: false,
+            plain_text: false,
+            warnings: Vec::new(),
+            images: Vec::new(),
+            svg_blips: HashMap::new(),
+            svg_markup: false,
+            spill: None,
         })
     }
 
@@ -50,6 +83,15 @@ impl ImageExtractor {
             mode: ImageMode::SaveToDir(output_dir),
             source: ImageSource::Bytes(bytes.to_vec()),
             counter: 0,
+            markdown_dir: None,
+            no_html: false,
+            html_links: false,
+            plain_text: false,
+            warnings: Vec::new(),
+            images: Vec::new(),
+            svg_blips: HashMap::new(),
+            svg_markup: false,
+            spill: None,
         })
     }
 
@@ -59,6 +101,15 @@ impl ImageExtractor {
             mode: ImageMode::Inline,
             source: ImageSource::Path(docx_path.as_ref().to_path_buf()),
             counter: 0,
+            markdown_dir: None,
+            no_html: false,
+            html_links: false,
+            plain_text: false,
+            warnings: Vec::new(),
+            images: Vec::new(),
+            svg_blips: HashMap::new(),
+            svg_markup: false,
+            spill: None,
         })
     }
 
@@ -68,6 +119,15 @@ impl ImageExtractor {
             mode: ImageMode::Inline,
             source: ImageSource::Bytes(bytes.to_vec()),
             counter: 0,
+            markdown_dir: None,
+            no_html: false,
+            html_links: false,
+            plain_text: false,
+            warnings: Vec::new(),
+            images: Vec::new(),
+            svg_blips: HashMap::new(),
+            svg_markup: false,
+            spill: None,
         })
     }
 
@@ -77,9 +137,69 @@ impl ImageExtractor {
             mode: ImageMode::Skip,
             source: ImageSource::None,
             counter: 0,
+            markdown_dir: None,
+            no_html: false,
+            html_links: false,
+            plain_text: false,
+            warnings: Vec::new(),
+            images: Vec::new(),
+            svg_blips: HashMap::new(),
+            svg_markup: false,
+            spill: None,
         }
     }
 
+    /// Links saved images relative to `dir`, where the Markdown output will live.
+    ///
+    /// Only affects extractors created with `new_with_dir*` or
+    /// [`with_spill_dir`](Self::with_spill_dir).
+    pub fn with_markdown_dir(mut self, dir: PathBuf) -> Self {
+        self.markdown_dir = Some(dir);
+        self
+    }
+
+    /// Embeds inline images as `![image](data:...)` instead of an `<img>` tag.
+    pub fn without_html(mut self) -> Self {
+        self.no_html = true;
+        self
+    }
+
+    /// Saves inline images larger than `max_bytes` to `dir` instead of
+    /// embedding them.
+    ///
+    /// Only affects extractors created with `new_inline*`.
+    pub fn with_spill_dir(mut self, max_bytes: usize, dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        self.spill = Some((max_bytes, dir));
+        Ok(self)
+    }
+
+    /// Writes inline SVG images as `<svg>` markup instead of a data URI.
+    ///
+    /// Has no effect together with [`without_html`](Self::without_html).
+    pub fn with_svg_markup(mut self) -> Self {
+        self.svg_markup = true;
+        self
+    }
+
+    /// Links saved images with `<img>` while `html` is set, as the content of
+    /// an HTML table cell is converted.
+    pub(crate) fn set_html_links(&mut self, html: bool) {
+        self.html_links = html;
+    }
+
+    /// Writes images as their alt text while `plain_text` is set.
+    pub(crate) fn set_plain_text(&mut self, plain_text: bool) {
+        self.plain_text = plain_text;
+    }
+
+    /// Sets the SVG versions of raster images (`asvg:svgBlip`), keyed by the
+    /// raster's relationship id. An image with an SVG version is extracted as
+    /// the SVG, or as the raster when the SVG part is missing.
+    pub(crate) fn set_svg_blips(&mut self, svg_blips: HashMap<String, String>) {
+        self.svg_blips = svg_blips;
+    }
+
     /// Extracts image from a Drawing element and returns Markdown.
     pub fn extract_from_drawing(
         &mut self,
@@ -97,13 +217,183 @@ impl ImageExtractor {
             return Ok(None);
         };
 
-        // Get image path from relationships
-        let Some(image_path) = rels.get(&rel_id) else {
+        let (doc_property, extent) = match (&drawing.inline, &drawing.anchor) {
+            (Some(inline), _) => (&inline.doc_property, inline.extent.as_ref()),
+            (None, Some(anchor)) => (&anchor.doc_property, anchor.extent.as_ref()),
+            (None, None) => return Ok(None),
+        };
+        let details = ImageDetails {
+            alt: doc_property
+                .descr
+                .as_deref()
+                .filter(|alt| !alt.is_empty())
+                .map(str::to_string),
+            size: extent.map(|extent| (emu_to_px(extent.cx), emu_to_px(extent.cy))),
+            ..Default::default()
+        };
+        self.extract_image(&rel_id, rels, details)
+    }
+
+    /// Extracts the image referenced by a relationship id and returns Markdown.
+    ///
+    /// An unknown relationship id or a target missing from the archive yields
+    /// `None`, and an unreadable target an `[image]` placeholder; both are
+    /// recorded for [`take_warnings`](Self::take_warnings).
+    pub fn extract_from_rel_id(
+        &mut self,
+        rel_id: &str,
+        rels: &HashMap<String, String>,
+    ) -> Result<Option<String>> {
+        self.extract_image(rel_id, rels, ImageDetails::default())
+    }
+
+    /// Extracts an image standing in for text, such as the preview of an
+    /// equation, with `alt` as its alt text in the output.
+    pub(crate) fn extract_described(
+        &mut self,
+        rel_id: &str,
+        rels: &HashMap<String, String>,
+        alt: Option<&str>,
+    ) -> Result<Option<String>> {
+        let details = ImageDetails {
+            alt: alt.map(str::to_string),
+            show_alt: true,
+            ..Default::default()
+        };
+        self.extract_image(rel_id, rels, details)
+    }
+
+    fn extract_image(
+        &mut self,
+        rel_id: &str,
+        rels: &HashMap<String, String>,
+        details: ImageDetails,
+    ) -> Result<Option<String>> {
+        if matches!(self.mode, ImageMode::Skip) {
+            return Ok(None);
+        }
+
+        let shown_alt = details
+            .alt
+            .as_deref()
+            .filter(|_| details.show_alt)
+            .unwrap_or("image");
+        let svg_path = self
+            .svg_blips
+            .get(rel_id)
+            .and_then(|svg_id| rels.get(svg_id));
+        if let Some(svg_path) = svg_path {
+            if let Ok(svg_data) = self.read_image_from_docx(svg_path) {
+                return self.process_image(details.image(rel_id, svg_path), svg_data, shown_alt);
+            }
+        }
+
+        let Some(image_path) = rels.get(rel_id) else {
+            self.warn_missing(rel_id, None);
             return Ok(None);
         };
 
-        // Extract and process image
-        self.process_image(image_path)
+        let reason = match self.read_image_from_docx(image_path) {
+            Ok(image_data) if image_data.is_empty() => "empty media part".to_string(),
+            Ok(image_data) => {
+                return self.process_image(details.image(rel_id, image_path), image_data, shown_alt)
+            }
+            Err(Error::MediaNotFound(_)) => {
+                self.warn_missing(rel_id, Some(image_path));
+                return Ok(None);
+            }
+            Err(e) => e.to_string(),
+        };
+        self.warn(ConversionWarning::CorruptMedia {
+            rid: rel_id.to_string(),
+            target: image_path.clone(),
+            reason,
+        });
+        Ok(Some("[image]".to_string()))
+    }
+
+    /// Returns the warnings recorded so far, and clears them: images skipped
+    /// because their relationship or media part is missing or unreadable,
+    /// and those the conversion records through [`warn`](Self::warn).
+    pub fn take_warnings(&mut self) -> Vec<ConversionWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Returns the images written so far, and clears them.
+    pub fn take_images(&mut self) -> Vec<ManifestImage> {
+        std::mem::take(&mut self.images)
+    }
+
+    /// Returns the targets of the unreadable media parts found so far.
+    pub(crate) fn corrupt_media(&self) -> Vec<&str> {
+        self.warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                ConversionWarning::CorruptMedia { target, .. } => Some(target.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn warn_missing(&mut self, rel_id: &str, target: Option<&String>) {
+        self.warn(ConversionWarning::MissingMedia {
+            rid: rel_id.to_string(),
+            target: target.cloned(),
+        });
+    }
+
+    /// Records a warning, unless it already was.
+    pub(crate) fn warn(&mut self, warning: ConversionWarning) {
+        if !self.warnings.contains(&warning) {
+            logging::warning!("{:?}", warning);
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Extracts every image in the archive's media folder, skipping text conversion.
+    ///
+    /// Images are saved like converted ones in `SaveToDir` mode, and those over
+    /// the size limit of [`with_spill_dir`](Self::with_spill_dir); in `Skip`
+    /// mode nothing is returned.
+    pub fn extract_media(&mut self) -> Result<Vec<ExtractedImage>> {
+        if matches!(self.mode, ImageMode::Skip) {
+            return Ok(Vec::new());
+        }
+        let parts = match &self.source {
+            ImageSource::Path(path) => media::read_media(File::open(path)?)?,
+            ImageSource::Bytes(bytes) => media::read_media(Cursor::new(bytes))?,
+            ImageSource::None => return Ok(Vec::new()),
+        };
+
+        let mut images = Vec::with_capacity(parts.len());
+        for part in parts {
+            self.counter += 1;
+            let path = match &self.mode {
+                ImageMode::SaveToDir(dir) => Some(save_image(
+                    dir,
+                    self.counter,
+                    extension(&part.name),
+                    &part.data,
+                )?),
+                ImageMode::Inline => match &self.spill {
+                    Some((max_bytes, dir)) if part.data.len() > *max_bytes => Some(save_image(
+                        dir,
+                        self.counter,
+                        extension(&part.name),
+                        &part.data,
+                    )?),
+                    _ => None,
+                },
+                ImageMode::Skip => None,
+            };
+            images.push(ExtractedImage {
+                name: part.name,
+                alt_text: part.alt_text,
+                data: part.data,
+                path,
+            });
+        }
+        Ok(images)
     }
 
     fn find_blip_id(&self, drawing: &Drawing) -> Option<String> {
@@ -151,13 +441,7 @@ impl ImageExtractor {
             return Ok(None);
         };
 
-        // Get image path from relationships
-        let Some(image_path) = rels.get(&rel_id) else {
-            return Ok(None);
-        };
-
-        // Extract and process image
-        self.process_image(image_path)
+        self.extract_from_rel_id(&rel_id, rels)
     }
 
     fn find_pict_blip_id(&self, pict: &rs_docx::document::Pict) -> Option<String> {
@@ -182,27 +466,36 @@ impl ImageExtractor {
         None
     }
 
-    fn process_image(&mut self, image_path: &str) -> Result<Option<String>> {
-        // Read image from DOCX archive
-        let image_data = self.read_image_from_docx(image_path)?;
-
+    fn process_image(
+        &mut self,
+        mut image: ManifestImage,
+        image_data: Vec<u8>,
+        alt: &str,
+    ) -> Result<Option<String>> {
         self.counter += 1;
 
         // Determine extension
-        let ext = Path::new(image_path)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("png");
+        let ext = extension(&image.target);
 
-        match &self.mode {
-            ImageMode::SaveToDir(dir) => {
-                let filename = format!("image_{}.{}", self.counter, ext);
-                let output_path = dir.join(&filename);
-
-                fs::write(&output_path, &image_data)?;
+        let spill_dir = match (&self.mode, &self.spill) {
+            (ImageMode::SaveToDir(dir), _) => Some(dir),
+            (ImageMode::Inline, Some((max_bytes, dir))) if image_data.len() > *max_bytes => {
+                Some(dir)
+            }
+            _ => None,
+        };
+        if let Some(dir) = spill_dir {
+            let (markdown, path) = self.save_linked(dir, ext, &image_data, alt)?;
+            image.path = Some(path);
+            self.images.push(image);
+            return Ok(Some(self.written(markdown, alt)));
+        }
 
-                // Return relative path
-                Ok(Some(format!("![image]({})", output_path.display())))
+        let markdown = match &self.mode {
+            ImageMode::Inline
+                if ext.eq_ignore_ascii_case("svg") && self.svg_markup && !self.no_html =>
+            {
+                svg_markup(&image_data)
             }
             ImageMode::Inline => {
                 let mime_type = match ext.to_lowercase().as_str() {
@@ -211,19 +504,71 @@ impl ImageExtractor {
                     "gif" => "image/gif",
                     "webp" => "image/webp",
                     "svg" => "image/svg+xml",
+                    "wmf" => "image/wmf",
+                    "emf" => "image/emf",
                     _ => "application/octet-stream",
                 };
 
                 let b64 = BASE64.encode(&image_data);
-                Ok(Some(format!(
-                    "<img src=\"data:{};base64,{}\" alt=\"image\" />",
-                    mime_type, b64
-                )))
+                if self.no_html {
+                    format!(
+                        "![{}](data:{};base64,{})",
+                        escape_markdown_link_text(alt),
+                        mime_type,
+                        b64
+                    )
+                } else {
+                    format!(
+                        "<img src=\"data:{};base64,{}\" alt=\"{}\" />",
+                        mime_type,
+                        b64,
+                        escape_html_attr(alt)
+                    )
+                }
             }
-            ImageMode::Skip => Ok(None),
+            ImageMode::SaveToDir(_) | ImageMode::Skip => return Ok(None),
+        };
+        self.images.push(image);
+        Ok(Some(self.written(markdown, alt)))
+    }
+
+    /// Returns what an image is written as: its markup, or its alt text in
+    /// plain text.
+    fn written(&self, markdown: String, alt: &str) -> String {
+        if self.plain_text {
+            alt.to_string()
+        } else {
+            markdown
         }
     }
 
+    /// Saves the current image to `dir` and returns a Markdown link to it, or
+    /// an `<img>` with `html_links`, with the path it was saved to.
+    fn save_linked(
+        &self,
+        dir: &Path,
+        ext: &str,
+        data: &[u8],
+        alt: &str,
+    ) -> Result<(String, PathBuf)> {
+        let output_path = save_image(dir, self.counter, ext, data)?;
+
+        let link = match &self.markdown_dir {
+            Some(markdown_dir) => relative_link(markdown_dir, &output_path)?,
+            None => output_path.display().to_string(),
+        };
+        let markdown = if self.html_links {
+            format!(
+                "<img src=\"{}\" alt=\"{}\" />",
+                escape_html_attr(&link),
+                escape_html_attr(alt)
+            )
+        } else {
+            format!("![{}]({})", escape_markdown_link_text(alt), link)
+        };
+        Ok((markdown, output_path))
+    }
+
     fn read_image_from_docx(&self, image_path: &str) -> Result<Vec<u8>> {
         match &self.source {
             ImageSource::Path(path) => {
@@ -263,3 +608,220 @@ impl ImageExtractor {
         Err(Error::MediaNotFound(image_path.to_string()))
     }
 }
+
+/// Alt text and displayed size of an image, from the drawing that shows it.
+#[derive(Debug, Default)]
+struct ImageDetails {
+    alt: Option<String>,
+    size: Option<(u32, u32)>,
+    /// Whether `alt` replaces the generic `image` alt text in the output.
+    show_alt: bool,
+}
+
+impl ImageDetails {
+    fn image(&self, rel_id: &str, target: &str) -> ManifestImage {
+        ManifestImage {
+            rid: rel_id.to_string(),
+            target: target.to_string(),
+            path: None,
+            alt: self.alt.clone(),
+            width: self.size.map(|(width, _)| width),
+            height: self.size.map(|(_, height)| height),
+        }
+    }
+}
+
+/// Converts English Metric Units to pixels at 96 DPI (9525 EMU per pixel).
+fn emu_to_px(emu: isize) -> u32 {
+    u32::try_from((emu.max(0) + 4762) / 9525).unwrap_or(u32::MAX)
+}
+
+/// Returns an SVG document as `<svg>` markup on a single line, without its
+/// XML declaration, comments before it or doctype, so it can sit inside a
+/// paragraph.
+fn svg_markup(data: &[u8]) -> String {
+    let svg = String::from_utf8_lossy(data);
+    let svg = svg.find("<svg").map_or(&*svg, |start| &svg[start..]);
+    svg.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the file extension of a media path, defaulting to `png`.
+fn extension(image_path: &str) -> &str {
+    Path::new(image_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("png")
+}
+
+/// Writes the `counter`-th image to `dir` and returns its path.
+fn save_image(dir: &Path, counter: usize, ext: &str, data: &[u8]) -> Result<PathBuf> {
+    let output_path = dir.join(format!("image_{}.{}", counter, ext));
+    fs::write(&output_path, data)?;
+    logging::debug!("wrote {} ({} bytes)", output_path.display(), data.len());
+    Ok(output_path)
+}
+
+/// Builds a `/`-separated link from `base_dir` to `target`, e.g. `./assets/image_1.png`.
+fn relative_link(base_dir: &Path, target: &Path) -> Result<String> {
+    let cwd = std::env::current_dir()?;
+    let base = normalize(&cwd.join(base_dir));
+    let target_path = cwd.join(target);
+    let target = normalize(&target_path);
+
+    let common = base
+        .iter()
+        .zip(target.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        // Different roots (e.g. Windows drives): no relative form exists.
+        return Ok(target_path.display().to_string());
+    }
+
+    let mut parts = match base.len() - common {
+        0 => vec![".".to_string()],
+        ups => vec!["..".to_string(); ups],
+    };
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|part| part.to_string_lossy().into_owned()),
+    );
+    Ok(parts.join("/"))
+}
+
+/// Lexically resolves `.` and `..` in an absolute path, returning its components.
+fn normalize(path: &Path) -> Vec<std::ffi::OsString> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if parts.len() > 1 {
+                    parts.pop();
+                }
+            }
+            other => parts.push(other.as_os_str().to_os_string()),
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_link_is_relative_to_markdown_dir() {
+        let link = |base: &str, target: &str| {
+            relative_link(Path::new(base), Path::new(target)).expect("cwd should be readable")
+        };
+
+        assert_eq!(
+            link("out", "out/assets/image_1.png"),
+            "./assets/image_1.png"
+        );
+        assert_eq!(
+            link("out/docs", "out/assets/image_1.png"),
+            "../assets/image_1.png"
+        );
+        assert_eq!(link("./out/", "out/./x/../image_1.png"), "./image_1.png");
+        assert_eq!(
+            link("/srv/site/docs", "/srv/media/a.png"),
+            "../../media/a.png"
+        );
+        assert!(link("/srv/site", "assets/a.png").ends_with("/assets/a.png"));
+    }
+
+    fn archive(parts: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in parts {
+            archive
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            std::io::Write::write_all(&mut archive, data).unwrap();
+        }
+        archive.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_svg_versions_are_preferred_over_rasters() {
+        let svg = b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <rect/>\n</svg>\n";
+        let bytes = archive(&[
+            ("word/media/image1.png", b"png"),
+            ("word/media/image2.svg", svg),
+        ]);
+        let rels = HashMap::from([
+            ("rId1".to_string(), "media/image1.png".to_string()),
+            ("rId2".to_string(), "media/image2.svg".to_string()),
+        ]);
+        let svg_blips = HashMap::from([("rId1".to_string(), "rId2".to_string())]);
+
+        let mut extractor = ImageExtractor::new_inline_from_bytes(&bytes).unwrap();
+        extractor.set_svg_blips(svg_blips.clone());
+        let image = extractor.extract_from_rel_id("rId1", &rels).unwrap();
+        assert_eq!(
+            image.unwrap(),
+            format!(
+                "<img src=\"data:image/svg+xml;base64,{}\" alt=\"image\" />",
+                BASE64.encode(svg)
+            )
+        );
+
+        let mut extractor = ImageExtractor::new_inline_from_bytes(&bytes)
+            .unwrap()
+            .with_svg_markup();
+        extractor.set_svg_blips(svg_blips);
+        assert_eq!(
+            extractor.extract_from_rel_id("rId1", &rels).unwrap(),
+            Some("<svg xmlns=\"http://www.w3.org/2000/svg\"> <rect/> </svg>".to_string())
+        );
+
+        // The raster is kept when the SVG part is missing.
+        let rels = HashMap::from([
+            ("rId1".to_string(), "media/image1.png".to_string()),
+            ("rId2".to_string(), "media/gone.svg".to_string()),
+        ]);
+        let mut extractor = ImageExtractor::new_inline_from_bytes(&bytes).unwrap();
+        extractor.set_svg_blips(HashMap::from([("rId1".to_string(), "rId2".to_string())]));
+        assert!(extractor
+            .extract_from_rel_id("rId1", &rels)
+            .unwrap()
+            .is_some_and(|image| image.contains("image/png")));
+        assert!(extractor.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_missing_relationships_and_media_are_recorded() {
+        let bytes = archive(&[("word/media/image1.png", b"png")]);
+
+        let rels = HashMap::from([
+            ("rId1".to_string(), "media/image1.png".to_string()),
+            ("rId2".to_string(), "media/image2.png".to_string()),
+        ]);
+        let mut extractor = ImageExtractor::new_inline_from_bytes(&bytes).unwrap();
+        for rel_id in ["rId1", "rId2", "rId3", "rId2"] {
+            let image = extractor.extract_from_rel_id(rel_id, &rels).unwrap();
+            assert_eq!(image.is_some(), rel_id == "rId1");
+        }
+
+        assert_eq!(
+            extractor.take_warnings(),
+            [
+                ConversionWarning::MissingMedia {
+                    rid: "rId2".to_string(),
+                    target: Some("media/image2.png".to_string()),
+                },
+                ConversionWarning::MissingMedia {
+                    rid: "rId3".to_string(),
+                    target: None,
+                },
+            ]
+        );
+        assert!(extractor.take_warnings().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Alphabetical index - regenerated from the index entry (`XE`) fields.
+//!
+//! Word's `INDEX` field result is derived content: page numbers of the
+//! entries. With `IndexHandling::Links` the entries are collected before
+//! conversion, each gets an anchor where it occurs, and the index is written
+//! as a nested list linking to them.
+
+use super::paragraph::ParagraphConverter;
+use super::raw_xml::element_key;
+use crate::render::{escape_markdown_link_destination, escape_markdown_link_text};
+use rs_docx::document::{
+    BodyContent, Paragraph, ParagraphContent, TableCellContent, TableRowContent,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Index entries of a document body and the anchors of their occurrences.
+#[derive(Debug, Default)]
+pub(crate) struct IndexEntries {
+    /// Anchors of the entries in each paragraph, in field order.
+    by_paragraph: HashMap<usize, Vec<String>>,
+    /// Root of the term tree; only its subterms are listed.
+    terms: IndexTerm,
+    count: usize,
+}
+
+/// A term with the anchors of its occurrences and its subentries
+/// (`XE "Term:Subentry"`), keyed case-insensitively.
+#[derive(Debug, Default)]
+struct IndexTerm {
+    name: String,
+    anchors: Vec<String>,
+    subterms: BTreeMap<String, IndexTerm>,
+}
+
+impl IndexEntries {
+    /// Numbers the index entries in document order as `xe-1`, `xe-2`, ...
+    pub fn build(body: &[BodyContent<'_>]) -> Self {
+        let mut entries = Self::default();
+        entries.visit(body);
+        entries
+    }
+
+    /// Returns the anchor of the `n`th index entry of a paragraph.
+    pub fn anchor(&self, para: &Paragraph<'_>, n: usize) -> Option<&str> {
+        self.by_paragraph
+            .get(&element_key(para))
+            .and_then(|anchors| anchors.get(n))
+            .map(String::as_str)
+    }
+
+    /// Renders the index as a nested list, sorted by term. Each term links to
+    /// its first occurrence and lists the others as `[2]`, `[3]`, ...; with
+    /// `links` false only the terms are listed.
+    pub fn render(&self, links: bool) -> String {
+        let mut lines = Vec::new();
+        for term in self.terms.subterms.values() {
+            term.render(0, links, &mut lines);
+        }
+        lines.join("\n")
+    }
+
+    fn visit(&mut self, body: &[BodyContent<'_>]) {
+        for content in body {
+            match content {
+                BodyContent::Paragraph(para) => self.add_paragraph(para),
+                BodyContent::Sdt(sdt) => {
+                    if let Some(sdt_content) = &sdt.content {
+                        self.visit(&sdt_content.content);
+                    }
+                }
+                BodyContent::Table(table) => {
+                    for row in &table.rows {
+                        for cell in &row.cells {
+                            let TableRowContent::TableCell(cell) = cell else {
+                                continue;
+                            };
+                            for item in &cell.content {
+                                if let TableCellContent::Paragraph(para) = item {
+                                    self.add_paragraph(para);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_paragraph(&mut self, para: &Paragraph<'_>) {
+        let mut anchors = Vec::new();
+        for entry in ParagraphConverter::index_entries(para) {
+            self.count += 1;
+            let anchor = format!("xe-{}", self.count);
+            let mut term = &mut self.terms;
+            for part in entry
+                .split(':')
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+            {
+                term = term
+                    .subterms
+                    .entry(part.to_lowercase())
+                    .or_insert_with(|| IndexTerm {
+                        name: part.to_string(),
+                        ..Default::default()
+                    });
+            }
+            term.anchors.push(anchor.clone());
+            anchors.push(anchor);
+        }
+        if !anchors.is_empty() {
+            self.by_paragraph.insert(element_key(para), anchors);
+        }
+
+        // Content controls inside the paragraph are converted as paragraphs too.
+        for content in &para.content {
+            if let ParagraphContent::SDT(sdt) = content {
+                if let Some(sdt_content) = &sdt.content {
+                    self.visit(&sdt_content.content);
+                }
+            }
+        }
+    }
+}
+
+impl IndexTerm {
+    fn render(&self, depth: usize, links: bool, lines: &mut Vec<String>) {
+        let name = escape_markdown_link_text(&self.name);
+        let mut line = format!("{}- ", "  ".repeat(depth));
+        match self.anchors.split_first() {
+            Some((first, others)) if links => {
+                line.push_str(&format!(
+                    "[{}](#{})",
+                    name,
+                    escape_markdown_link_destination(first)
+                ));
+                for (n, anchor) in others.iter().enumerate() {
+                    line.push_str(&format!(
+                        ", [{}](#{})",
+                        n + 2,
+                        escape_markdown_link_destination(anchor)
+                    ));
+                }
+            }
+            _ => line.push_str(&name),
+        }
+        lines.push(line);
+        for term in self.subterms.values() {
+            term.render(depth + 1, links, lines);
+        }
+    }
+}
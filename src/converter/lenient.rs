@@ -0,0 +1,164 @@
+//! Lenient parsing - parses a package part by part for `convert_lenient`.
+//!
+//! `DocxFile::parse` fails as a whole when any part is malformed, and a bug
+//! in rs_docx may panic instead. Here every part the conversion reads is
+//! parsed on its own behind `catch_unwind`; a part that fails is left out and
+//! reported as a warning.
+
+use super::logging;
+use super::ConversionWarning;
+use crate::Result;
+use hard_xml::XmlRead;
+use rs_docx::document::{Comments, Document, EndNotes, FootNotes, Numbering};
+use rs_docx::rels::Relationships;
+use rs_docx::styles::Styles;
+use rs_docx::Docx;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+const DOCUMENT: &str = "word/document.xml";
+const STYLES: &str = "word/styles.xml";
+const NUMBERING: &str = "word/numbering.xml";
+const FOOTNOTES: &str = "word/footnotes.xml";
+const ENDNOTES: &str = "word/endnotes.xml";
+const COMMENTS: &str = "word/comments.xml";
+const DOCUMENT_RELS: &str = "word/_rels/document.xml.rels";
+
+/// The parts the conversion reads, in the order they are parsed.
+const PARTS: &[&str] = &[
+    DOCUMENT,
+    DOCUMENT_RELS,
+    STYLES,
+    NUMBERING,
+    FOOTNOTES,
+    ENDNOTES,
+    COMMENTS,
+];
+
+/// The XML of the parts a conversion reads, or why a part could not be read.
+/// Parts missing from the archive are absent.
+#[derive(Debug, Default)]
+pub(crate) struct PackageParts {
+    parts: HashMap<&'static str, std::result::Result<String, String>>,
+}
+
+impl PackageParts {
+    /// Reads the parts from a DOCX archive. Only an unreadable archive is an
+    /// error; a part that cannot be read is recorded as failed.
+    pub fn read<R: Read + Seek>(reader: R) -> Result<Self> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+        let mut parts = HashMap::new();
+        for &name in PARTS {
+            let Ok(mut file) = archive.by_name(name) else {
+                continue;
+            };
+            let mut xml = String::new();
+            let read = file
+                .read_to_string(&mut xml)
+                .map(|_| xml)
+                .map_err(|e| e.to_string());
+            parts.insert(name, read);
+        }
+        Ok(Self { parts })
+    }
+
+    /// Builds a `Docx` from the parts that parse, pushing a
+    /// `ConversionWarning::MalformedPart` for each one that does not. A
+    /// malformed main document leaves the body empty.
+    pub fn parse(&self, warnings: &mut Vec<ConversionWarning>) -> Docx<'_> {
+        let mut docx = Docx::default();
+        if let Some(document) = self.parse_part::<Document>(DOCUMENT, warnings) {
+            docx.document = document;
+        }
+        docx.document_rels = self.parse_part::<Relationships>(DOCUMENT_RELS, warnings);
+        docx.styles = self
+            .parse_part::<Styles>(STYLES, warnings)
+            .unwrap_or_default();
+        docx.numbering = self.parse_part::<Numbering>(NUMBERING, warnings);
+        docx.footnotes = self.parse_part::<FootNotes>(FOOTNOTES, warnings);
+        docx.endnotes = self.parse_part::<EndNotes>(ENDNOTES, warnings);
+        docx.comments = self.parse_part::<Comments>(COMMENTS, warnings);
+        docx
+    }
+
+    fn parse_part<'a, T: XmlRead<'a>>(
+        &'a self,
+        name: &str,
+        warnings: &mut Vec<ConversionWarning>,
+    ) -> Option<T> {
+        let reason = match self.parts.get(name)? {
+            Ok(xml) => match catch_panic(|| T::from_str(xml)) {
+                Ok(Ok(part)) => return Some(part),
+                Ok(Err(e)) => e.to_string(),
+                Err(reason) => reason,
+            },
+            Err(reason) => reason.clone(),
+        };
+        logging::warning!("skipping malformed part {}: {}", name, reason);
+        warnings.push(ConversionWarning::MalformedPart {
+            part: name.to_string(),
+            reason,
+        });
+        None
+    }
+}
+
+/// Runs `f`, turning a panic into its message.
+///
+/// The panic hook still runs, so the message is also printed to stderr by
+/// default.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_parts_are_skipped_with_a_warning() {
+        let mut parts = PackageParts::default();
+        parts.parts.insert(
+            DOCUMENT,
+            Ok(r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t>Kept</w:t></w:r></w:p></w:body></w:document>"#.to_string()),
+        );
+        parts
+            .parts
+            .insert(STYLES, Ok("<w:styles><w:style".to_string()));
+        parts
+            .parts
+            .insert(NUMBERING, Err("invalid UTF-8".to_string()));
+
+        let mut warnings = Vec::new();
+        let docx = parts.parse(&mut warnings);
+        assert_eq!(docx.document.body.content.len(), 1);
+        assert!(docx.numbering.is_none());
+        let parts: Vec<&str> = warnings
+            .iter()
+            .map(|warning| match warning {
+                ConversionWarning::MalformedPart { part, .. } => part.as_str(),
+                other => panic!("unexpected warning {:?}", other),
+            })
+            .collect();
+        assert_eq!(parts, [STYLES, NUMBERING]);
+    }
+
+    #[test]
+    fn test_panics_become_messages() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        assert_eq!(
+            catch_panic(|| -> () { panic!("bad {}", "part") }),
+            Err("bad part".to_string())
+        );
+    }
+}
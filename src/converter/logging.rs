@@ -0,0 +1,46 @@
+//! Diagnostics through the [`log`](https://docs.rs/log) facade, enabled by
+//! the `log` feature.
+//!
+//! Without the feature the macros only wrap their arguments in an unused
+//! `format_args!`, which is never formatted, and [`phase`] calls its closure
+//! directly, so no logging code is compiled in.
+
+/// Logs a debug event under the `dm2xcod` target.
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::debug!(target: "dm2xcod", $($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+/// Logs a warning under the `dm2xcod` target, e.g. for skipped content.
+macro_rules! warning {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::warn!(target: "dm2xcod", $($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+pub(crate) use {debug, warning};
+
+/// Runs one phase of a conversion (`parse`, `extract`, `render`), logging
+/// how long it took.
+#[inline]
+pub(crate) fn phase<T>(name: &str, run: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "log")]
+    {
+        let start = std::time::Instant::now();
+        let result = run();
+        debug!("{} finished in {:?}", name, start.elapsed());
+        result
+    }
+    #[cfg(not(feature = "log"))]
+    {
+        let _ = name;
+        run()
+    }
+}
@@ -0,0 +1,78 @@
+//! Conversion manifest - what a conversion found besides the Markdown itself.
+//!
+//! The manifest serializes to JSON (see the README for the schema), which the
+//! CLI writes with `--manifest`.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Something the conversion skipped because the document is broken.
+///
+/// Serialized with a `kind` field of `missing_media`, `corrupt_media`,
+/// `malformed_part`, `truncated_body`, `unsupported_math` or
+/// `repeated_heading`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ConversionWarning {
+    /// An image that could not be converted: its relationship id is not in
+    /// `word/_rels/document.xml.rels` (`target` is `None`), or its target is
+    /// not in the archive.
+    MissingMedia { rid: String, target: Option<String> },
+    /// An image whose media part could not be read, such as a truncated or
+    /// empty entry. It is written as an `[image]` placeholder.
+    CorruptMedia {
+        rid: String,
+        target: String,
+        reason: String,
+    },
+    /// A package part (e.g. `word/styles.xml`) that
+    /// [`convert_lenient`](super::DocxToMarkdown::convert_lenient) could not
+    /// read or parse. The conversion went on without it.
+    MalformedPart { part: String, reason: String },
+    /// A panic that stopped
+    /// [`convert_lenient`](super::DocxToMarkdown::convert_lenient) converting
+    /// the body. The blocks converted before it are kept; the notes are not.
+    TruncatedBody { reason: String },
+    /// An Office Math structure, named like its element (e.g. `m:f` for a
+    /// fraction), that an equation lost in conversion: equations are written
+    /// as their text, a placeholder or a preview image. Reported with
+    /// `warn_unsupported_math`.
+    UnsupportedMath { construct: String },
+    /// A heading directly followed, with no other heading in between, by
+    /// headings of the same level and text; `paragraphs` are their positions
+    /// among the document's paragraphs, counted from 0 in document order.
+    /// Reported with `RepeatedHeadings::Warn` and `Merge`.
+    RepeatedHeading {
+        text: String,
+        level: usize,
+        paragraphs: Vec<usize>,
+    },
+}
+
+/// An image written to the output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestImage {
+    /// Relationship id the document refers to the image by.
+    pub rid: String,
+    /// Relationship target of the media part (e.g. `media/image1.png`).
+    pub target: String,
+    /// Where the image was saved; `None` when it was embedded inline.
+    pub path: Option<PathBuf>,
+    /// Alt text of the drawing (`descr`).
+    pub alt: Option<String>,
+    /// Displayed width in pixels at 96 DPI, from the drawing's extent.
+    pub width: Option<u32>,
+    /// Displayed height in pixels at 96 DPI, from the drawing's extent.
+    pub height: Option<u32>,
+}
+
+/// Details of a conversion, returned by
+/// [`DocxToMarkdown::convert_with_manifest`](super::DocxToMarkdown::convert_with_manifest).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConversionManifest {
+    /// Images written to the output, in document order.
+    pub images: Vec<ManifestImage>,
+    /// Problems found while converting, in document order, each reported once.
+    pub warnings: Vec<ConversionWarning>,
+}
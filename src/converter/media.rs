@@ -0,0 +1,215 @@
+//! Media reader - lists the images stored in a DOCX archive.
+//!
+//! Only the package parts are read (`word/media/*`, the main document and its
+//! relationships); no paragraph or table conversion takes place.
+
+use super::context::part_path;
+use super::raw_xml::unescape_xml;
+use crate::Result;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::PathBuf;
+use xmlparser::{Token, Tokenizer};
+
+/// An image extracted from a DOCX archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedImage {
+    /// Archive path of the media part (e.g. `word/media/image1.png`).
+    pub name: String,
+    /// Alt text from the drawing that shows the image (`descr`, else `title`).
+    pub alt_text: Option<String>,
+    /// Raw image bytes.
+    pub data: Vec<u8>,
+    /// Where the image was written, with `ImageHandling::SaveToDir`.
+    pub path: Option<PathBuf>,
+}
+
+/// Media part read from the archive, before it is saved.
+pub(crate) struct MediaPart {
+    pub name: String,
+    pub alt_text: Option<String>,
+    pub data: Vec<u8>,
+}
+
+/// Reads every `word/media/` part in archive order, with its alt text.
+pub(crate) fn read_media<R: Read + Seek>(reader: R) -> Result<Vec<MediaPart>> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+
+    let alt_texts = match (
+        read_part(&mut archive, "word/document.xml")?,
+        read_part(&mut archive, "word/_rels/document.xml.rels")?,
+    ) {
+        (Some(document), Some(rels)) => image_alt_texts(&document, &rels),
+        _ => HashMap::new(),
+    };
+
+    let names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with("word/media/") && !name.ends_with('/'))
+        .map(str::to_owned)
+        .collect();
+
+    let mut media = Vec::with_capacity(names.len());
+    for name in names {
+        let mut data = Vec::new();
+        archive.by_name(&name)?.read_to_end(&mut data)?;
+        media.push(MediaPart {
+            alt_text: alt_texts.get(&name).cloned(),
+            name,
+            data,
+        });
+    }
+    Ok(media)
+}
+
+fn read_part<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>> {
+    match archive.by_name(name) {
+        Ok(mut entry) => {
+            let mut xml = String::new();
+            entry.read_to_string(&mut xml)?;
+            Ok(Some(xml))
+        }
+        Err(zip::result::ZipError::FileNotFound) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Maps media archive paths to the alt text of the first drawing showing them.
+///
+/// DrawingML images take `wp:docPr` `descr` (else `title`) and reference the
+/// image by `a:blip r:embed`; VML images carry `o:title` on `v:imagedata`.
+fn image_alt_texts(document: &str, rels: &str) -> HashMap<String, String> {
+    let targets = relationship_targets(rels);
+    let mut alt_texts = HashMap::new();
+    let mut element = ("", "");
+    let mut alt: Option<String> = None;
+    let mut vml_rel_id: Option<&str> = None;
+    let mut vml_title: Option<String> = None;
+
+    for token in Tokenizer::from(document) {
+        let Ok(token) = token else {
+            break;
+        };
+        match token {
+            Token::ElementStart { prefix, local, .. } => {
+                element = (prefix.as_str(), local.as_str());
+                match element {
+                    ("wp", "docPr") => alt = None,
+                    ("v", "imagedata") => {
+                        vml_rel_id = None;
+                        vml_title = None;
+                    }
+                    _ => {}
+                }
+            }
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => {
+                let value = value.as_str();
+                match (element, prefix.as_str(), local.as_str()) {
+                    (("wp", "docPr"), "", "descr") if !value.trim().is_empty() => {
+                        alt = Some(unescape_xml(value));
+                    }
+                    (("wp", "docPr"), "", "title") if !value.trim().is_empty() => {
+                        alt.get_or_insert_with(|| unescape_xml(value));
+                    }
+                    (("a", "blip"), "r", "embed") => {
+                        if let (Some(alt), Some(target)) = (&alt, targets.get(value)) {
+                            alt_texts
+                                .entry(target.clone())
+                                .or_insert_with(|| alt.clone());
+                        }
+                    }
+                    (("v", "imagedata"), "r", "id") => vml_rel_id = Some(value),
+                    (("v", "imagedata"), "o", "title") if !value.trim().is_empty() => {
+                        vml_title = Some(unescape_xml(value));
+                    }
+                    _ => {}
+                }
+            }
+            Token::ElementEnd { .. } if element == ("v", "imagedata") => {
+                if let (Some(title), Some(target)) =
+                    (vml_title.take(), vml_rel_id.and_then(|id| targets.get(id)))
+                {
+                    alt_texts.entry(target.clone()).or_insert(title);
+                }
+                element = ("", "");
+            }
+            _ => {}
+        }
+    }
+    alt_texts
+}
+
+/// Maps relationship ids to the archive paths of internal targets.
+pub(crate) fn relationship_targets(rels: &str) -> HashMap<String, String> {
+    let mut targets = HashMap::new();
+    let mut id = None;
+    let mut target = None;
+    let mut external = false;
+
+    for token in Tokenizer::from(rels) {
+        let Ok(token) = token else {
+            break;
+        };
+        match token {
+            Token::ElementStart { local, .. } if local.as_str() == "Relationship" => {
+                id = None;
+                target = None;
+                external = false;
+            }
+            Token::Attribute { local, value, .. } => match local.as_str() {
+                "Id" => id = Some(value.as_str().to_string()),
+                "Target" => target = Some(unescape_xml(value.as_str())),
+                "TargetMode" => external = value.as_str() == "External",
+                _ => {}
+            },
+            Token::ElementEnd { .. } => {
+                if let (Some(id), Some(target), false) = (id.take(), target.take(), external) {
+                    targets.insert(id, part_path(&target));
+                }
+            }
+            _ => {}
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_alt_texts_follow_relationships() {
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png"/>
+            <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="/word/media/image2.jpeg"/>
+            <Relationship Id="rId6" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image3.gif"/>
+            <Relationship Id="rId7" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com" TargetMode="External"/>
+        </Relationships>"#;
+        let document = r#"<w:document xmlns:w="w" xmlns:wp="wp" xmlns:a="a" xmlns:r="r" xmlns:v="v" xmlns:o="o"><w:body>
+            <w:p><w:r><w:drawing><wp:inline><wp:docPr id="1" name="Picture 1" descr="Sales &amp; costs" title="Chart"/>
+                <a:graphic><a:graphicData><pic:pic xmlns:pic="pic"><pic:blipFill><a:blip r:embed="rId4"/></pic:blipFill></pic:pic></a:graphicData></a:graphic>
+            </wp:inline></w:drawing></w:r></w:p>
+            <w:p><w:r><w:drawing><wp:anchor><wp:docPr id="2" name="Picture 2" title="Logo"/>
+                <a:graphic><a:graphicData><pic:pic xmlns:pic="pic"><pic:blipFill><a:blip r:embed="rId5"/></pic:blipFill></pic:pic></a:graphicData></a:graphic>
+            </wp:anchor></w:drawing></w:r></w:p>
+            <w:p><w:r><w:drawing><wp:inline><wp:docPr id="3" name="Picture 3"/>
+                <a:graphic><a:graphicData><pic:pic xmlns:pic="pic"><pic:blipFill><a:blip r:embed="rId4"/></pic:blipFill></pic:pic></a:graphicData></a:graphic>
+            </wp:inline></w:drawing></w:r></w:p>
+            <w:p><w:r><w:pict><v:shape><v:imagedata r:id="rId6" o:title="Scanned page"/></v:shape></w:pict></w:r></w:p>
+        </w:body></w:document>"#;
+
+        let alt_texts = image_alt_texts(document, rels);
+        assert_eq!(alt_texts.len(), 3);
+        assert_eq!(alt_texts["word/media/image1.png"], "Sales & costs");
+        assert_eq!(alt_texts["word/media/image2.jpeg"], "Logo");
+        assert_eq!(alt_texts["word/media/image3.gif"], "Scanned page");
+    }
+}
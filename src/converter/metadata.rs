@@ -0,0 +1,116 @@
+//! Document metadata - core (`docProps/core.xml`) and extended
+//! (`docProps/app.xml`) document properties.
+
+use rs_docx::app::App;
+use rs_docx::core::Core;
+use std::borrow::Cow;
+
+/// Document properties from `docProps/core.xml` and `docProps/app.xml`.
+///
+/// Every field is `None` when the property is missing or blank.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentMetadata {
+    /// Document title (`dc:title`).
+    pub title: Option<String>,
+    /// Subject (`dc:subject`).
+    pub subject: Option<String>,
+    /// Author (`dc:creator`).
+    pub creator: Option<String>,
+    /// Keywords (`cp:keywords`), as written.
+    pub keywords: Option<String>,
+    /// Description or comments (`dc:description`).
+    pub description: Option<String>,
+    /// Category (`cp:category`).
+    pub category: Option<String>,
+    /// Language (`dc:language`).
+    pub language: Option<String>,
+    /// Status such as "Draft" (`cp:contentStatus`).
+    pub content_status: Option<String>,
+    /// Who saved the document last (`cp:lastModifiedBy`).
+    pub last_modified_by: Option<String>,
+    /// Revision number (`cp:revision`).
+    pub revision: Option<u32>,
+    /// Creation time as stored, usually W3CDTF (`dcterms:created`).
+    pub created: Option<String>,
+    /// Last modification time as stored, usually W3CDTF (`dcterms:modified`).
+    pub modified: Option<String>,
+    /// Application that wrote the document (`Application`).
+    pub application: Option<String>,
+    /// Version of that application (`AppVersion`).
+    pub app_version: Option<String>,
+    /// Company (`Company`).
+    pub company: Option<String>,
+    /// Attached template (`Template`).
+    pub template: Option<String>,
+    /// Total editing time in minutes (`TotalTime`).
+    pub total_editing_minutes: Option<u32>,
+    /// Page count when last saved (`Pages`).
+    pub pages: Option<u32>,
+    /// Word count when last saved (`Words`).
+    pub words: Option<u32>,
+    /// Character count, excluding spaces, when last saved (`Characters`).
+    pub characters: Option<u32>,
+    /// Paragraph count when last saved (`Paragraphs`).
+    pub paragraphs: Option<u32>,
+}
+
+// Both parts come in a namespaced and a plain variant with the same fields.
+macro_rules! copy_core {
+    ($metadata:ident, $core:ident) => {{
+        $metadata.title = text(&$core.title);
+        $metadata.subject = text(&$core.subject);
+        $metadata.creator = text(&$core.creator);
+        $metadata.keywords = text(&$core.keywords);
+        $metadata.description = text(&$core.description);
+        $metadata.category = text(&$core.category);
+        $metadata.language = text(&$core.language);
+        $metadata.content_status = text(&$core.content_status);
+        $metadata.last_modified_by = text(&$core.last_modified_by);
+        $metadata.revision = number(&$core.revision);
+        $metadata.created = text(&$core.created);
+        $metadata.modified = text(&$core.modified);
+    }};
+}
+
+macro_rules! copy_app {
+    ($metadata:ident, $app:ident) => {{
+        $metadata.application = text(&$app.application);
+        $metadata.app_version = text(&$app.app_version);
+        $metadata.company = text(&$app.company);
+        $metadata.template = text(&$app.template);
+        $metadata.total_editing_minutes = number(&$app.total_time);
+        $metadata.pages = number(&$app.pages);
+        $metadata.words = number(&$app.words);
+        $metadata.characters = number(&$app.characters);
+        $metadata.paragraphs = number(&$app.paragraphs);
+    }};
+}
+
+impl DocumentMetadata {
+    pub(crate) fn from_docx(docx: &rs_docx::Docx<'_>) -> Self {
+        let mut metadata = Self::default();
+        match &docx.core {
+            Some(Core::CoreNamespace(core)) => copy_core!(metadata, core),
+            Some(Core::CoreNoNamespace(core)) => copy_core!(metadata, core),
+            None => {}
+        }
+        match &docx.app {
+            Some(App::AppNoApNamespace(app)) => copy_app!(metadata, app),
+            Some(App::AppWithApNamespace(app)) => copy_app!(metadata, app),
+            None => {}
+        }
+        metadata
+    }
+}
+
+fn text(value: &Option<Cow<'_, str>>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_owned)
+}
+
+fn number(value: &Option<Cow<'_, str>>) -> Option<u32> {
+    value.as_deref().and_then(|value| value.trim().parse().ok())
+}
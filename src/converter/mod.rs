@@ -2,36 +2,88 @@
 
 mod hyperlink;
 mod image;
+mod manifest;
+mod media;
+mod metadata;
 mod numbering;
 mod paragraph;
 mod run;
 
+mod bibliography;
+mod block_iter;
+mod chart;
+mod code_block;
+mod comments;
 mod context;
+mod figure;
+mod fragment;
+mod glossary;
+mod index;
+mod lenient;
+mod logging;
+mod nesting;
+mod raw_xml;
+mod repeated_headings;
+mod slug;
 mod styles;
+mod symbols;
+mod tab_table;
 mod table;
 mod table_grid;
+mod text_only;
 
 use crate::adapters::docx::{AstExtractor, DocxExtractor};
+use crate::adapters::DocumentExtractor;
+use crate::core::ast::{DocumentAst, ReferenceDefinitions};
 #[cfg(test)]
 use crate::render::escape_html_attr;
-use crate::render::{MarkdownRenderer, Renderer};
-use crate::{error::Error, ConvertOptions, ImageHandling, Result};
-#[cfg(test)]
+use crate::render::{MarkdownRenderer, PlainTextRenderer, Renderer};
+use crate::{error::Error, ConvertOptions, ImageHandling, LineEnding, Result};
 use rs_docx::document::BodyContent;
 use rs_docx::DocxFile;
 use std::collections::HashMap;
 use std::path::Path;
 
+pub use self::block_iter::BlockIter;
 pub use self::context::ConversionContext;
+pub use self::fragment::FragmentContext;
 pub use self::hyperlink::resolve_hyperlink;
 pub use self::image::ImageExtractor;
-pub use self::numbering::NumberingResolver;
+pub use self::manifest::{ConversionManifest, ConversionWarning, ManifestImage};
+pub use self::media::ExtractedImage;
+pub use self::metadata::DocumentMetadata;
+pub use self::numbering::{ListMarker, NumberingResolver};
 pub use self::paragraph::ParagraphConverter;
 pub use self::run::RunConverter;
 pub use self::styles::StyleResolver;
 pub use self::table::TableConverter;
 
+pub(crate) use self::code_block::CodeBlock;
+pub(crate) use self::slug::github_slug;
+pub(crate) use self::tab_table::convert_blocks as convert_tab_tables;
+
+/// A converted document with its notes split off, returned by
+/// [`DocxToMarkdown::convert_with_notes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownWithNotes {
+    /// The document, with note markers but without note definitions.
+    pub markdown: String,
+    /// The note definitions, one `[^label]: text` line each.
+    pub notes: String,
+}
+
+/// A converted document with the details of its conversion, returned by
+/// [`DocxToMarkdown::convert_with_manifest`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownWithManifest {
+    /// The converted document, as returned by [`DocxToMarkdown::convert`].
+    pub markdown: String,
+    /// What the conversion skipped or could not resolve.
+    pub manifest: ConversionManifest,
+}
+
 /// Main converter struct that orchestrates DOCX to Markdown conversion.
+#[derive(Clone)]
 pub struct DocxToMarkdown<E = DocxExtractor, R = MarkdownRenderer> {
     options: ConvertOptions,
     extractor: E,
@@ -95,23 +147,253 @@ where
     /// # Returns
     /// The converted Markdown content as a String.
     pub fn convert<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        self.convert_with_renderer(path.as_ref(), &self.renderer)
+    }
+
+    /// Converts a DOCX file, returning the footnotes, endnotes and comments
+    /// separately instead of appended to the document.
+    ///
+    /// `markdown` is rendered without the notes section and keeps its `[^1]`,
+    /// `[^en1]` and `[^c<id>]` markers; `notes` holds the matching Markdown
+    /// definitions (`[^1]: ...`) for a separate notes file, and is empty when
+    /// the document has no notes.
+    pub fn convert_with_notes<P: AsRef<Path>>(&self, path: P) -> Result<MarkdownWithNotes> {
+        let mut document = self.extract_path(path.as_ref(), self.renderer.plain_text())?;
+        let references = std::mem::take(&mut document.references);
+        Ok(MarkdownWithNotes {
+            markdown: self.render_document(&self.renderer, &document)?,
+            notes: self.finish_output(MarkdownRenderer::render_notes(&references)),
+        })
+    }
+
+    /// Converts a DOCX file, also returning a manifest of the conversion.
+    ///
+    /// The manifest lists images that were skipped because their relationship
+    /// id is unknown or their media part is missing from the archive. Images
+    /// are not looked up with `ImageHandling::Skip`, so none are reported then.
+    pub fn convert_with_manifest<P: AsRef<Path>>(&self, path: P) -> Result<MarkdownWithManifest> {
+        let (document, manifest) =
+            self.extract_path_with_manifest(path.as_ref(), self.renderer.plain_text())?;
+        Ok(MarkdownWithManifest {
+            markdown: self.render_document(&self.renderer, &document)?,
+            manifest,
+        })
+    }
+
+    /// Converts a possibly malformed or hostile DOCX file, returning whatever
+    /// could be converted with warnings about the rest.
+    ///
+    /// Panics are caught at these boundaries:
+    /// - each package part the conversion reads (`word/document.xml`, its
+    ///   relationships, styles, numbering, footnotes, endnotes and comments)
+    ///   is parsed on its own; a part that fails to parse or panics is left
+    ///   out and reported as `ConversionWarning::MalformedPart`. Other parts,
+    ///   such as headers and themes, are never parsed;
+    /// - the conversion of the body; a panic there keeps the blocks converted
+    ///   before it, drops the notes, and is reported as
+    ///   `ConversionWarning::TruncatedBody`.
+    ///
+    /// An archive that cannot be opened, and conversion errors such as
+    /// `strict_reference_validation` failures, are still returned as errors.
+    /// The panic hook is not replaced, so caught panics are still printed to
+    /// stderr by default.
+    pub fn convert_lenient<P: AsRef<Path>>(&self, path: P) -> Result<MarkdownWithManifest> {
         let path = path.as_ref();
+        let parts = lenient::PackageParts::read(std::fs::File::open(path)?)?;
+        let mut warnings = Vec::new();
+        let docx = parts.parse(&mut warnings);
+
+        let mut image_extractor = self.path_image_extractor(path)?;
+        let raw_parts = raw_xml::read_raw_parts(std::fs::File::open(path)?).ok();
+        let mut document = DocumentAst::default();
+        let extracted = lenient::catch_panic(|| {
+            let plain_text = self.renderer.plain_text();
+            self.with_context(
+                &docx,
+                &mut image_extractor,
+                raw_parts,
+                plain_text,
+                |body, context| {
+                    self.extractor.extract_blocks(body, context, &mut |block| {
+                        document.blocks.push(block);
+                        Ok(())
+                    })
+                },
+            )
+        });
+        document.references = match extracted {
+            Ok(result) => result?.1,
+            Err(reason) => {
+                logging::warning!("conversion stopped early: {}", reason);
+                warnings.push(ConversionWarning::TruncatedBody { reason });
+                ReferenceDefinitions {
+                    markers: self.options.note_markers.clone(),
+                    ..Default::default()
+                }
+            }
+        };
 
-        // Parse DOCX file
+        warnings.extend(image_extractor.take_warnings());
+        Ok(MarkdownWithManifest {
+            markdown: self.render_document(&self.renderer, &document)?,
+            manifest: ConversionManifest {
+                images: image_extractor.take_images(),
+                warnings,
+            },
+        })
+    }
+
+    /// Converts a DOCX file lazily, yielding one [`BlockNode`] at a time.
+    ///
+    /// Conversion runs on a background thread that stays at most a few blocks
+    /// ahead of the iterator, so neither the whole AST nor the Markdown string
+    /// is built; the package itself is still parsed up front. The notes the
+    /// blocks reference are available from [`BlockIter::references`] once the
+    /// iterator is exhausted.
+    ///
+    /// Errors reading the file are returned directly; parse and conversion
+    /// errors are yielded by the iterator.
+    ///
+    /// [`BlockNode`]: crate::core::ast::BlockNode
+    pub fn blocks_iter<P: AsRef<Path>>(&self, path: P) -> Result<BlockIter>
+    where
+        E: Clone + Send + 'static,
+        R: Clone + Send + 'static,
+    {
+        let path = path.as_ref();
         let docx_file =
             DocxFile::from_file(path).map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        let mut image_extractor = self.path_image_extractor(path)?;
+        let raw_parts = raw_xml::read_raw_parts(std::fs::File::open(path)?)?;
+
+        let (sender, receiver) = std::sync::mpsc::sync_channel(block_iter::BLOCK_BUFFER);
+        let converter = self.clone();
+        std::thread::spawn(move || {
+            let result =
+                converter.stream_blocks(&docx_file, &mut image_extractor, raw_parts, &sender);
+            // The receiver is gone when the iterator was dropped early.
+            let _ = sender.send(result.map(block_iter::BlockEvent::End));
+        });
+        Ok(BlockIter::new(receiver))
+    }
+
+    fn stream_blocks(
+        &self,
+        docx_file: &DocxFile,
+        image_extractor: &mut ImageExtractor,
+        raw_parts: raw_xml::RawParts,
+        sender: &block_iter::BlockSender,
+    ) -> Result<ReferenceDefinitions> {
         let docx = docx_file
             .parse()
             .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        let plain_text = self.renderer.plain_text();
+        let ((), references) = self.with_context(
+            &docx,
+            image_extractor,
+            Some(raw_parts),
+            plain_text,
+            |body, context| {
+                self.extractor.extract_blocks(body, context, &mut |block| {
+                    sender
+                        .send(Ok(block_iter::BlockEvent::Block(block)))
+                        .map_err(|_| Error::Conversion("block iterator was dropped".to_string()))
+                })
+            },
+        )?;
+        Ok(references)
+    }
 
-        // Initialize image extractor based on options
+    /// Extracts only the images of a DOCX file, skipping text conversion.
+    ///
+    /// Every part in the archive's media folder is returned with its bytes and,
+    /// when a drawing in the main document shows it, its alt text.
+    /// `ImageHandling::SaveToDir` also writes the images to that directory;
+    /// `ImageHandling::Skip` returns nothing.
+    pub fn extract_images<P: AsRef<Path>>(&self, path: P) -> Result<Vec<ExtractedImage>> {
+        let path = path.as_ref();
         let mut image_extractor = match &self.options.image_handling {
             ImageHandling::SaveToDir(dir) => ImageExtractor::new_with_dir(path, dir.clone())?,
             ImageHandling::Inline => ImageExtractor::new_inline(path)?,
+            ImageHandling::InlineUpTo { max_bytes, dir } => {
+                ImageExtractor::new_inline(path)?.with_spill_dir(*max_bytes, dir.clone())?
+            }
             ImageHandling::Skip => ImageExtractor::new_skip(),
         };
+        image_extractor.extract_media()
+    }
+
+    /// Checks that a DOCX file can be converted, without converting it.
+    ///
+    /// The package parts are parsed like for a conversion, so malformed XML
+    /// fails with `Error::DocxParse` (a missing `word/document.xml` with
+    /// `Error::Zip`), and every internal relationship of the main document
+    /// must point to a part in the archive, else `Error::RelationshipNotFound`
+    /// lists the missing targets. No paragraphs, tables or images are
+    /// converted, so this is much faster than [`Self::convert`].
+    pub fn validate<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+        archive.by_name("word/document.xml")?;
+        DocxFile::from_file(path)
+            .and_then(|docx_file| docx_file.parse().map(|_| ()))
+            .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+
+        let mut rels = String::new();
+        match archive.by_name("word/_rels/document.xml.rels") {
+            Ok(mut entry) => {
+                std::io::Read::read_to_string(&mut entry, &mut rels)?;
+            }
+            Err(zip::result::ZipError::FileNotFound) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+        let mut missing: Vec<(String, String)> = media::relationship_targets(&rels)
+            .into_iter()
+            .filter(|(_, target)| archive.index_for_name(target).is_none())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        missing.sort();
+        let missing: Vec<String> = missing
+            .into_iter()
+            .map(|(id, target)| format!("{} ({})", id, target))
+            .collect();
+        Err(Error::RelationshipNotFound(missing.join(", ")))
+    }
+
+    /// Extracts the custom XML data parts of a DOCX file as `(part name, xml)`.
+    ///
+    /// Content controls bound to form data store it in `customXml/item*.xml`
+    /// parts. They are returned unparsed, in archive order; the conversion
+    /// itself never reads them.
+    pub fn extract_custom_xml<P: AsRef<Path>>(&self, path: P) -> Result<Vec<(String, String)>> {
+        let file = std::fs::File::open(path.as_ref())?;
+        raw_xml::read_custom_xml(std::io::BufReader::new(file))
+    }
+
+    /// Reads the document properties of a DOCX file without converting it.
+    ///
+    /// Core properties (title, author, revision, dates, ...) come from
+    /// `docProps/core.xml`, extended ones (company, editing time, counts, ...)
+    /// from `docProps/app.xml`.
+    pub fn extract_metadata<P: AsRef<Path>>(&self, path: P) -> Result<DocumentMetadata> {
+        let docx_file =
+            DocxFile::from_file(path.as_ref()).map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        let docx = docx_file
+            .parse()
+            .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        Ok(DocumentMetadata::from_docx(&docx))
+    }
 
-        self.convert_inner(&docx, &mut image_extractor)
+    /// Converts a DOCX file to plain text without any Markdown markup.
+    ///
+    /// Uses the configured extractor and image handling, extracting the
+    /// document without markup for [`PlainTextRenderer`]: formatting is left
+    /// out, links and images are reduced to their text, and tables become
+    /// tab-separated rows.
+    pub fn to_plain_text<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        self.convert_with_renderer(path.as_ref(), &PlainTextRenderer)
     }
 
     /// Converts a DOCX file from bytes to Markdown.
@@ -122,35 +404,335 @@ where
     /// # Returns
     /// The converted Markdown content as a String.
     pub fn convert_from_bytes(&self, bytes: &[u8]) -> Result<String> {
+        let document = self.extract_from_bytes(bytes)?;
+        self.render_document(&self.renderer, &document)
+    }
+
+    /// Converts a DOCX already parsed with `rs_docx` to Markdown, without
+    /// parsing it again.
+    ///
+    /// `source_path` is the file `docx` was parsed from. Without it images are
+    /// skipped whatever `image_handling` says, and elements that `rs_docx`
+    /// drops (equations, charts, table captions, ...) are not recovered from
+    /// the raw XML.
+    pub fn convert_parsed(
+        &self,
+        docx: &rs_docx::Docx,
+        source_path: Option<&Path>,
+    ) -> Result<String> {
+        let (mut image_extractor, raw_parts) = match source_path {
+            Some(path) => (
+                self.path_image_extractor(path)?,
+                Some(raw_xml::read_raw_parts(std::fs::File::open(path)?)?),
+            ),
+            None => (self.configure_images(ImageExtractor::new_skip()), None),
+        };
+        let plain_text = self.renderer.plain_text();
+        let document = self.extract_document(docx, &mut image_extractor, raw_parts, plain_text)?;
+        self.render_document(&self.renderer, &document)
+    }
+
+    /// Converts a slice of body content parsed elsewhere, such as a section
+    /// cut out of a larger document, to Markdown.
+    ///
+    /// `context` supplies the styles, numbering, relationships and notes the
+    /// content refers to; any of them may be left empty. Images are skipped,
+    /// as there is no package to read them from, and elements that `rs_docx`
+    /// drops are not recovered. Notes the fragment references are appended
+    /// as usual.
+    pub fn convert_fragment(
+        &self,
+        content: &[BodyContent<'_>],
+        context: &FragmentContext<'_>,
+    ) -> Result<String> {
+        let mut image_extractor = self.configure_images(ImageExtractor::new_skip());
+        let (mut document, references) = self.with_body_context(
+            &context.docx,
+            content,
+            &mut image_extractor,
+            None,
+            self.renderer.plain_text(),
+            |body, context| self.extractor.extract(body, context),
+        )?;
+        document.references = references;
+        self.render_document(&self.renderer, &document)
+    }
+
+    /// Extracts the document AST from DOCX bytes without rendering it.
+    ///
+    /// The blocks are extracted for the configured renderer, as plain text
+    /// when [`Renderer::plain_text`] says so.
+    pub fn extract_from_bytes(&self, bytes: &[u8]) -> Result<DocumentAst> {
+        self.extract_bytes(bytes, self.renderer.plain_text())
+    }
+
+    /// Extracts the document AST from DOCX bytes, as plain text blocks with
+    /// `plain_text`.
+    fn extract_bytes(&self, bytes: &[u8], plain_text: bool) -> Result<DocumentAst> {
+        logging::debug!("converting {} bytes", bytes.len());
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+        let document_xml = raw_xml::read_document(&mut archive)?;
+        if text_only::is_text_only(&document_xml) {
+            let (document, _) = self.extract_text_only(&mut archive, document_xml, plain_text)?;
+            return Ok(document);
+        }
+
         let reader = std::io::Cursor::new(bytes);
-        let docx_file =
-            DocxFile::from_reader(reader).map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
-        let docx = docx_file
-            .parse()
+        let docx_file = logging::phase("parse", || DocxFile::from_reader(reader))
+            .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        let docx = logging::phase("parse", || docx_file.parse())
             .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
 
         // Initialize image extractor based on options
-        let mut image_extractor = match &self.options.image_handling {
+        let mut image_extractor = self.configure_images(match &self.options.image_handling {
             ImageHandling::SaveToDir(dir) => {
                 ImageExtractor::new_with_dir_from_bytes(bytes, dir.clone())?
             }
             ImageHandling::Inline => ImageExtractor::new_inline_from_bytes(bytes)?,
+            ImageHandling::InlineUpTo { max_bytes, dir } => {
+                ImageExtractor::new_inline_from_bytes(bytes)?
+                    .with_spill_dir(*max_bytes, dir.clone())?
+            }
             ImageHandling::Skip => ImageExtractor::new_skip(),
+        });
+
+        let raw_parts = raw_xml::read_raw_parts_from(&mut archive, document_xml)?;
+        self.extract_document(&docx, &mut image_extractor, Some(raw_parts), plain_text)
+    }
+
+    /// Extracts a document that [`text_only::is_text_only`] accepted, parsing
+    /// only the parts its paragraphs can refer to and skipping images and the
+    /// raw XML scan, which would find nothing.
+    fn extract_text_only<S: std::io::Read + std::io::Seek>(
+        &self,
+        archive: &mut zip::ZipArchive<S>,
+        document_xml: String,
+        plain_text: bool,
+    ) -> Result<(DocumentAst, ConversionManifest)> {
+        logging::debug!("text-only document, skipping images and the raw XML scan");
+        let parts = text_only::TextOnlyParts::read(archive, document_xml)?;
+        let docx = parts.parse()?;
+        let mut image_extractor = self.configure_images(ImageExtractor::new_skip());
+        let document = self.extract_document(&docx, &mut image_extractor, None, plain_text)?;
+        let manifest = ConversionManifest {
+            images: Vec::new(),
+            warnings: image_extractor.take_warnings(),
         };
+        Ok((document, manifest))
+    }
+
+    /// Creates the image extractor for `image_handling`, reading images from
+    /// the DOCX file at `path`.
+    fn path_image_extractor(&self, path: &Path) -> Result<ImageExtractor> {
+        Ok(self.configure_images(match &self.options.image_handling {
+            ImageHandling::SaveToDir(dir) => ImageExtractor::new_with_dir(path, dir.clone())?,
+            ImageHandling::Inline => ImageExtractor::new_inline(path)?,
+            ImageHandling::InlineUpTo { max_bytes, dir } => {
+                ImageExtractor::new_inline(path)?.with_spill_dir(*max_bytes, dir.clone())?
+            }
+            ImageHandling::Skip => ImageExtractor::new_skip(),
+        }))
+    }
+
+    /// Applies `markdown_output_dir`, so saved images are linked relative to the
+    /// output, `no_html` and `inline_svg`.
+    fn configure_images(&self, mut extractor: ImageExtractor) -> ImageExtractor {
+        if let Some(dir) = &self.options.markdown_output_dir {
+            extractor = extractor.with_markdown_dir(dir.clone());
+        }
+        if self.options.no_html {
+            extractor = extractor.without_html();
+        }
+        if self.options.inline_svg {
+            extractor = extractor.with_svg_markup();
+        }
+        extractor
+    }
+
+    fn convert_with_renderer<T: Renderer>(&self, path: &Path, renderer: &T) -> Result<String> {
+        let document = self.extract_path(path, renderer.plain_text())?;
+        self.render_document(renderer, &document)
+    }
+
+    /// Renders `document` with `renderer` and finishes the output.
+    fn render_document<T: Renderer>(&self, renderer: &T, document: &DocumentAst) -> Result<String> {
+        logging::phase("render", || {
+            renderer.render_with_options(document, &self.options)
+        })
+        .map(|output| self.finish_output(output))
+    }
+
+    /// Drops byte order marks from the text, applies `line_ending` and, with
+    /// `emit_bom`, prepends a BOM.
+    ///
+    /// A U+FEFF inside the document is a stray BOM (or the deprecated
+    /// zero-width no-break space) and would confuse tools reading the output.
+    /// Carriage returns in the document text are normalized too, so the
+    /// output never mixes line endings.
+    fn finish_output(&self, mut output: String) -> String {
+        if output.contains('\u{FEFF}') {
+            output = output.replace('\u{FEFF}', "");
+        }
+        if output.contains('\r') {
+            output = output.replace("\r\n", "\n").replace('\r', "\n");
+        }
+        if self.options.line_ending == LineEnding::CrLf {
+            output = output.replace('\n', "\r\n");
+        }
+        if self.options.emit_bom && !output.is_empty() {
+            output.insert(0, '\u{FEFF}');
+        }
+        output
+    }
+
+    fn extract_path(&self, path: &Path, plain_text: bool) -> Result<DocumentAst> {
+        self.extract_path_with_manifest(path, plain_text)
+            .map(|(document, _)| document)
+    }
 
-        self.convert_inner(&docx, &mut image_extractor)
+    fn extract_path_with_manifest(
+        &self,
+        path: &Path,
+        plain_text: bool,
+    ) -> Result<(DocumentAst, ConversionManifest)> {
+        logging::debug!("converting {}", path.display());
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+        let document_xml = raw_xml::read_document(&mut archive)?;
+        if text_only::is_text_only(&document_xml) {
+            return self.extract_text_only(&mut archive, document_xml, plain_text);
+        }
+
+        // Parse DOCX file
+        let docx_file = logging::phase("parse", || DocxFile::from_file(path))
+            .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+        let docx = logging::phase("parse", || docx_file.parse())
+            .map_err(|e| Error::DocxParse(format!("{:?}", e)))?;
+
+        // Initialize image extractor based on options
+        let mut image_extractor = self.path_image_extractor(path)?;
+
+        let raw_parts = raw_xml::read_raw_parts_from(&mut archive, document_xml)?;
+        let document =
+            self.extract_document(&docx, &mut image_extractor, Some(raw_parts), plain_text)?;
+        let manifest = ConversionManifest {
+            images: image_extractor.take_images(),
+            warnings: image_extractor.take_warnings(),
+        };
+        logging::debug!(
+            "converted {}: {} blocks, {} images, {} warnings",
+            path.display(),
+            document.blocks.len(),
+            manifest.images.len(),
+            manifest.warnings.len()
+        );
+        Ok((document, manifest))
     }
 
+    #[cfg(test)]
     fn convert_inner<'a>(
         &'a self,
         docx: &'a rs_docx::Docx,
         image_extractor: &'a mut ImageExtractor,
     ) -> Result<String> {
+        let plain_text = self.renderer.plain_text();
+        let document = self.extract_document(docx, image_extractor, None, plain_text)?;
+        self.renderer.render_with_options(&document, &self.options)
+    }
+
+    fn extract_document(
+        &self,
+        docx: &rs_docx::Docx,
+        image_extractor: &mut ImageExtractor,
+        raw_parts: Option<raw_xml::RawParts>,
+        plain_text: bool,
+    ) -> Result<DocumentAst> {
+        let (mut document, references) = self.with_context(
+            docx,
+            image_extractor,
+            raw_parts,
+            plain_text,
+            |body, context| self.extractor.extract(body, context),
+        )?;
+        document.references = references;
+        Ok(document)
+    }
+
+    /// Builds the conversion context for `docx` and runs `extract` on the body,
+    /// converting to plain text with `plain_text`.
+    ///
+    /// Returns the result with the notes the context collected, after strict
+    /// reference validation.
+    fn with_context<T, F>(
+        &self,
+        docx: &rs_docx::Docx,
+        image_extractor: &mut ImageExtractor,
+        raw_parts: Option<raw_xml::RawParts>,
+        plain_text: bool,
+        extract: F,
+    ) -> Result<(T, ReferenceDefinitions)>
+    where
+        F: for<'c> FnOnce(&[BodyContent<'c>], &mut ConversionContext<'c>) -> Result<T>,
+    {
+        self.with_body_context(
+            docx,
+            &docx.document.body.content,
+            image_extractor,
+            raw_parts,
+            plain_text,
+            extract,
+        )
+    }
+
+    /// Like [`Self::with_context`], converting `body` in place of the
+    /// document's own body.
+    fn with_body_context<T, F>(
+        &self,
+        docx: &rs_docx::Docx,
+        body: &[BodyContent<'_>],
+        image_extractor: &mut ImageExtractor,
+        mut raw_parts: Option<raw_xml::RawParts>,
+        plain_text: bool,
+        extract: F,
+    ) -> Result<(T, ReferenceDefinitions)>
+    where
+        F: for<'c> FnOnce(&[BodyContent<'c>], &mut ConversionContext<'c>) -> Result<T>,
+    {
+        self.options.note_markers.validate()?;
+        self.options.leading_tab.validate()?;
+        if self.options.preserve_note_ids && self.options.unify_notes {
+            return Err(Error::InvalidOption(
+                "preserve_note_ids cannot be combined with unify_notes".to_string(),
+            ));
+        }
+        let separator = &self.options.paragraph_separator;
+        if separator.is_empty() || separator.contains(|c| c != '\n' && c != '\r') {
+            return Err(Error::InvalidOption(format!(
+                "paragraph separator {:?} must consist of line breaks",
+                separator
+            )));
+        }
+        let notes = docx
+            .footnotes
+            .iter()
+            .flat_map(|notes| &notes.content)
+            .map(|note| note.content.as_slice())
+            .chain(
+                docx.endnotes
+                    .iter()
+                    .flat_map(|notes| &notes.content)
+                    .map(|note| note.content.as_slice()),
+            );
+        nesting::check(
+            std::iter::once(body).chain(notes),
+            self.options.max_nesting_depth,
+        )?;
+
         // Build relationship map for hyperlinks
         let rels = self.build_relationship_map(docx);
 
         // Initialize numbering resolver
-        let mut numbering_resolver = NumberingResolver::new(docx);
+        let mut numbering_resolver =
+            NumberingResolver::new(docx).with_localization(self.options.localization.clone());
 
         // Initialize style resolver
         let style_resolver = StyleResolver::new(&docx.styles);
@@ -165,11 +747,28 @@ where
             docx.endnotes.as_ref(),
             &style_resolver,
         );
-
-        let mut document = self
-            .extractor
-            .extract(&docx.document.body.content, &mut context)?;
-        document.references = context.reference_definitions();
+        context.set_plain_text(plain_text);
+        if let Some(raw_parts) = &mut raw_parts {
+            context.set_chart_parts(std::mem::take(&mut raw_parts.charts));
+            context.set_resolved_comments(std::mem::take(&mut raw_parts.resolved_comments));
+        }
+        if let Some(raw_parts) = &raw_parts {
+            context.set_raw_index(raw_xml::RawDocumentIndex::build(&raw_parts.document, body));
+            context.set_raw_document(&raw_parts.document);
+            context.set_glossary(&raw_parts.glossary);
+        }
+        context.index_repeated_headings(body);
+        context.index_heading_slugs(body);
+        context.index_figures(body);
+        context.index_index_entries(body);
+        context.index_bibliography(body);
+
+        let extracted = logging::phase("extract", || {
+            let extracted = extract(body, &mut context)?;
+            context.convert_notes()?;
+            Ok::<_, Error>(extracted)
+        })?;
+        let references = context.reference_definitions();
 
         if self.options.strict_reference_validation {
             let missing = context.take_missing_references();
@@ -177,8 +776,14 @@ where
                 return Err(Error::MissingReference(missing.join(", ")));
             }
         }
+        if self.options.strict_media {
+            let corrupt = context.corrupt_media();
+            if !corrupt.is_empty() {
+                return Err(Error::CorruptMedia(corrupt.join(", ")));
+            }
+        }
 
-        self.renderer.render(&document)
+        Ok((extracted, references))
     }
 
     #[cfg(test)]
@@ -232,9 +837,14 @@ where
                     }
                 }
             }
-            BodyContent::BookmarkStart(bookmark) => {
-                if let Some(name) = &bookmark.name {
+            BodyContent::BookmarkStart(bookmark) if !context.no_html() => {
+                if let Some(name) = bookmark
+                    .name
+                    .as_ref()
+                    .filter(|name| context.bookmark_slug(name).is_none())
+                {
                     output.push_str(&format!("<a id=\"{}\"></a>", escape_html_attr(name)));
+                    output.push_str("\n\n");
                 }
             }
             _ => {}
@@ -255,13 +865,61 @@ where
     }
 }
 
+impl<E, R> DocumentExtractor for DocxToMarkdown<E, R>
+where
+    E: AstExtractor,
+    R: Renderer,
+{
+    fn extract(&self, bytes: &[u8]) -> Result<DocumentAst> {
+        self.extract_from_bytes(bytes)
+    }
+
+    fn extract_plain_text(&self, bytes: &[u8]) -> Result<DocumentAst> {
+        self.extract_bytes(bytes, true)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<E, R> DocxToMarkdown<E, R>
+where
+    E: AstExtractor + Clone + Send + 'static,
+    R: Renderer + Clone + Send + 'static,
+{
+    /// Converts a DOCX file to Markdown without blocking the async runtime.
+    ///
+    /// File IO and conversion run on tokio's blocking thread pool via
+    /// [`tokio::task::spawn_blocking`], delegating to [`DocxToMarkdown::convert`].
+    pub async fn convert_async<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let converter = self.clone();
+        let path = path.as_ref().to_path_buf();
+        Self::run_blocking(move || converter.convert(path)).await
+    }
+
+    /// Converts DOCX bytes to Markdown without blocking the async runtime.
+    ///
+    /// Delegates to [`DocxToMarkdown::convert_from_bytes`] on tokio's blocking thread pool.
+    pub async fn convert_from_bytes_async(&self, bytes: Vec<u8>) -> Result<String> {
+        let converter = self.clone();
+        Self::run_blocking(move || converter.convert_from_bytes(&bytes)).await
+    }
+
+    async fn run_blocking<F>(task: F) -> Result<String>
+    where
+        F: FnOnce() -> Result<String> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(task)
+            .await
+            .map_err(|e| Error::Conversion(format!("async conversion task failed: {e}")))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::ast::{BlockNode, DocumentAst};
+    use crate::core::ast::BlockNode;
     use rs_docx::document::{
-        BodyContent, BookmarkStart, EndNote, EndNotes, FootNote, FootNotes, Paragraph, Run,
-        RunContent, SDTContent, SDT, TableCell, Text,
+        BodyContent, BookmarkStart, EndNote, EndNotes, FootNote, FootNotes, NoteSeparator,
+        Paragraph, Run, RunContent, SDTContent, TableCell, Text, SDT,
     };
     use std::borrow::Cow;
     use std::collections::HashMap;
@@ -418,8 +1076,63 @@ mod tests {
         .unwrap();
 
         // Verify
-        assert!(result.contains("<a id=\"TestAnchor\"></a>"));
-        assert!(result.contains("Content"));
+        assert_eq!(result, "<a id=\"TestAnchor\"></a>\n\nContent\n\n");
+    }
+
+    #[test]
+    fn test_sdt_blocks_are_separated_like_top_level_blocks() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:r><w:t>Before</w:t></w:r></w:p>
+            <w:sdt><w:sdtContent>
+                <w:p><w:r><w:t>First</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Second</w:t></w:r></w:p>
+            </w:sdtContent></w:sdt>
+            <w:p><w:r><w:t>After</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions::default();
+        let style_resolver = StyleResolver::new(&docx.styles);
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let expected = "Before\n\nFirst\n\nSecond\n\nAfter\n\n";
+        let mut concatenated = String::new();
+        for content in body {
+            concatenated.push_str(
+                &DocxToMarkdown::<DocxExtractor, MarkdownRenderer>::convert_content(
+                    content,
+                    &mut context,
+                )
+                .unwrap(),
+            );
+        }
+        assert_eq!(concatenated, expected);
+
+        let document = DocxExtractor
+            .extract(body, &mut context)
+            .expect("extraction failed");
+        assert_eq!(
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed"),
+            expected
+        );
     }
 
     #[test]
@@ -457,6 +1170,276 @@ mod tests {
         assert_eq!(context.comment_count(), 1);
     }
 
+    #[test]
+    fn test_note_markers_are_used_for_references_and_definitions() {
+        let styles = rs_docx::styles::Styles::new();
+        let docx = rs_docx::Docx::default();
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            note_markers: crate::NoteMarkers {
+                footnote: "[^fn-{n}]".to_string(),
+                endnote: "[^end-{n}]".to_string(),
+                comment: "[^note-{id}]".to_string(),
+            },
+            ..Default::default()
+        };
+        let rels = HashMap::new();
+        let style_resolver = StyleResolver::new(&styles);
+
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        assert_eq!(context.register_footnote_reference(42), "[^fn-1]");
+        assert_eq!(context.register_endnote_reference(7), "[^end-1]");
+        assert_eq!(context.register_comment_reference("3"), "[^note-3]");
+        assert_eq!(
+            MarkdownRenderer::render_notes(&context.reference_definitions()),
+            "[^fn-1]: \n[^end-1]: \n[^note-3]: \n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_note_markers_are_rejected() {
+        let invalid = |footnote: &str, endnote: &str| {
+            let options = ConvertOptions {
+                note_markers: crate::NoteMarkers {
+                    footnote: footnote.to_string(),
+                    endnote: endnote.to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let converter = DocxToMarkdown::new(options);
+            let mut image_extractor = ImageExtractor::new_skip();
+            matches!(
+                converter.convert_inner(&rs_docx::Docx::default(), &mut image_extractor),
+                Err(Error::InvalidOption(_))
+            )
+        };
+
+        assert!(!invalid("[^fn-{n}]", "[^en{n}]"));
+        assert!(invalid("[^fn]", "[^en{n}]"));
+        assert!(invalid("(fn-{n})", "[^en{n}]"));
+        assert!(invalid("[^fn {n}]", "[^en{n}]"));
+        assert!(invalid("[^{n}]", "[^{n}]"));
+    }
+
+    #[test]
+    fn test_inline_footnote_style_replaces_marker_with_note_text() {
+        let styles = rs_docx::styles::Styles::new();
+        let docx = rs_docx::Docx::default();
+        let footnotes = FootNotes {
+            content: vec![FootNote {
+                id: Some(1),
+                content: vec![
+                    BodyContent::Paragraph(Paragraph::default().push_text("See *this*")),
+                    BodyContent::Paragraph(Paragraph::default().push_text("[draft]")),
+                ],
+                ..Default::default()
+            }],
+        };
+
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            footnote_style: crate::FootnoteStyle::Inline,
+            ..Default::default()
+        };
+        let rels = HashMap::new();
+        let style_resolver = StyleResolver::new(&styles);
+
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            Some(&footnotes),
+            None,
+            &style_resolver,
+        );
+
+        let expected = " (See \\*this\\* \\[draft\\])";
+        assert_eq!(context.register_footnote_reference(1), expected);
+        assert_eq!(context.register_footnote_reference(1), expected);
+        assert_eq!(context.register_footnote_reference(9), "");
+        assert_eq!(context.footnote_count(), 0);
+        assert_eq!(context.take_missing_references(), ["footnote:9"]);
+    }
+
+    #[test]
+    fn test_multi_paragraph_footnotes_indent_their_paragraphs() {
+        let styles = rs_docx::styles::Styles::new();
+        let docx = rs_docx::Docx::default();
+        let footnotes = FootNotes {
+            content: vec![FootNote {
+                id: Some(1),
+                content: vec![
+                    BodyContent::Paragraph(Paragraph::default().push_text("Other")),
+                    BodyContent::Paragraph(Paragraph::default()),
+                    BodyContent::Paragraph(Paragraph::default().push_text("Second para")),
+                ],
+                ..Default::default()
+            }],
+        };
+
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions::default();
+        let rels = HashMap::new();
+        let style_resolver = StyleResolver::new(&styles);
+
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            Some(&footnotes),
+            None,
+            &style_resolver,
+        );
+
+        assert_eq!(context.register_footnote_reference(1), "[^1]");
+        context.convert_notes().expect("note conversion failed");
+        assert_eq!(
+            MarkdownRenderer::render_notes(&context.reference_definitions()),
+            "[^1]: Other\n\n    Second para\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_notes_share_the_footnote_sequence() {
+        let styles = rs_docx::styles::Styles::new();
+        let docx = rs_docx::Docx::default();
+        let footnotes = FootNotes {
+            content: vec![FootNote {
+                id: Some(1),
+                content: vec![BodyContent::Paragraph(
+                    Paragraph::default().push_text("Footnote"),
+                )],
+                ..Default::default()
+            }],
+        };
+        let endnotes = EndNotes {
+            content: vec![EndNote {
+                id: Some(1),
+                content: vec![BodyContent::Paragraph(
+                    Paragraph::default().push_text("Endnote"),
+                )],
+                ..Default::default()
+            }],
+        };
+
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            unify_notes: true,
+            ..Default::default()
+        };
+        let rels = HashMap::new();
+        let style_resolver = StyleResolver::new(&styles);
+
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            Some(&footnotes),
+            Some(&endnotes),
+            &style_resolver,
+        );
+
+        assert_eq!(context.register_endnote_reference(1), "[^1]");
+        assert_eq!(context.register_footnote_reference(1), "[^2]");
+        assert_eq!(context.register_endnote_reference(1), "[^1]");
+        assert_eq!(context.endnote_count(), 0);
+        assert_eq!(
+            MarkdownRenderer::render_notes(&context.reference_definitions()),
+            "[^1]: Endnote\n[^2]: Footnote\n"
+        );
+    }
+
+    #[test]
+    fn test_preserved_note_ids_number_markers_and_definitions() {
+        let styles = rs_docx::styles::Styles::new();
+        let docx = rs_docx::Docx::default();
+        let note = |id, ty, text| FootNote {
+            ty,
+            id: Some(id),
+            content: vec![BodyContent::Paragraph(Paragraph::default().push_text(text))],
+        };
+        let footnotes = FootNotes {
+            content: vec![
+                note(-1, Some(NoteSeparator::Separator), "----"),
+                note(0, Some(NoteSeparator::ContinuationSeparator), "--"),
+                note(2, None, "Two"),
+                note(5, None, "Five"),
+            ],
+        };
+        let endnotes = EndNotes {
+            content: vec![EndNote {
+                id: Some(3),
+                content: vec![BodyContent::Paragraph(
+                    Paragraph::default().push_text("Three"),
+                )],
+                ..Default::default()
+            }],
+        };
+
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            preserve_note_ids: true,
+            ..Default::default()
+        };
+        let rels = HashMap::new();
+        let style_resolver = StyleResolver::new(&styles);
+
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            Some(&footnotes),
+            Some(&endnotes),
+            &style_resolver,
+        );
+
+        assert_eq!(context.register_footnote_reference(5), "[^5]");
+        assert_eq!(context.register_footnote_reference(2), "[^2]");
+        assert_eq!(context.register_footnote_reference(5), "[^5]");
+        assert_eq!(context.register_endnote_reference(3), "[^en3]");
+        assert_eq!(context.register_footnote_reference(0), "");
+        assert!(context.take_missing_references().is_empty());
+        assert_eq!(
+            MarkdownRenderer::render_notes(&context.reference_definitions()),
+            "[^5]: Five\n[^2]: Two\n[^en3]: Three\n"
+        );
+    }
+
+    #[test]
+    fn test_preserved_note_ids_cannot_be_unified() {
+        let converter = DocxToMarkdown::new(ConvertOptions {
+            preserve_note_ids: true,
+            unify_notes: true,
+            ..Default::default()
+        });
+        let result = converter.convert_fragment(&[], &FragmentContext::new());
+        assert!(matches!(result, Err(Error::InvalidOption(_))));
+    }
+
     #[test]
     fn test_with_components_uses_custom_extractor_and_renderer() {
         let docx = rs_docx::Docx {
@@ -521,6 +1504,133 @@ mod tests {
         assert_eq!(rendered, "blocks=1;footnotes=1;first=");
     }
 
+    #[test]
+    fn test_byte_order_marks_are_dropped_unless_emitted() {
+        let path = temp_docx_path("bom");
+        let mut docx = rs_docx::Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("\u{FEFF}Hello\u{FEFF} world"));
+        docx.write_file(&path)
+            .expect("failed to write generated docx");
+        let bytes = std::fs::read(&path).expect("failed to read generated docx");
+        let _ = std::fs::remove_file(&path);
+
+        let converter = DocxToMarkdown::new(ConvertOptions::default());
+        let markdown = converter.convert_from_bytes(&bytes).unwrap();
+        assert_eq!(markdown.trim_end(), "Hello world");
+
+        let converter = DocxToMarkdown::new(ConvertOptions {
+            emit_bom: true,
+            ..Default::default()
+        });
+        let markdown = converter.convert_from_bytes(&bytes).unwrap();
+        assert_eq!(markdown.trim_end(), "\u{FEFF}Hello world");
+    }
+
+    #[test]
+    fn test_line_ending_applies_to_every_line() {
+        let path = temp_docx_path("crlf");
+        let mut docx = rs_docx::Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("one\r\ntwo"))
+            .push(Paragraph::default().push_text("three\rfour"));
+        docx.write_file(&path)
+            .expect("failed to write generated docx");
+        let bytes = std::fs::read(&path).expect("failed to read generated docx");
+        let _ = std::fs::remove_file(&path);
+
+        let convert = |line_ending| {
+            DocxToMarkdown::new(ConvertOptions {
+                line_ending,
+                ..Default::default()
+            })
+            .convert_from_bytes(&bytes)
+            .unwrap()
+        };
+        let lf = convert(LineEnding::Lf);
+        assert!(lf.starts_with("one\ntwo\n\nthree\nfour\n"));
+        assert!(!lf.contains('\r'));
+        assert_eq!(convert(LineEnding::CrLf), lf.replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn test_paragraph_separator_follows_line_ending() {
+        let path = temp_docx_path("separator");
+        let mut docx = rs_docx::Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("one"))
+            .push(Paragraph::default().push_text("two"));
+        docx.write_file(&path)
+            .expect("failed to write generated docx");
+        let bytes = std::fs::read(&path).expect("failed to read generated docx");
+        let _ = std::fs::remove_file(&path);
+
+        let convert = |paragraph_separator: &str, line_ending| {
+            DocxToMarkdown::new(ConvertOptions {
+                paragraph_separator: paragraph_separator.to_string(),
+                line_ending,
+                ..Default::default()
+            })
+            .convert_from_bytes(&bytes)
+        };
+        assert_eq!(convert("\n", LineEnding::Lf).unwrap(), "one\ntwo\n");
+        assert_eq!(
+            convert("\r\n\r\n\r\n", LineEnding::Lf).unwrap(),
+            "one\n\n\ntwo\n\n\n"
+        );
+        assert_eq!(convert("\n", LineEnding::CrLf).unwrap(), "one\r\ntwo\r\n");
+        for invalid in ["", " \n", "\n<br>\n"] {
+            assert!(matches!(
+                convert(invalid, LineEnding::Lf),
+                Err(Error::InvalidOption(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_plain_text_keeps_markdown_syntax_in_the_text() {
+        let path = temp_docx_path("plain");
+        let mut docx = rs_docx::Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("a*b* <tag> [l](u)"))
+            .push(Paragraph::default().push_text("# not heading"))
+            .push(Paragraph::default().push_text("---"));
+        docx.write_file(&path)
+            .expect("failed to write generated docx");
+        let bytes = std::fs::read(&path).expect("failed to read generated docx");
+        let _ = std::fs::remove_file(&path);
+
+        let converter = DocxToMarkdown::new(ConvertOptions::default());
+        let document = converter.extract_plain_text(&bytes).unwrap();
+        assert_eq!(
+            PlainTextRenderer.render(&document).unwrap(),
+            "a*b* <tag> [l](u)\n\n# not heading\n\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_is_not_escaped() {
+        let path = temp_docx_path("plain_escapes");
+        let mut docx = rs_docx::Docx::default();
+        docx.document
+            .push(Paragraph::default().push_text("C:\\[x]\\dir"))
+            .push(Paragraph::default().push_text("b ~ c x^2 a == b"));
+        docx.write_file(&path)
+            .expect("failed to write generated docx");
+        let bytes = std::fs::read(&path).expect("failed to read generated docx");
+        let _ = std::fs::remove_file(&path);
+
+        let converter = DocxToMarkdown::new(ConvertOptions {
+            escape_extended_syntax: crate::ExtendedSyntaxEscaping::All,
+            ..Default::default()
+        });
+        let document = converter.extract_plain_text(&bytes).unwrap();
+        assert_eq!(
+            PlainTextRenderer.render(&document).unwrap(),
+            "C:\\[x]\\dir\n\nb ~ c x^2 a == b\n\n"
+        );
+    }
+
     #[test]
     fn test_with_components_strict_validation_fails_for_missing_comment() {
         let docx = rs_docx::Docx::default();
@@ -574,6 +1684,442 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_column_sections_flow_as_one_column() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:r><w:t>Intro</w:t></w:r><w:pPr><w:sectPr><w:type w:val="continuous"/></w:sectPr></w:pPr></w:p>
+            <w:p><w:r><w:t>Left</w:t><w:br w:type="column"/><w:t>Right</w:t></w:r></w:p>
+            <w:p><w:pPr><w:sectPr><w:cols w:space="720" w:num="2"/></w:sectPr></w:pPr><w:r><w:t>More</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Outro</w:t></w:r></w:p>
+            <w:sectPr><w:cols w:space="720"/></w:sectPr>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |annotate_columns| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                annotate_columns,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(raw_xml::RawDocumentIndex::build(xml, body));
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        assert_eq!(
+            convert(false).trim_end(),
+            "Intro\n\nLeft\n\nRight\n\nMore\n\nOutro"
+        );
+        assert_eq!(
+            convert(true).trim_end(),
+            "Intro\n\n<!-- columns: 2 -->\n\nLeft\n\nRight\n\nMore\n\n<!-- /columns -->\n\nOutro"
+        );
+    }
+
+    #[test]
+    fn test_single_cell_tables_unwrap_their_content() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid><w:gridCol w:w="9000"/></w:tblGrid><w:tr><w:tc>
+                <w:p><w:r><w:rPr><w:b/></w:rPr><w:t>Note</w:t></w:r><w:r><w:t xml:space="preserve"> boxed text</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Second paragraph</w:t></w:r></w:p>
+            </w:tc></w:tr></w:tbl>
+            <w:tbl><w:tblGrid><w:gridCol w:w="4500"/><w:gridCol w:w="4500"/></w:tblGrid><w:tr><w:tc><w:p><w:r><w:t>a</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>b</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |single_cell_tables| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                single_cell_tables,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        let table = "<table>\n  <tr>\n    <td>a</td>\n    <td>b</td>\n  </tr>\n</table>";
+        assert_eq!(
+            convert(crate::SingleCellTables::Unwrap).trim_end(),
+            format!(
+                "<strong>Note</strong> boxed text\n\nSecond paragraph\n\n{}",
+                table
+            )
+        );
+        assert_eq!(
+            convert(crate::SingleCellTables::Quote).trim_end(),
+            format!(
+                "> <strong>Note</strong> boxed text\n>\n> Second paragraph\n\n{}",
+                table
+            )
+        );
+        assert!(convert(crate::SingleCellTables::Table)
+            .starts_with("<table>\n  <tr>\n    <td><strong>Note</strong> boxed text<br/>"));
+    }
+
+    #[test]
+    fn test_toc_entries_become_links_without_page_numbers() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="TOC1"/></w:pPr><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> TOC \o "1-3" \h \z \u </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:hyperlink w:anchor="_Toc1"><w:r><w:t>1.</w:t></w:r><w:r><w:tab/></w:r><w:r><w:t>Introduction</w:t></w:r><w:r><w:tab/></w:r><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGEREF _Toc1 \h </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:hyperlink></w:p>
+            <w:p><w:pPr><w:pStyle w:val="TOC2"/></w:pPr><w:hyperlink w:anchor="_Toc2"><w:r><w:t>Scope [draft]</w:t></w:r><w:r><w:tab/></w:r><w:r><w:t>12</w:t></w:r></w:hyperlink></w:p>
+            <w:p><w:pPr><w:pStyle w:val="TOC1"/></w:pPr><w:r><w:t>Appendix</w:t></w:r><w:r><w:tab/></w:r><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGEREF _Toc3 </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>iv</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>
+            <w:p><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:bookmarkStart w:id="1" w:name="_Toc1"/><w:r><w:t>Introduction</w:t></w:r><w:bookmarkEnd w:id="1"/></w:p>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |toc| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                toc,
+                slug_fn: Some(crate::SlugFn::default()),
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.index_heading_slugs(body);
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        assert_eq!(
+            convert(crate::TocHandling::Links).trim_end(),
+            "- [1. Introduction](#introduction)\n\n\
+             \x20 - [Scope \\[draft\\]](#_Toc2)\n\n\
+             - [Appendix](#_Toc3)\n\n\
+             <a id=\"introduction\"></a>\n# Introduction"
+        );
+        assert!(
+            convert(crate::TocHandling::Keep).starts_with("[1.\tIntroduction\t1](#introduction)")
+        );
+    }
+
+    #[test]
+    fn test_slug_fn_names_heading_anchors_and_toc_links() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:hyperlink w:anchor="_Toc1"><w:r><w:t>Getting Started</w:t></w:r></w:hyperlink></w:p>
+            <w:p><w:hyperlink w:anchor="_Toc2"><w:r><w:t>Again</w:t></w:r></w:hyperlink></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:bookmarkStart w:id="1" w:name="_Toc1"/><w:r><w:t>Getting Started!</w:t></w:r><w:bookmarkEnd w:id="1"/></w:p>
+            <w:bookmarkStart w:id="2" w:name="_Toc2"/>
+            <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Getting Started</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |slug_fn| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                slug_fn,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.index_heading_slugs(body);
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        assert_eq!(
+            convert(None).trim_end(),
+            "[Getting Started](#_Toc1)\n\n[Again](#_Toc2)\n\n\
+             <a id=\"_Toc1\"></a>\n# Getting Started!\n\n\
+             <a id=\"_Toc2\"></a>\n\n## Getting Started"
+        );
+        assert_eq!(
+            convert(Some(crate::SlugFn::default())).trim_end(),
+            "[Getting Started](#getting-started)\n\n[Again](#getting-started-1)\n\n\
+             <a id=\"getting-started\"></a>\n# Getting Started!\n\n\
+             <a id=\"getting-started-1\"></a>\n## Getting Started"
+        );
+        let custom = crate::SlugFn::new(|text, level| format!("h{}-{}", level, text.len()));
+        assert_eq!(
+            convert(Some(custom)).trim_end(),
+            "[Getting Started](#h1-16)\n\n[Again](#h2-15)\n\n\
+             <a id=\"h1-16\"></a>\n# Getting Started!\n\n\
+             <a id=\"h2-15\"></a>\n## Getting Started"
+        );
+    }
+
+    #[test]
+    fn test_heading_ids_as_attributes() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:hyperlink w:anchor="_Toc1"><w:r><w:t>Overview</w:t></w:r></w:hyperlink></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:bookmarkStart w:id="1" w:name="_Toc1"/><w:r><w:t>Project Overview</w:t></w:r><w:bookmarkEnd w:id="1"/></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Project Overview</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |slug_fn, no_html| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                slug_fn,
+                no_html,
+                heading_id_style: crate::HeadingIdStyle::Attribute,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.index_heading_slugs(body);
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        let expected = "[Overview](#project-overview)\n\n\
+                        # Project Overview {#project-overview}\n\n\
+                        ## Project Overview {#project-overview-1}";
+        assert_eq!(convert(None, false).trim_end(), expected);
+        assert_eq!(convert(None, true).trim_end(), expected);
+        let custom = crate::SlugFn::new(|_, level| format!("h{}", level));
+        assert_eq!(
+            convert(Some(custom), false).trim_end(),
+            "[Overview](#h1)\n\n# Project Overview {#h1}\n\n## Project Overview {#h2}"
+        );
+    }
+
+    #[test]
+    fn test_callout_styles_render_github_alerts() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Note"/></w:pPr><w:r><w:t>First line.</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Note"/></w:pPr><w:r><w:t>Second line.</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Warning"/></w:pPr><w:r><w:t>Careful.</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="IntenseQuote"/></w:pPr><w:r><w:t>Quoted.</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Body.</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = rs_docx::document::Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+
+        let convert = |callout_styles, localization| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor = ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                callout_styles,
+                localization,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed")
+        };
+
+        assert_eq!(
+            convert(None, None).trim_end(),
+            "First line.\n\nSecond line.\n\nCareful.\n\nQuoted.\n\nBody."
+        );
+        assert_eq!(
+            convert(Some(crate::CalloutStyles::default()), None).trim_end(),
+            "> [!NOTE]\n> First line.\n>\n> Second line.\n\n\
+             > [!WARNING]\n> Careful.\n\n\
+             > Quoted.\n\n\
+             Body."
+        );
+
+        // The localization names the styles `callout_styles` does not list.
+        #[derive(Debug)]
+        struct Careful;
+        impl crate::LocalizationStrategy for Careful {
+            fn callout_style_names(&self) -> &[(&str, crate::Callout)] {
+                &[("Note", crate::Callout::Alert(crate::AlertKind::Caution))]
+            }
+        }
+        let styles = crate::CalloutStyles { styles: Vec::new() };
+        assert_eq!(
+            convert(Some(styles), Some(std::sync::Arc::new(Careful))).trim_end(),
+            "> [!CAUTION]\n> First line.\n>\n> Second line.\n\n\
+             Careful.\n\nQuoted.\n\nBody."
+        );
+    }
+
+    #[test]
+    fn test_image_only_paragraphs_become_figures() {
+        use crate::FigureSyntax;
+        use hard_xml::XmlRead;
+
+        let drawing = r#"<w:r><w:drawing><wp:inline><wp:extent cx="1905000" cy="1905000"/><wp:docPr id="1" name="Picture"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic><pic:nvPicPr><pic:cNvPr id="2" name="Picture"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:embed="rId20"/></pic:blipFill><pic:spPr/></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#;
+        let xml = format!(
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>
+            <w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t xml:space="preserve"> </w:t></w:r>{drawing}</w:p>
+            <w:p><w:pPr><w:jc w:val="center"/></w:pPr>{drawing}</w:p>
+            <w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Figure 1: The moon</w:t></w:r></w:p>
+        </w:body></w:document>"#
+        );
+        let document = rs_docx::document::Document::from_str(&xml).expect("document should parse");
+        let body = &document.body.content;
+        let docx_bytes = std::fs::read("tests/pandoc/image.docx").expect("sample should exist");
+
+        let convert = |figure_captions, figure_syntax| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::from([("rId20".to_string(), "media/rId20.jpg".to_string())]);
+            let mut numbering_resolver = NumberingResolver::new(&docx);
+            let mut image_extractor =
+                ImageExtractor::new_inline_from_bytes(&docx_bytes).expect("images should load");
+            let options = ConvertOptions {
+                figure_captions,
+                figure_syntax,
+                ..Default::default()
+            };
+            let style_resolver = StyleResolver::new(&docx.styles);
+            let mut context = ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.index_figures(body);
+            let document = DocxExtractor
+                .extract(body, &mut context)
+                .expect("extraction failed");
+            let markdown = MarkdownRenderer
+                .render(&document)
+                .expect("rendering failed");
+            let data = markdown
+                .split("base64,")
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .expect("image data should be inlined")
+                .to_string();
+            markdown.replace(&data, "...")
+        };
+
+        let image = r#"<img src="data:image/jpeg;base64,..." alt="image" />"#;
+        assert_eq!(
+            convert(true, FigureSyntax::Html).trim_end(),
+            format!(
+                "{image}\n\n<figure>\n\n{image}\n\n\
+                 <figcaption>Figure 1: The moon</figcaption>\n</figure>"
+            )
+        );
+        assert_eq!(
+            convert(false, FigureSyntax::Html).trim_end(),
+            format!("{image}\n\n{image}\n\nFigure 1: The moon")
+        );
+        assert_eq!(
+            convert(true, FigureSyntax::Pandoc).trim_end(),
+            format!(
+                "{image}\n\n\
+                 ![Figure 1: The moon](data:image/jpeg;base64,...){{#fig:1}}"
+            )
+        );
+    }
+
     #[test]
     fn test_convert_content_body_run_is_rendered() {
         let mut run = Run::default();
@@ -599,12 +2145,11 @@ mod tests {
             &style_resolver,
         );
 
-        let output =
-            DocxToMarkdown::<DocxExtractor, MarkdownRenderer>::convert_content(
-                &BodyContent::Run(run),
-                &mut context,
-            )
-            .expect("conversion failed");
+        let output = DocxToMarkdown::<DocxExtractor, MarkdownRenderer>::convert_content(
+            &BodyContent::Run(run),
+            &mut context,
+        )
+        .expect("conversion failed");
         assert_eq!(output, "loose run\n\n");
     }
 
@@ -629,12 +2174,11 @@ mod tests {
             &style_resolver,
         );
 
-        let output =
-            DocxToMarkdown::<DocxExtractor, MarkdownRenderer>::convert_content(
-                &BodyContent::TableCell(cell),
-                &mut context,
-            )
-            .expect("conversion failed");
+        let output = DocxToMarkdown::<DocxExtractor, MarkdownRenderer>::convert_content(
+            &BodyContent::TableCell(cell),
+            &mut context,
+        )
+        .expect("conversion failed");
         assert_eq!(output, "cell text\n\n");
     }
 }
@@ -0,0 +1,167 @@
+//! Nesting depth - guards the recursive passes against deeply nested input.
+//!
+//! Tables in cells and content controls in content controls are converted
+//! recursively, so a document nesting them thousands of levels deep would
+//! overflow the stack. The depth is measured up front, without recursion,
+//! and conversion is refused beyond `max_nesting_depth`.
+
+use crate::{Error, Result};
+use rs_docx::document::{
+    BodyContent, Paragraph, ParagraphContent, Table, TableCellContent, TableRowContent,
+};
+
+/// Content waiting to be measured.
+enum Item<'t, 'a> {
+    Body(&'t BodyContent<'a>),
+    Cell(&'t TableCellContent<'a>),
+    Paragraph(&'t Paragraph<'a>),
+    Table(&'t Table<'a>),
+}
+
+/// Returns an error when tables and content controls nest more than `max`
+/// levels deep in any of `bodies`.
+pub(crate) fn check<'t, 'a: 't>(
+    bodies: impl IntoIterator<Item = &'t [BodyContent<'a>]>,
+    max: usize,
+) -> Result<()> {
+    let depth = bodies.into_iter().map(nesting_depth).max().unwrap_or(0);
+    if depth > max {
+        return Err(Error::NestingTooDeep(format!(
+            "tables and content controls nest {} levels deep, more than max_nesting_depth ({})",
+            depth, max
+        )));
+    }
+    Ok(())
+}
+
+/// Returns how many tables and content controls (`w:sdt`) enclose the most
+/// deeply nested content of `body`; 0 for a body without either.
+fn nesting_depth(body: &[BodyContent<'_>]) -> usize {
+    let mut stack: Vec<(Item<'_, '_>, usize)> = body
+        .iter()
+        .map(|content| (Item::Body(content), 0))
+        .collect();
+    let mut max = 0;
+    while let Some((item, depth)) = stack.pop() {
+        max = max.max(depth);
+        match item {
+            Item::Body(BodyContent::Paragraph(para)) => stack.push((Item::Paragraph(para), depth)),
+            Item::Body(BodyContent::Table(table)) => stack.push((Item::Table(table), depth + 1)),
+            Item::Body(BodyContent::Sdt(sdt)) => {
+                if let Some(sdt_content) = &sdt.content {
+                    stack.extend(
+                        sdt_content
+                            .content
+                            .iter()
+                            .map(|content| (Item::Body(content), depth + 1)),
+                    );
+                }
+            }
+            Item::Body(BodyContent::TableCell(cell)) => {
+                stack.extend(
+                    cell.content
+                        .iter()
+                        .map(|content| (Item::Cell(content), depth)),
+                );
+            }
+            Item::Body(_) => {}
+            Item::Cell(TableCellContent::Paragraph(para)) => {
+                stack.push((Item::Paragraph(para), depth))
+            }
+            Item::Cell(TableCellContent::Table(table)) => {
+                stack.push((Item::Table(table), depth + 1))
+            }
+            Item::Paragraph(para) => {
+                for content in &para.content {
+                    if let ParagraphContent::SDT(sdt) = content {
+                        if let Some(sdt_content) = &sdt.content {
+                            stack.extend(
+                                sdt_content
+                                    .content
+                                    .iter()
+                                    .map(|content| (Item::Body(content), depth + 1)),
+                            );
+                        }
+                    }
+                }
+            }
+            Item::Table(table) => {
+                for row in &table.rows {
+                    for cell in &row.cells {
+                        match cell {
+                            TableRowContent::TableCell(cell) => stack.extend(
+                                cell.content
+                                    .iter()
+                                    .map(|content| (Item::Cell(content), depth)),
+                            ),
+                            TableRowContent::SDT(sdt) => {
+                                if let Some(sdt_content) = &sdt.content {
+                                    stack.extend(
+                                        sdt_content
+                                            .content
+                                            .iter()
+                                            .map(|content| (Item::Body(content), depth + 1)),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rs_docx::document::{SDTContent, TableCell, TableRow, SDT};
+
+    /// A paragraph inside `levels` tables, each the only content of the
+    /// enclosing table's cell.
+    fn nested_tables(levels: usize) -> BodyContent<'static> {
+        let mut content = TableCellContent::Paragraph(Paragraph::default().push_text("deep"));
+        for _ in 0..levels {
+            let table = Table {
+                rows: vec![TableRow {
+                    cells: vec![TableRowContent::TableCell(TableCell {
+                        content: vec![content],
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            content = TableCellContent::Table(table);
+        }
+        match content {
+            TableCellContent::Table(table) => BodyContent::Table(table),
+            TableCellContent::Paragraph(para) => BodyContent::Paragraph(para),
+        }
+    }
+
+    #[test]
+    fn test_nesting_depth_counts_tables_and_content_controls() {
+        assert_eq!(nesting_depth(&[nested_tables(0)]), 0);
+        assert_eq!(nesting_depth(&[nested_tables(3)]), 3);
+
+        let sdt = BodyContent::Sdt(SDT {
+            content: Some(SDTContent {
+                content: vec![nested_tables(2)],
+            }),
+            ..Default::default()
+        });
+        assert_eq!(nesting_depth(&[nested_tables(1), sdt]), 3);
+    }
+
+    #[test]
+    fn test_deep_nesting_is_refused() {
+        let body = [nested_tables(1000)];
+        assert!(check([&body[..]], 1000).is_ok());
+        assert!(matches!(
+            check([&body[..]], 100),
+            Err(Error::NestingTooDeep(message)) if message.contains("1000 levels")
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 //! Numbering resolver - handles list numbering and indentation.
 
+use crate::LocalizationStrategy;
 use rs_docx::Docx;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Resolver for DOCX numbering definitions.
 pub struct NumberingResolver<'a> {
@@ -19,9 +21,41 @@ pub struct NumberingResolver<'a> {
     level_shifts: HashMap<i32, i32>,
     /// Maps (numId, ilvl) -> override LevelDef (style change)
     style_overrides: HashMap<(i32, i32), LevelDef>,
+    /// Formats list numbers before the built-in formats are tried.
+    localization: Option<Arc<dyn LocalizationStrategy>>,
     _phantom: std::marker::PhantomData<&'a ()>,
 }
 
+/// A resolved list item marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListMarker {
+    /// Marker text as Word displays it (e.g., "1.", "a)", "-").
+    pub label: String,
+    /// Counter value for numbered levels; `None` for bullets and unnumbered levels.
+    pub number: Option<i32>,
+}
+
+impl ListMarker {
+    fn bullet() -> Self {
+        Self {
+            label: "-".to_string(),
+            number: None,
+        }
+    }
+}
+
+/// Arabic letters in alphabetical (hijā'ī) order, for `arabicAlpha`.
+const ARABIC_ALPHA: [char; 28] = [
+    'أ', 'ب', 'ت', 'ث', 'ج', 'ح', 'خ', 'د', 'ذ', 'ر', 'ز', 'س', 'ش', 'ص', 'ض', 'ط', 'ظ', 'ع', 'غ',
+    'ف', 'ق', 'ك', 'ل', 'م', 'ن', 'ه', 'و', 'ي',
+];
+
+/// Arabic letters in abjad order, for `arabicAbjad`.
+const ARABIC_ABJAD: [char; 28] = [
+    'أ', 'ب', 'ج', 'د', 'ه', 'و', 'ز', 'ح', 'ط', 'ي', 'ك', 'ل', 'م', 'ن', 'س', 'ع', 'ف', 'ص', 'ق',
+    'ر', 'ش', 'ت', 'ث', 'خ', 'ذ', 'ض', 'ظ', 'غ',
+];
+
 #[derive(Clone, Debug)]
 struct LevelDef {
     ilvl: i32,
@@ -153,10 +187,20 @@ impl<'a> NumberingResolver<'a> {
             style_overrides,
             counters: HashMap::new(),
             level_shifts,
+            localization: None,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Formats list numbers with `localization` where it returns a number.
+    pub fn with_localization(
+        mut self,
+        localization: Option<Arc<dyn LocalizationStrategy>>,
+    ) -> Self {
+        self.localization = localization;
+        self
+    }
+
     /// Gets the indentation level for a list item.
     pub fn get_indent(&self, num_id: i32, ilvl: i32) -> usize {
         let mut indent = ilvl;
@@ -174,12 +218,18 @@ impl<'a> NumberingResolver<'a> {
     /// Gets the marker for a list item (e.g., "1.", "-", "a)").
     /// Updates the internal counter state.
     pub fn next_marker(&mut self, num_id: i32, ilvl: i32) -> String {
+        self.next_item(num_id, ilvl).label
+    }
+
+    /// Gets the marker for a list item along with its counter value.
+    /// Updates the internal counter state.
+    pub fn next_item(&mut self, num_id: i32, ilvl: i32) -> ListMarker {
         let Some(&abs_id) = self.num_instances.get(&num_id) else {
-            return "-".to_string();
+            return ListMarker::bullet();
         };
 
         let Some(levels) = self.abstract_nums.get(&abs_id) else {
-            return "-".to_string();
+            return ListMarker::bullet();
         };
 
         // Initialize counters for this abstract_num_id if not present
@@ -195,7 +245,7 @@ impl<'a> NumberingResolver<'a> {
             .or_else(|| levels.first());
 
         let Some(level) = level_def else {
-            return "-".to_string();
+            return ListMarker::bullet();
         };
 
         // Increment current level
@@ -221,6 +271,11 @@ impl<'a> NumberingResolver<'a> {
             *counter = 0;
         }
 
+        let number = match level.num_fmt.as_str() {
+            "bullet" | "none" => None,
+            _ => Some(counters[ilvl_idx]),
+        };
+
         // Use level text if available (substituting placeholders)
         if let Some(text) = &level.lvl_text {
             let mut marker = text.clone();
@@ -245,21 +300,41 @@ impl<'a> NumberingResolver<'a> {
                     // If count is 0, it means it hasn't been initialized/incremented yet, so use start value
                     let val = if *count == 0 { 1 } else { *count };
 
-                    let formatted_num = Self::format_num(fmt, val);
+                    let formatted_num = Self::localize_num(self.localization.as_deref(), fmt, val);
                     marker = marker.replace(&placeholder, &formatted_num);
                 }
             }
-            return marker;
+            return ListMarker {
+                label: marker,
+                number,
+            };
         }
 
         // Fallback: if no lvlText, add dot for standard types
-        let raw_num = Self::format_num(&level.num_fmt, counters[ilvl_idx]);
-        match level.num_fmt.as_str() {
-            "decimal" | "lowerLetter" | "upperLetter" | "lowerRoman" | "upperRoman" => {
+        let raw_num = Self::localize_num(
+            self.localization.as_deref(),
+            &level.num_fmt,
+            counters[ilvl_idx],
+        );
+        let label = match level.num_fmt.as_str() {
+            "decimal" | "lowerLetter" | "upperLetter" | "lowerRoman" | "upperRoman"
+            | "hindiNumbers" | "arabicAlpha" | "arabicAbjad" => {
                 format!("{}.", raw_num)
             }
             _ => raw_num,
-        }
+        };
+        ListMarker { label, number }
+    }
+
+    /// Formats a number with `localization`, falling back to [`Self::format_num`].
+    fn localize_num(
+        localization: Option<&dyn LocalizationStrategy>,
+        fmt: &str,
+        val: i32,
+    ) -> String {
+        localization
+            .and_then(|localization| localization.list_number(fmt, val))
+            .unwrap_or_else(|| Self::format_num(fmt, val))
     }
 
     /// Formats a number according to the format string.
@@ -287,6 +362,9 @@ impl<'a> NumberingResolver<'a> {
             "chosung" => Self::format_chosung(val),
             "geonodeo" => Self::format_geonodeo(val),
             "decimalEnclosedCircle" => Self::format_circle_number(val),
+            "hindiNumbers" => Self::format_arabic_indic(val),
+            "arabicAlpha" => Self::format_arabic_letter(&ARABIC_ALPHA, val),
+            "arabicAbjad" => Self::format_arabic_letter(&ARABIC_ABJAD, val),
             _ => format!("{}", val),
         }
     }
@@ -308,8 +386,28 @@ impl<'a> NumberingResolver<'a> {
         }
     }
 
+    /// Converts a number to Arabic-Indic digits (١, ٢, ٣...), Word's `hindiNumbers`.
+    fn format_arabic_indic(val: i32) -> String {
+        val.to_string()
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => char::from_u32(0x0660 + digit).unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+
+    /// Converts a number to an Arabic letter (أ, ب, ...) in the given order.
+    fn format_arabic_letter(letters: &[char], val: i32) -> String {
+        if val >= 1 && val as usize <= letters.len() {
+            letters[(val - 1) as usize].to_string()
+        } else {
+            Self::format_arabic_indic(val)
+        }
+    }
+
     /// Converts a number to Korean Ganada (가, 나, 다...).
-    fn format_ganada(val: i32) -> String {
+    pub(crate) fn format_ganada(val: i32) -> String {
         let chars = [
             '가', '나', '다', '라', '마', '바', '사', '아', '자', '차', '카', '타', '파', '하',
         ];
@@ -441,4 +539,57 @@ mod tests {
         let marker = resolver.next_marker(2, 0);
         assert_eq!(marker, "A)");
     }
+
+    #[test]
+    fn test_localization_formats_list_numbers() {
+        let level = |ilvl, fmt, text| Level {
+            i_level: Some(ilvl),
+            start: Some(LevelStart { value: Some(1) }),
+            number_format: Some(NumFmt {
+                value: Cow::Borrowed(fmt),
+            }),
+            level_text: Some(LevelText {
+                value: Some(Cow::Borrowed(text)),
+            }),
+            ..Default::default()
+        };
+        let docx = Docx {
+            numbering: Some(Numbering {
+                abstract_numberings: vec![AbstractNum {
+                    abstract_num_id: Some(1),
+                    levels: vec![
+                        level(0, "upperRoman", "%1."),
+                        level(1, "lowerLetter", "%1-%2)"),
+                    ],
+                    ..Default::default()
+                }],
+                numberings: vec![Num {
+                    num_id: Some(1),
+                    abstract_num_id: Some(AbstractNumId { value: Some(1) }),
+                    level_overrides: vec![],
+                }],
+            }),
+            ..Default::default()
+        };
+
+        let mut resolver = NumberingResolver::new(&docx)
+            .with_localization(Some(Arc::new(crate::KoreanLocalization)));
+        assert_eq!(resolver.next_marker(1, 0), "I.");
+        assert_eq!(resolver.next_marker(1, 1), "I-가)");
+        assert_eq!(resolver.next_marker(1, 1), "I-나)");
+
+        let mut resolver = NumberingResolver::new(&docx);
+        resolver.next_marker(1, 0);
+        assert_eq!(resolver.next_marker(1, 1), "I-a)");
+    }
+
+    #[test]
+    fn test_arabic_number_formats() {
+        assert_eq!(NumberingResolver::format_num("hindiNumbers", 7), "٧");
+        assert_eq!(NumberingResolver::format_num("hindiNumbers", 120), "١٢٠");
+        assert_eq!(NumberingResolver::format_num("arabicAlpha", 3), "ت");
+        assert_eq!(NumberingResolver::format_num("arabicAbjad", 3), "ج");
+        assert_eq!(NumberingResolver::format_num("arabicAbjad", 29), "٢٩");
+        assert_eq!(NumberingResolver::format_num("arabicAlpha", 0), "٠");
+    }
 }
@@ -1,15 +1,82 @@
 //! Paragraph converter - handles paragraph elements and their structure.
 
-use super::{ConversionContext, RunConverter};
+use super::context::{is_hidden, is_underlined};
+use super::figure::is_image_only;
+use super::raw_xml::{RawSmartTag, RawSmartTagContent};
+use super::{ConversionContext, ListMarker};
 use crate::render::{
     escape_html_attr, escape_markdown_link_destination, escape_markdown_link_text,
+    escape_markdown_link_title,
+};
+use crate::{
+    CodeBlocks, HeadingIdStyle, LeadingTab, ListLabels, Result, TrackedChanges, UnderlineHandling,
 };
-use crate::Result;
 use rs_docx::document::{Hyperlink, Paragraph, ParagraphContent};
+use std::borrow::Cow;
 
 /// Converter for Paragraph elements.
 pub struct ParagraphConverter;
 
+/// Text emitted for a page break (`w:br w:type="page"`).
+const PAGE_BREAK: &str = "\n\n---\n\n";
+/// Text emitted for a column break (`w:br w:type="column"`).
+const COLUMN_BREAK: &str = "\n\n";
+/// Placeholder for a line break (`w:br`, `w:cr`), replaced by the configured syntax.
+const LINE_BREAK: char = '\n';
+/// Placeholders for positional tabs with `PositionalTabs::Columns`, one per
+/// alignment of the text after them. Control characters cannot occur in
+/// document text, as XML 1.0 does not allow them.
+pub(super) const PTAB_LEFT: &str = "\u{1C}";
+pub(super) const PTAB_CENTER: &str = "\u{1D}";
+pub(super) const PTAB_RIGHT: &str = "\u{1E}";
+const PTABS: [char; 3] = ['\u{1C}', '\u{1D}', '\u{1E}'];
+
+/// Splits run text at page, column, and line breaks, and at positional tab
+/// placeholders.
+///
+/// Each part is paired with the break text that precedes it, so the breaks can
+/// be emitted outside of the run's formatting.
+fn split_breaks<'t>(
+    text: &'t str,
+    line_break: &'static str,
+) -> Vec<(Option<&'static str>, &'t str)> {
+    let mut parts = Vec::new();
+    for (i, page) in text.split(PAGE_BREAK).enumerate() {
+        for (j, column) in page.split(COLUMN_BREAK).enumerate() {
+            for (k, line) in column.split(LINE_BREAK).enumerate() {
+                let delimiter = match (i, j, k) {
+                    (_, _, 1..) => Some(line_break),
+                    (_, 1.., 0) => Some(COLUMN_BREAK),
+                    (1.., 0, 0) => Some(PAGE_BREAK),
+                    (0, 0, 0) => None,
+                };
+                for (n, (ptab, part)) in split_positional_tabs(line).into_iter().enumerate() {
+                    parts.push((if n == 0 { delimiter } else { ptab }, part));
+                }
+            }
+        }
+    }
+    parts
+}
+
+/// Splits text at positional tab placeholders, pairing each part with the
+/// placeholder before it.
+fn split_positional_tabs(text: &str) -> Vec<(Option<&'static str>, &str)> {
+    let mut parts = Vec::new();
+    let mut ptab = None;
+    let mut rest = text;
+    while let Some(index) = rest.find(PTABS) {
+        parts.push((ptab, &rest[..index]));
+        let next = &rest[index..index + 1];
+        ptab = [PTAB_LEFT, PTAB_CENTER, PTAB_RIGHT]
+            .into_iter()
+            .find(|&placeholder| placeholder == next);
+        rest = &rest[index + 1..];
+    }
+    parts.push((ptab, rest));
+    parts
+}
+
 /// Segment of formatted text with consistent styling.
 #[derive(Debug, Clone, PartialEq, Default)]
 struct FormattedSegment {
@@ -18,9 +85,43 @@ struct FormattedSegment {
     is_italic: bool,
     has_underline: bool,
     has_strike: bool,
+    /// Whether the text is a key name, written as `<kbd>`.
+    is_kbd: bool,
     is_insertion: bool,
     is_deletion: bool,
+    /// Author and date of the tracked change the text belongs to.
+    revision: Option<Revision>,
     anchor: Option<String>,
+    lang: Option<String>,
+}
+
+/// Metadata of a tracked change (`w:ins`/`w:del`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Revision {
+    author: Option<String>,
+    date: Option<String>,
+}
+
+impl Revision {
+    /// Returns `None` when the change records neither author nor date.
+    fn new(author: Option<&str>, date: Option<&str>) -> Option<Self> {
+        (author.is_some() || date.is_some()).then(|| Self {
+            author: author.map(str::to_string),
+            date: date.map(str::to_string),
+        })
+    }
+
+    /// Renders the metadata as ` data-author=".." data-date=".."`.
+    fn html_attrs(&self) -> String {
+        let mut attrs = String::new();
+        if let Some(author) = &self.author {
+            attrs.push_str(&format!(" data-author=\"{}\"", escape_html_attr(author)));
+        }
+        if let Some(date) = &self.date {
+            attrs.push_str(&format!(" data-date=\"{}\"", escape_html_attr(date)));
+        }
+        attrs
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,11 +130,67 @@ enum FieldPhase {
     Result,
 }
 
+/// Complex field currently open in a paragraph (`w:fldChar begin` .. `end`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldFrame {
+    phase: FieldPhase,
+    instruction: String,
+}
+
+/// Kind of a legacy form field (`FORMTEXT`, `FORMCHECKBOX`, `FORMDROPDOWN`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormField {
+    Text,
+    CheckBox,
+    DropDown,
+}
+
+impl FieldFrame {
+    fn form_field(&self) -> Option<FormField> {
+        match self.instruction.split_whitespace().next()? {
+            "FORMTEXT" => Some(FormField::Text),
+            "FORMCHECKBOX" => Some(FormField::CheckBox),
+            "FORMDROPDOWN" => Some(FormField::DropDown),
+            _ => None,
+        }
+    }
+
+    /// Whether the field result is rendered. Check boxes and drop-down
+    /// lists are rendered from their form field data instead.
+    fn shows_result(&self) -> bool {
+        self.phase == FieldPhase::Result
+            && !matches!(
+                self.form_field(),
+                Some(FormField::CheckBox | FormField::DropDown)
+            )
+    }
+
+    /// Returns the entry text of an index entry (`XE "entry"`) field.
+    fn index_entry(&self) -> Option<&str> {
+        let rest = self.instruction.trim_start().strip_prefix("XE")?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let rest = rest.trim();
+        let entry = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or(quoted),
+            None => rest.split_whitespace().next().unwrap_or(rest),
+        };
+        Some(entry)
+    }
+}
+
 impl ParagraphConverter {
     /// Filters a run so only field-visible content remains, updating field stack.
+    ///
+    /// Entries of index entry (`XE`) fields closed in this run are appended to
+    /// `index_entries`; they are hidden text rather than visible field results.
+    /// Form fields closed in this run are appended to `form_fields`.
     fn filter_run_by_field_state<'a>(
         run: &rs_docx::document::Run<'a>,
-        field_stack: &mut Vec<FieldPhase>,
+        field_stack: &mut Vec<FieldFrame>,
+        index_entries: &mut Vec<String>,
+        form_fields: &mut Vec<FormField>,
     ) -> rs_docx::document::Run<'a> {
         let mut filtered = run.clone();
         filtered.content.clear();
@@ -43,26 +200,38 @@ impl ParagraphConverter {
                 rs_docx::document::RunContent::FieldChar(fc) => {
                     if let Some(char_type) = &fc.ty {
                         match char_type {
-                            rs_docx::document::CharType::Begin => {
-                                field_stack.push(FieldPhase::Instruction)
-                            }
+                            rs_docx::document::CharType::Begin => field_stack.push(FieldFrame {
+                                phase: FieldPhase::Instruction,
+                                instruction: String::new(),
+                            }),
                             rs_docx::document::CharType::Separate => {
                                 if let Some(last) = field_stack.last_mut() {
-                                    *last = FieldPhase::Result;
+                                    last.phase = FieldPhase::Result;
                                 }
                             }
                             rs_docx::document::CharType::End => {
-                                let _ = field_stack.pop();
+                                if let Some(field) = field_stack.pop() {
+                                    if let Some(entry) = field.index_entry() {
+                                        index_entries.push(entry.to_string());
+                                    }
+                                    form_fields.extend(field.form_field());
+                                }
                             }
                         }
                     }
                 }
-                // Keep existing behavior: field instructions are never rendered.
-                rs_docx::document::RunContent::InstrText(_)
-                | rs_docx::document::RunContent::DelInstrText(_) => {}
+                // Field instructions are never rendered, only recorded.
+                rs_docx::document::RunContent::InstrText(instr) => {
+                    if let Some(last) = field_stack.last_mut() {
+                        if last.phase == FieldPhase::Instruction {
+                            last.instruction.push_str(&instr.text);
+                        }
+                    }
+                }
+                rs_docx::document::RunContent::DelInstrText(_) => {}
                 _ => {
                     // Skip non-instruction payload while inside field instruction section.
-                    if field_stack.last() != Some(&FieldPhase::Instruction) {
+                    if field_stack.last().map_or(true, FieldFrame::shows_result) {
                         filtered.content.push(content.clone());
                     }
                 }
@@ -72,25 +241,190 @@ impl ParagraphConverter {
         filtered
     }
 
+    /// Returns the entries of the index entry (`XE`) fields in the
+    /// paragraph's runs, in field order.
+    pub(super) fn index_entries(para: &Paragraph<'_>) -> Vec<String> {
+        let mut field_stack = Vec::new();
+        let mut entries = Vec::new();
+        for content in &para.content {
+            if let ParagraphContent::Run(run) = content {
+                Self::filter_run_by_field_state(
+                    run,
+                    &mut field_stack,
+                    &mut entries,
+                    &mut Vec::new(),
+                );
+            }
+        }
+        entries
+    }
+
+    /// Returns the text of a paragraph to write in a code block, and how the
+    /// block is fenced: a paragraph in one of the `verbatim_styles` gets a
+    /// plain fence; with `code_blocks` set, so does a paragraph that is not a
+    /// heading or list item and whose runs with text are all in a monospace
+    /// font, outside hyperlinks.
+    pub(crate) fn code_text<'a>(
+        para: &Paragraph<'a>,
+        context: &ConversionContext<'a>,
+    ) -> Option<(String, CodeBlocks)> {
+        let para_style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        let runs = Self::literal_runs(para);
+        let text: String = runs.iter().map(|(_, text, _)| text.as_str()).collect();
+        if text.trim().is_empty() {
+            return None;
+        }
+        if context.is_verbatim_style(para_style_id) {
+            return Some((text, CodeBlocks::default()));
+        }
+
+        let settings = context.code_blocks()?;
+        if Self::is_heading_or_list_item(para, context) {
+            return None;
+        }
+        let is_code = runs.iter().all(|(run, text, in_link)| {
+            text.trim().is_empty() || (!in_link && context.effective_monospace(run, para_style_id))
+        });
+        is_code.then(|| (text, settings.clone()))
+    }
+
+    /// Returns the runs of a paragraph, including those of hyperlinks, with
+    /// their text as written: tabs and line breaks are kept, field
+    /// instructions dropped, and nothing is escaped. The flag marks runs of a
+    /// hyperlink.
+    fn literal_runs<'a>(para: &Paragraph<'a>) -> Vec<(rs_docx::document::Run<'a>, String, bool)> {
+        let mut field_stack = Vec::new();
+        let mut runs = Vec::new();
+        for content in &para.content {
+            let (content_runs, in_link) = match content {
+                ParagraphContent::Run(run) => (std::slice::from_ref(run), false),
+                ParagraphContent::Link(link) => (link.content.as_slice(), true),
+                _ => continue,
+            };
+            for run in content_runs {
+                let run = Self::filter_run_by_field_state(
+                    run,
+                    &mut field_stack,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                );
+                let mut text = String::new();
+                for item in &run.content {
+                    match item {
+                        rs_docx::document::RunContent::Text(t) => text.push_str(&t.text),
+                        rs_docx::document::RunContent::Tab(_)
+                        | rs_docx::document::RunContent::PTab(_) => text.push('\t'),
+                        rs_docx::document::RunContent::Break(_)
+                        | rs_docx::document::RunContent::CarriageReturn(_) => text.push(LINE_BREAK),
+                        _ => {}
+                    }
+                }
+                runs.push((run, text, in_link));
+            }
+        }
+        runs
+    }
+
+    /// Returns true when a run of the paragraph, or of a hyperlink or
+    /// insertion in it, holds a `w:lastRenderedPageBreak` hint.
+    pub(crate) fn has_rendered_page_break(para: &Paragraph<'_>) -> bool {
+        para.content
+            .iter()
+            .flat_map(|content| match content {
+                ParagraphContent::Run(run) => std::slice::from_ref(run),
+                ParagraphContent::Link(link) => link.content.as_slice(),
+                ParagraphContent::Insertion(ins) => ins.runs.as_slice(),
+                _ => &[],
+            })
+            .flat_map(|run| &run.content)
+            .any(|item| {
+                matches!(
+                    item,
+                    rs_docx::document::RunContent::LastRenderedPageBreak(_)
+                )
+            })
+    }
+
     /// Converts a Paragraph to Markdown.
     pub fn convert<'a>(
         para: &Paragraph<'a>,
         context: &mut ConversionContext<'a>,
     ) -> Result<String> {
+        context.warn_repeated_heading(para);
+        if context.is_merged_heading(para) {
+            return Ok(String::new());
+        }
+        if is_image_only(para) && !Self::is_heading_or_list_item(para, context) {
+            return Self::convert_image_only(para, context);
+        }
+        if let Some(level) = context.toc_entry_level(para) {
+            return Ok(Self::convert_toc_entry(para, level, context));
+        }
+        if context.is_generated_index(para) {
+            return Ok(context.take_generated_index().unwrap_or_default());
+        }
+
         // Collect all formatted segments from runs
-        let segments = Self::collect_segments(para, context)?;
+        context.set_in_list_item(Self::is_list_item(para, context));
+        let segments = Self::collect_segments(para, context);
+        context.set_in_list_item(false);
+        let mut segments = segments?;
+        Self::apply_underline_handling(&mut segments, context.underline_handling());
+
+        // A drop-cap frame only holds the first letter of the next paragraph,
+        // so carry it over instead of emitting a detached block.
+        if context.is_drop_cap(para) {
+            let letters: String = segments.iter().map(|seg| seg.text.as_str()).collect();
+            context.push_drop_cap(letters.trim());
+            return Ok(String::new());
+        }
+        if let Some(letters) = context.take_drop_cap() {
+            let first_content = segments
+                .iter()
+                .position(|seg| !(seg.text.is_empty() && seg.anchor.is_some()))
+                .unwrap_or(segments.len());
+            segments.insert(
+                first_content,
+                FormattedSegment {
+                    text: letters,
+                    ..Default::default()
+                },
+            );
+        }
+
+        if let Some(space) = context.cjk_latin_space() {
+            segments = Self::space_cjk_latin_boundaries(segments, space);
+        }
 
         // Merge adjacent segments with same formatting
         let merged = Self::merge_segments(segments);
 
         // Separate leading anchors (anchors at the start with empty text) from the rest
         let mut leading_anchors = Vec::new();
+        let mut heading_id = None;
+        if let Some(slug) = context.heading_slug(para).filter(|_| !context.plain_text()) {
+            match context.heading_id_style() {
+                HeadingIdStyle::Attribute => heading_id = Some(format!(" {{#{}}}", slug)),
+                HeadingIdStyle::HtmlAnchor if !context.no_html() => {
+                    leading_anchors.push(format!("<a id=\"{}\"></a>", escape_html_attr(slug)));
+                }
+                HeadingIdStyle::HtmlAnchor => {}
+            }
+        }
         let mut content_segments = Vec::new();
         let mut looking_for_anchors = true;
 
         for seg in merged {
             if looking_for_anchors && seg.text.is_empty() && seg.anchor.is_some() {
-                if let Some(anchor) = &seg.anchor {
+                if let Some(anchor) = seg
+                    .anchor
+                    .as_ref()
+                    .filter(|a| Self::emits_anchor(a, context))
+                {
                     // Use id attribute instead of name for better compatibility (VS Code etc.)
                     leading_anchors.push(format!("<a id=\"{}\"></a>", escape_html_attr(anchor)));
                 }
@@ -105,6 +439,26 @@ impl ParagraphConverter {
 
         let anchor_tags = leading_anchors.join("");
 
+        if context.is_figure_caption(para) {
+            if let Some(images) = context.take_figure() {
+                if let Some(figure) = context.pandoc_figure(&images, text.trim()) {
+                    if anchor_tags.is_empty() {
+                        return Ok(figure);
+                    }
+                    return Ok(format!("{}\n\n{}", anchor_tags, figure));
+                }
+                if context.no_html() {
+                    return Ok(format!("{}\n\n{}", images, text.trim()));
+                }
+                return Ok(format!(
+                    "<figure>\n\n{}\n\n<figcaption>{}{}</figcaption>\n</figure>",
+                    images,
+                    anchor_tags,
+                    text.trim()
+                ));
+            }
+        }
+
         let is_effectively_empty = if context.preserve_whitespace() {
             text.is_empty()
         } else {
@@ -116,8 +470,15 @@ impl ParagraphConverter {
             return Ok(anchor_tags);
         }
 
+        if context.is_bibliography_entry(para) {
+            return Ok(format!("- {}{}", anchor_tags, text.trim()));
+        }
+
         // Apply paragraph-level formatting
-        let formatted_text = Self::apply_paragraph_formatting(para, text, context)?;
+        let mut formatted_text = Self::apply_paragraph_formatting(para, text, context)?;
+        if !formatted_text.is_empty() {
+            formatted_text.extend(heading_id);
+        }
 
         if !anchor_tags.is_empty() {
             // Place anchors on the line BEFORE the paragraph
@@ -129,6 +490,173 @@ impl ParagraphConverter {
         }
     }
 
+    /// Returns the paragraph text, trimmed unless `preserve_whitespace` is
+    /// set, with the tabs it starts with written according to `leading_tab`.
+    fn output_text(text: &str, context: &ConversionContext<'_>) -> String {
+        let preserve = context.preserve_whitespace();
+        let text = if preserve { text } else { text.trim_end() };
+        let body = text.trim_start_matches([' ', '\t']);
+        let indent = &text[..text.len() - body.len()];
+        let tabs = indent.matches('\t').count();
+        let mut output = match context.leading_tab() {
+            LeadingTab::Prefix(prefix) => prefix.repeat(tabs),
+            LeadingTab::Strip => String::new(),
+        };
+        if preserve {
+            output.extend(indent.chars().filter(|&c| c != '\t'));
+        }
+        output.push_str(if preserve { body } else { body.trim_start() });
+        output
+    }
+
+    fn is_heading_or_list_item<'a>(para: &Paragraph<'a>, context: &ConversionContext<'a>) -> bool {
+        context.paragraph_heading_level(para).is_some() || Self::is_numbered(para, context)
+    }
+
+    /// Returns true for a numbered or bulleted paragraph that is not a heading.
+    fn is_list_item<'a>(para: &Paragraph<'a>, context: &ConversionContext<'a>) -> bool {
+        context.paragraph_heading_level(para).is_none() && Self::is_numbered(para, context)
+    }
+
+    fn is_numbered<'a>(para: &Paragraph<'a>, context: &ConversionContext<'a>) -> bool {
+        let para_style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        context
+            .resolve_paragraph_property(para.property.as_ref(), para_style_id)
+            .numbering
+            .is_some()
+    }
+
+    /// Converts a paragraph holding only images: each image on its own line,
+    /// without alignment or run formatting.
+    ///
+    /// A captioned figure's images are held until its caption paragraph,
+    /// which emits the `<figure>`.
+    fn convert_image_only<'a>(
+        para: &Paragraph<'a>,
+        context: &mut ConversionContext<'a>,
+    ) -> Result<String> {
+        let mut anchors = String::new();
+        let mut images = Vec::new();
+        for content in &para.content {
+            match content {
+                ParagraphContent::BookmarkStart(bookmark) => {
+                    if let Some(name) = bookmark
+                        .name
+                        .as_deref()
+                        .filter(|name| Self::emits_anchor(name, context))
+                    {
+                        anchors.push_str(&format!("<a id=\"{}\"></a>", escape_html_attr(name)));
+                    }
+                }
+                ParagraphContent::Run(run) => {
+                    for item in &run.content {
+                        let image = match item {
+                            rs_docx::document::RunContent::Drawing(drawing) => {
+                                context.extract_image_from_drawing(drawing)
+                            }
+                            rs_docx::document::RunContent::Pict(pict) => {
+                                context.extract_image_from_pict(pict)
+                            }
+                            _ => continue,
+                        };
+                        if let Ok(Some(image)) = image {
+                            images.push(image);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let images = images.join("\n");
+        if context.has_figure_caption(para) && !images.is_empty() {
+            context.push_figure(images);
+            return Ok(anchors);
+        }
+        Ok(match (anchors.is_empty(), images.is_empty()) {
+            (_, true) => anchors,
+            (true, false) => images,
+            (false, false) => format!("{}\n{}", anchors, images),
+        })
+    }
+
+    /// Converts a table of contents entry to a list item linking to its
+    /// heading, nested by TOC level.
+    ///
+    /// The page number follows the entry's last tab, whose dot leader fills
+    /// the line; both are dropped, and any other tab becomes a space. The link
+    /// target is the entry's hyperlink anchor or, in a TOC without hyperlinks,
+    /// the bookmark of its `PAGEREF` field.
+    fn convert_toc_entry<'a>(
+        para: &Paragraph<'a>,
+        level: usize,
+        context: &mut ConversionContext<'a>,
+    ) -> String {
+        let para_style_id = para
+            .property
+            .as_ref()
+            .and_then(|p| p.style_id.as_ref())
+            .map(|s| s.value.as_ref());
+        let mut text = String::new();
+        let mut anchor: Option<String> = None;
+        let mut field_stack = Vec::new();
+
+        for content in &para.content {
+            let runs = match content {
+                ParagraphContent::Run(run) => std::slice::from_ref(run),
+                ParagraphContent::Link(hyperlink) => {
+                    if anchor.is_none() {
+                        anchor = hyperlink.anchor.as_deref().map(str::to_owned);
+                    }
+                    hyperlink.content.as_slice()
+                }
+                _ => continue,
+            };
+            for run in runs {
+                for item in &run.content {
+                    if let rs_docx::document::RunContent::InstrText(instr) = item {
+                        let mut words = instr.text.split_whitespace();
+                        if anchor.is_none() && words.next() == Some("PAGEREF") {
+                            anchor = words.next().map(str::to_owned);
+                        }
+                    }
+                }
+                let filtered_run = Self::filter_run_by_field_state(
+                    run,
+                    &mut field_stack,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                );
+                text.push_str(&Self::extract_text(&filtered_run, context, para_style_id));
+            }
+        }
+
+        let title = text
+            .rsplit_once('\t')
+            .map_or(text.as_str(), |(title, _)| title);
+        let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+        if title.is_empty() {
+            return String::new();
+        }
+        let indent = "  ".repeat(level - 1);
+        match anchor.filter(|_| !context.plain_text()) {
+            Some(anchor) => {
+                let anchor = context.bookmark_slug(&anchor).unwrap_or(&anchor);
+                format!(
+                    "{}- [{}](#{})",
+                    indent,
+                    escape_markdown_link_text(&title),
+                    escape_markdown_link_destination(anchor)
+                )
+            }
+            None => format!("{}- {}", indent, title),
+        }
+    }
+
     /// Collects formatted segments from paragraph content.
     fn collect_segments<'a>(
         para: &Paragraph<'a>,
@@ -144,24 +672,120 @@ impl ParagraphConverter {
             .and_then(|p| p.style_id.as_ref())
             .map(|s| s.value.as_ref());
 
-        for content in &para.content {
+        let equations = context.paragraph_equations(para);
+        let mut equations = equations.iter().peekable();
+        let mut display_equations = Vec::new();
+        let charts = context.paragraph_charts(para);
+        let mut charts = charts.iter().peekable();
+        let vml_images = context.paragraph_vml_images(para);
+        let mut vml_images = vml_images.iter().peekable();
+        let rubies = context.paragraph_rubies(para);
+        let mut rubies = rubies.iter().peekable();
+        let smart_tags = context.paragraph_smart_tags(para);
+        let mut smart_tags = smart_tags.iter().peekable();
+        let raw_form_fields = context.paragraph_form_fields(para);
+        let mut raw_form_fields = raw_form_fields.iter();
+        let mut index_entry_count = 0;
+
+        for (idx, content) in para.content.iter().enumerate() {
+            while let Some(equation) = equations.next_if(|eq| eq.position <= idx) {
+                if equation.display {
+                    display_equations.push(segments.len());
+                }
+                segments.push(FormattedSegment {
+                    text: context.render_equation(equation),
+                    ..Default::default()
+                });
+            }
+            while let Some(chart) = charts.next_if(|chart| chart.position <= idx) {
+                segments.push(FormattedSegment {
+                    text: context.render_chart(chart),
+                    ..Default::default()
+                });
+            }
+            while let Some(image) = vml_images.next_if(|image| image.position <= idx) {
+                segments.push(FormattedSegment {
+                    text: context.render_vml_image(image),
+                    ..Default::default()
+                });
+            }
+            while let Some(ruby) = rubies.next_if(|ruby| ruby.position <= idx) {
+                segments.push(FormattedSegment {
+                    text: context.render_ruby(ruby),
+                    ..Default::default()
+                });
+            }
+            while let Some(tag) = smart_tags.next_if(|tag| tag.position <= idx) {
+                Self::push_smart_tag(tag, context, para_style_id, &mut segments);
+            }
+
             match content {
                 ParagraphContent::Run(run) => {
-                    let filtered_run = Self::filter_run_by_field_state(run, &mut field_stack);
-                    if filtered_run.content.is_empty() {
-                        continue;
-                    }
+                    let mut index_entries = Vec::new();
+                    let mut form_fields = Vec::new();
+                    let filtered_run = Self::filter_run_by_field_state(
+                        run,
+                        &mut field_stack,
+                        &mut index_entries,
+                        &mut form_fields,
+                    );
 
                     // Extract visible text only (field instructions already filtered out).
-                    let text = Self::extract_text(&filtered_run, context);
-                    if !text.is_empty() {
-                        let segs =
-                            Self::run_to_segment(&filtered_run, &text, context, para_style_id);
-                        segments.extend(segs);
+                    if !filtered_run.content.is_empty() {
+                        let text = Self::extract_text(&filtered_run, context, para_style_id);
+                        if !text.is_empty() {
+                            let segs =
+                                Self::run_to_segment(&filtered_run, &text, context, para_style_id);
+                            segments.extend(segs);
+                        }
+                    }
+
+                    // Form fields are matched to their data in document order.
+                    for field in form_fields {
+                        let data = raw_form_fields.next();
+                        let text = match field {
+                            FormField::Text => continue,
+                            FormField::CheckBox => {
+                                let checked = data.and_then(|data| data.checked);
+                                if checked == Some(true) { "[x]" } else { "[ ]" }.to_string()
+                            }
+                            FormField::DropDown => match data.and_then(|d| d.selected_entry()) {
+                                Some(entry) => context.document_text(entry).into_owned(),
+                                None => continue,
+                            },
+                        };
+                        segments.push(FormattedSegment {
+                            text,
+                            ..Default::default()
+                        });
+                    }
+
+                    // Index entries are hidden text in Word.
+                    for entry in index_entries {
+                        if let Some(anchor) = context.index_entry_anchor(para, index_entry_count) {
+                            segments.push(FormattedSegment {
+                                anchor: Some(anchor.to_string()),
+                                ..Default::default()
+                            });
+                        }
+                        index_entry_count += 1;
+                        let text = context.hidden_text_replacement(&entry).unwrap_or(entry);
+                        if !text.is_empty() {
+                            segments.push(FormattedSegment {
+                                text,
+                                ..Default::default()
+                            });
+                        }
                     }
                 }
                 ParagraphContent::Link(hyperlink) => {
-                    let link_md = Self::convert_hyperlink(hyperlink, context, para_style_id)?;
+                    let tooltip = context.link_tooltip(para, idx);
+                    let link_md = Self::convert_hyperlink(
+                        hyperlink,
+                        tooltip.as_deref(),
+                        context,
+                        para_style_id,
+                    )?;
                     if !link_md.is_empty() {
                         // Hyperlinks are treated as plain text segments
                         segments.push(FormattedSegment {
@@ -171,7 +795,12 @@ impl ParagraphConverter {
                     }
                 }
                 ParagraphContent::BookmarkStart(bookmark) => {
-                    if let Some(name) = &bookmark.name {
+                    // A bookmark without an anchor must not split the runs around it.
+                    if let Some(name) = bookmark
+                        .name
+                        .as_deref()
+                        .filter(|name| Self::emits_anchor(name, context))
+                    {
                         segments.push(FormattedSegment {
                             anchor: Some(name.to_string()),
                             ..Default::default()
@@ -182,54 +811,203 @@ impl ParagraphConverter {
                 ParagraphContent::CommentRangeStart(_) => {}
                 ParagraphContent::CommentRangeEnd(_) => {}
                 ParagraphContent::SDT(sdt) => {
-                    // Structured document tags (TOC, etc.) - extract inner content
-                    if let Some(sdt_content) = &sdt.content {
-                        for bc in &sdt_content.content {
-                            if let rs_docx::document::BodyContent::Paragraph(inner_para) = bc {
-                                let inner_segs = Self::collect_segments(inner_para, context)?;
-                                segments.extend(inner_segs);
-                            }
+                    // Structured document tags (TOC, etc.) - extract inner content,
+                    // or that of the building block an empty one refers to
+                    let block = context.building_block(sdt)?;
+                    let content = match (&block, &sdt.content) {
+                        (Some(block), _) => block.content.as_slice(),
+                        (None, Some(sdt_content)) => sdt_content.content.as_slice(),
+                        (None, None) => &[],
+                    };
+                    for bc in content {
+                        if let rs_docx::document::BodyContent::Paragraph(inner_para) = bc {
+                            let inner_segs = Self::collect_segments(inner_para, context)?;
+                            segments.extend(inner_segs);
                         }
                     }
                 }
                 ParagraphContent::Insertion(ins) => {
                     // Handle inserted content (track changes)
+                    let tracked_changes = context.tracked_changes();
+                    if tracked_changes == TrackedChanges::RejectAll {
+                        continue;
+                    }
+                    let revision = Revision::new(ins.author.as_deref(), ins.date.as_deref());
                     for run in &ins.runs {
-                        let text = Self::extract_text(run, context);
+                        let text = Self::extract_text(run, context, para_style_id);
                         if !text.is_empty() {
                             let mut segs = Self::run_to_segment(run, &text, context, para_style_id);
-                            for seg in &mut segs {
-                                seg.is_insertion = true;
+                            if tracked_changes == TrackedChanges::Markup {
+                                for seg in &mut segs {
+                                    seg.is_insertion = true;
+                                    seg.revision = revision.clone();
+                                }
                             }
                             segments.extend(segs);
                         }
                     }
                 }
-                ParagraphContent::Deletion(del) => {
-                    // Handle deleted content (track changes)
-                    let text = Self::extract_deleted_text(del);
-                    if !text.is_empty() {
-                        segments.push(FormattedSegment {
-                            text,
-                            is_deletion: true,
-                            ..Default::default()
-                        });
+                ParagraphContent::Deletion(del) => match context.tracked_changes() {
+                    TrackedChanges::AcceptAll => {}
+                    // Rejected deletions are restored with their run formatting.
+                    TrackedChanges::RejectAll => {
+                        for run in &del.runs {
+                            let text = context
+                                .document_text(&Self::extract_deleted_run_text(run))
+                                .into_owned();
+                            if !text.is_empty() {
+                                segments.extend(Self::run_to_segment(
+                                    run,
+                                    &text,
+                                    context,
+                                    para_style_id,
+                                ));
+                            }
+                        }
+                    }
+                    TrackedChanges::Markup => {
+                        let text = context
+                            .document_text(&Self::extract_deleted_text(del))
+                            .into_owned();
+                        if !text.is_empty() {
+                            segments.push(FormattedSegment {
+                                text,
+                                is_deletion: true,
+                                revision: Revision::new(del.author.as_deref(), del.date.as_deref()),
+                                ..Default::default()
+                            });
+                        }
                     }
+                },
+            }
+        }
+
+        for equation in equations {
+            if equation.display {
+                display_equations.push(segments.len());
+            }
+            segments.push(FormattedSegment {
+                text: context.render_equation(equation),
+                ..Default::default()
+            });
+        }
+        for chart in charts {
+            segments.push(FormattedSegment {
+                text: context.render_chart(chart),
+                ..Default::default()
+            });
+        }
+        for image in vml_images {
+            segments.push(FormattedSegment {
+                text: context.render_vml_image(image),
+                ..Default::default()
+            });
+        }
+        for ruby in rubies {
+            segments.push(FormattedSegment {
+                text: context.render_ruby(ruby),
+                ..Default::default()
+            });
+        }
+        for tag in smart_tags {
+            Self::push_smart_tag(tag, context, para_style_id, &mut segments);
+        }
+
+        Ok(Self::break_display_equations(
+            segments,
+            &display_equations,
+            context.hard_line_break(),
+        ))
+    }
+
+    /// Puts the display equations (`m:oMathPara`) at the given segment
+    /// indices on lines of their own, with line breaks between them and the
+    /// paragraph's other content.
+    fn break_display_equations(
+        segments: Vec<FormattedSegment>,
+        display_equations: &[usize],
+        line_break: &str,
+    ) -> Vec<FormattedSegment> {
+        if display_equations.is_empty() {
+            return segments;
+        }
+        let mut broken = Vec::with_capacity(segments.len() + 2 * display_equations.len());
+        let mut has_content = false;
+        let mut after_display = false;
+        for (idx, segment) in segments.into_iter().enumerate() {
+            if !segment.text.trim().is_empty() {
+                let display = display_equations.contains(&idx);
+                if has_content && (display || after_display) {
+                    broken.push(FormattedSegment {
+                        text: line_break.to_string(),
+                        ..Default::default()
+                    });
                 }
+                has_content = true;
+                after_display = display;
             }
+            broken.push(segment);
         }
+        broken
+    }
 
-        Ok(segments)
+    /// Appends the segments of a smart tag's runs, converted like the
+    /// paragraph's own, in a `<span>` naming its type with
+    /// `SmartTags::Annotate`.
+    fn push_smart_tag<'a>(
+        tag: &RawSmartTag,
+        context: &mut ConversionContext<'a>,
+        para_style_id: Option<&str>,
+        segments: &mut Vec<FormattedSegment>,
+    ) {
+        let open = context.smart_tag_open(tag);
+        if let Some(open) = &open {
+            segments.push(FormattedSegment {
+                text: open.clone(),
+                ..Default::default()
+            });
+        }
+        for item in &tag.content {
+            match item {
+                RawSmartTagContent::Run(range) => {
+                    let Some(run) = context.smart_tag_run(range) else {
+                        continue;
+                    };
+                    let text = Self::extract_text(&run, context, para_style_id);
+                    if !text.is_empty() {
+                        segments.extend(Self::run_to_segment(&run, &text, context, para_style_id));
+                    }
+                }
+                RawSmartTagContent::Tag(tag) => {
+                    Self::push_smart_tag(tag, context, para_style_id, segments);
+                }
+            }
+        }
+        if open.is_some() {
+            segments.push(FormattedSegment {
+                text: "</span>".to_string(),
+                ..Default::default()
+            });
+        }
     }
 
     /// Extracts deleted text from a Deletion element.
     fn extract_deleted_text(del: &rs_docx::document::Deletion) -> String {
+        del.runs
+            .iter()
+            .map(Self::extract_deleted_run_text)
+            .collect()
+    }
+
+    /// Extracts the deleted text (`w:delText`) of a run in a Deletion element.
+    ///
+    /// Only deleted runs carry `w:delText`; [`extract_text`](Self::extract_text)
+    /// reads `w:t` alone, so deleted text never reaches the output otherwise.
+    fn extract_deleted_run_text(run: &rs_docx::document::Run) -> String {
         let mut text = String::new();
-        for run in &del.runs {
-            for content in &run.content {
-                if let rs_docx::document::RunContent::DelText(del_text) = content {
-                    text.push_str(&del_text.text);
-                }
+        for content in &run.content {
+            if let rs_docx::document::RunContent::DelText(del_text) = content {
+                text.push_str(&del_text.text);
             }
         }
         text
@@ -239,22 +1017,34 @@ impl ParagraphConverter {
     fn extract_text<'a>(
         run: &rs_docx::document::Run<'a>,
         context: &mut ConversionContext<'a>,
+        para_style_id: Option<&str>,
     ) -> String {
         let mut text = String::new();
+        let symbols = context.symbol_overrides();
+        let symbol_font = symbols.text_font(run.property.as_ref());
+        let uppercase = context.uppercase_run(run, para_style_id);
         for content in &run.content {
             match content {
-                rs_docx::document::RunContent::Text(t) => {
-                    text.push_str(&t.text);
-                }
+                rs_docx::document::RunContent::Text(t) => match symbol_font {
+                    Some(font) => {
+                        text.push_str(&context.document_text(&symbols.map_text(font, &t.text)))
+                    }
+                    None if uppercase => {
+                        text.push_str(&context.document_text(&t.text.to_uppercase()))
+                    }
+                    None => text.push_str(&context.document_text(&t.text)),
+                },
                 rs_docx::document::RunContent::Tab(_) => {
                     text.push('\t');
                 }
                 rs_docx::document::RunContent::Break(br) => match br.ty {
-                    Some(rs_docx::document::BreakType::Page) => text.push_str("\n\n---\n\n"),
-                    _ => text.push('\n'),
+                    Some(rs_docx::document::BreakType::Page) => text.push_str(PAGE_BREAK),
+                    // Columns flow into one, so a column break only separates paragraphs.
+                    Some(rs_docx::document::BreakType::Column) => text.push_str(COLUMN_BREAK),
+                    _ => text.push(LINE_BREAK),
                 },
                 rs_docx::document::RunContent::CarriageReturn(_) => {
-                    text.push('\n');
+                    text.push(LINE_BREAK);
                 }
                 rs_docx::document::RunContent::NoBreakHyphen(_) => {
                     text.push('\u{2011}');
@@ -263,18 +1053,15 @@ impl ParagraphConverter {
                     text.push('\u{00AD}');
                 }
                 rs_docx::document::RunContent::Sym(sym) => {
-                    if let Some(char_code) = &sym.char {
-                        if let Ok(code) = u32::from_str_radix(char_code, 16) {
-                            if let Some(c) = char::from_u32(code) {
-                                text.push(c);
-                            }
-                        }
-                    }
+                    text.extend(symbols.sym_text(sym.font.as_deref(), sym.char.as_deref()));
                 }
-                rs_docx::document::RunContent::PTab(_) => {
-                    text.push('\t');
+                rs_docx::document::RunContent::PTab(ptab) => {
+                    text.push_str(context.positional_tab(ptab, true));
                 }
-                rs_docx::document::RunContent::LastRenderedPageBreak(_) => {
+                // With `page_boundaries` the hint becomes a block of its own.
+                rs_docx::document::RunContent::LastRenderedPageBreak(_)
+                    if !context.page_boundaries() =>
+                {
                     text.push_str("\n\n---\n\n");
                 }
                 rs_docx::document::RunContent::PgNum(_) => {
@@ -329,7 +1116,7 @@ impl ParagraphConverter {
         text
     }
 
-    /// Creates formatted segments from a run, splitting on page breaks.
+    /// Creates formatted segments from a run, splitting on page and column breaks.
     fn run_to_segment<'a>(
         run: &rs_docx::document::Run<'a>,
         text: &str,
@@ -358,19 +1145,42 @@ impl ParagraphConverter {
             .as_ref()
             .map(|i| i.value.unwrap_or(true))
             .unwrap_or(false);
-        let has_underline = props.underline.is_some();
+        let has_underline = is_underlined(&props);
         let has_strike = props
             .strike
             .as_ref()
             .map(|s| s.value.unwrap_or(true))
             .unwrap_or(false);
 
-        let delimiter = "\n\n---\n\n";
-        let parts: Vec<&str> = text.split(delimiter).collect();
+        if is_hidden(&props) {
+            if let Some(replacement) = context.hidden_text_replacement(text) {
+                if replacement.is_empty() {
+                    return Vec::new();
+                }
+                return vec![FormattedSegment {
+                    text: replacement,
+                    ..Default::default()
+                }];
+            }
+        }
+
+        let lang = context.run_language(&props, text);
+        let is_kbd = context.is_kbd_style(run_style_id);
+
+        let text = match context.cjk_latin_space() {
+            Some(space) => crate::localization::space_cjk_latin(text, space),
+            None => Cow::Borrowed(text),
+        };
+        let parts = split_breaks(&text, context.line_break());
         let mut segments = Vec::new();
 
-        for (i, part) in parts.iter().enumerate() {
-            if i > 0 {
+        for (delimiter, part) in parts {
+            // Plain text has no rule for a page break to be written as.
+            let delimiter = delimiter.map(|delimiter| match delimiter {
+                PAGE_BREAK if context.plain_text() => COLUMN_BREAK,
+                delimiter => delimiter,
+            });
+            if let Some(delimiter) = delimiter {
                 // Add the break segment with no formatting
                 segments.push(FormattedSegment {
                     text: delimiter.to_string(),
@@ -378,9 +1188,12 @@ impl ParagraphConverter {
                     is_italic: false,
                     has_underline: false,
                     has_strike: false,
+                    is_kbd: false,
                     is_insertion: false,
                     is_deletion: false,
+                    revision: None,
                     anchor: None,
+                    lang: None,
                 });
             }
             if !part.is_empty() {
@@ -390,9 +1203,12 @@ impl ParagraphConverter {
                     is_italic,
                     has_underline,
                     has_strike,
+                    is_kbd,
                     is_insertion: false,
                     is_deletion: false,
+                    revision: None,
                     anchor: None,
+                    lang: lang.clone(),
                 });
             }
         }
@@ -400,6 +1216,43 @@ impl ParagraphConverter {
         segments
     }
 
+    /// Turns underlines into italics for `UnderlineHandling::Emphasis`, or
+    /// drops them for `Ignore`.
+    fn apply_underline_handling(segments: &mut [FormattedSegment], handling: UnderlineHandling) {
+        if handling == UnderlineHandling::Html {
+            return;
+        }
+        for seg in segments.iter_mut().filter(|seg| seg.has_underline) {
+            seg.is_italic |= handling == UnderlineHandling::Emphasis;
+            seg.has_underline = false;
+        }
+    }
+
+    /// Inserts an unformatted `space` between segments where CJK and Latin
+    /// text touch, such as a bold Korean word followed by an English one.
+    fn space_cjk_latin_boundaries(
+        segments: Vec<FormattedSegment>,
+        space: &str,
+    ) -> Vec<FormattedSegment> {
+        let mut spaced: Vec<FormattedSegment> = Vec::with_capacity(segments.len());
+        let mut last_char = None;
+        for seg in segments {
+            if let (Some(before), Some(after)) = (last_char, seg.text.chars().next()) {
+                if crate::localization::is_cjk_latin_boundary(before, after) {
+                    spaced.push(FormattedSegment {
+                        text: space.to_string(),
+                        ..Default::default()
+                    });
+                }
+            }
+            if let Some(c) = seg.text.chars().last() {
+                last_char = Some(c);
+            }
+            spaced.push(seg);
+        }
+        spaced
+    }
+
     /// Merges adjacent segments with identical formatting.
     fn merge_segments(segments: Vec<FormattedSegment>) -> Vec<FormattedSegment> {
         let mut merged: Vec<FormattedSegment> = Vec::new();
@@ -411,9 +1264,12 @@ impl ParagraphConverter {
                     && last.is_italic == seg.is_italic
                     && last.has_underline == seg.has_underline
                     && last.has_strike == seg.has_strike
+                    && last.is_kbd == seg.is_kbd
                     && last.is_insertion == seg.is_insertion
                     && last.is_deletion == seg.is_deletion
+                    && last.revision == seg.revision
                     && last.anchor == seg.anchor
+                    && last.lang == seg.lang
                 {
                     // Merge text
                     last.text.push_str(&seg.text);
@@ -497,29 +1353,68 @@ impl ParagraphConverter {
         )
     }
 
+    /// Returns true when a bookmark is rendered as its own `<a id>` anchor,
+    /// i.e. HTML is allowed and the bookmark is not replaced by a heading slug.
+    /// Word's hidden `_GoBack` bookmark (the last edit position) never is.
+    fn emits_anchor(bookmark: &str, context: &ConversionContext<'_>) -> bool {
+        bookmark != "_GoBack" && !context.no_html() && context.bookmark_slug(bookmark).is_none()
+    }
+
     /// Converts segments to markdown text.
     fn segments_to_markdown(
         segments: &[FormattedSegment],
         context: &ConversionContext<'_>,
     ) -> String {
+        if context.plain_text() {
+            return segments.iter().map(|seg| seg.text.as_str()).collect();
+        }
         let mut result = String::new();
+        let mut open_lang: Option<&str> = None;
 
         for seg in segments {
+            // Adjacent segments sharing a language share one span
+            if seg.lang.as_deref() != open_lang {
+                if open_lang.is_some() {
+                    result.push_str("</span>");
+                }
+                if let Some(lang) = &seg.lang {
+                    result.push_str(&format!("<span lang=\"{}\">", escape_html_attr(lang)));
+                }
+                open_lang = seg.lang.as_deref();
+            }
+
             // Render anchor if present
-            if let Some(anchor) = &seg.anchor {
+            if let Some(anchor) = seg
+                .anchor
+                .as_ref()
+                .filter(|a| Self::emits_anchor(a, context))
+            {
                 result.push_str(&format!("<a id=\"{}\"></a>", anchor));
             }
 
             let mut text = seg.text.clone();
+            if seg.is_kbd {
+                text = format!("<kbd>{}</kbd>", text);
+            }
 
             // Apply track changes formatting first
             if seg.is_deletion {
-                // Deleted text: strikethrough
-                text = Self::apply_format_safely(&text, "~~", "~~");
+                // Deleted text: strikethrough, or a del tag to carry the revision metadata
+                text = match &seg.revision {
+                    Some(revision) if !context.no_html() => {
+                        format!("<del{}>{}</del>", revision.html_attrs(), text)
+                    }
+                    _ => Self::apply_format_safely(&text, "~~", "~~"),
+                };
             }
-            if seg.is_insertion {
+            if seg.is_insertion && !context.no_html() {
                 // Inserted text: HTML ins tag or underline
-                text = format!("<ins>{}</ins>", text);
+                let attrs = seg
+                    .revision
+                    .as_ref()
+                    .map(Revision::html_attrs)
+                    .unwrap_or_default();
+                text = format!("<ins{}>{}</ins>", attrs, text);
             }
 
             // Apply regular formatting
@@ -535,7 +1430,17 @@ impl ParagraphConverter {
                 }
             }
 
-            if seg.is_bold && seg.is_italic {
+            if context.no_html() {
+                let marker = match (seg.is_bold, seg.is_italic) {
+                    (true, true) => "***",
+                    (true, false) => "**",
+                    (false, true) => "*",
+                    (false, false) => "",
+                };
+                if !marker.is_empty() {
+                    text = Self::apply_format_safely(&text, marker, marker);
+                }
+            } else if seg.is_bold && seg.is_italic {
                 text = format!("<strong><em>{}</em></strong>", text);
             } else if seg.is_bold {
                 text = format!("<strong>{}</strong>", text);
@@ -546,9 +1451,34 @@ impl ParagraphConverter {
             result.push_str(&text);
         }
 
+        if open_lang.is_some() {
+            result.push_str("</span>");
+        }
+
         result
     }
 
+    /// Formats an ordered list item marker according to the list options.
+    ///
+    /// Plain decimal labels (`1.` / `1)`) always take the configured
+    /// delimiter; other numbered labels are rewritten only with
+    /// [`ListLabels::Markdown`].
+    fn list_item_marker(marker: ListMarker, context: &ConversionContext<'_>) -> String {
+        let Some(number) = marker.number else {
+            return marker.label;
+        };
+
+        let plain_decimal = marker
+            .label
+            .strip_suffix(['.', ')'])
+            .is_some_and(|digits| digits == number.to_string());
+        if plain_decimal || context.list_labels() == ListLabels::Markdown {
+            format!("{}{}", number, context.ordered_list_delimiter().as_char())
+        } else {
+            marker.label
+        }
+    }
+
     /// Applies paragraph-level formatting (heading, list, alignment).
     fn apply_paragraph_formatting<'a>(
         para: &Paragraph<'a>,
@@ -568,17 +1498,17 @@ impl ParagraphConverter {
         let mut prefix = String::new();
         let mut is_heading = false;
 
-        // Check for heading via pStyle
-        if let Some(style) = &effective_props.style_id {
-            if let Some(heading_level) = crate::localization::parse_heading_style(&style.value) {
-                // Don't generate heading for empty text
-                if text.trim().is_empty() {
-                    return Ok(String::new());
-                }
-                prefix.push_str(&"#".repeat(heading_level));
+        // Check for heading via pStyle or outline level
+        if let Some(heading_level) = context.heading_style_level(&effective_props) {
+            // Don't generate heading for empty text
+            if text.trim().is_empty() {
+                return Ok(String::new());
+            }
+            if !context.plain_text() {
+                prefix.push_str(&"#".repeat(context.heading_level(heading_level)));
                 prefix.push(' ');
-                is_heading = true;
             }
+            is_heading = true;
         }
 
         // Check for numbering (list items)
@@ -586,9 +1516,10 @@ impl ParagraphConverter {
             if let (Some(num_id), Some(ilvl)) = (&num_pr.id, &num_pr.level) {
                 let num_id_val = num_id.value as i32;
                 let ilvl_val = ilvl.value as i32;
-                let marker = context.next_list_marker(num_id_val, ilvl_val);
+                let marker = context.next_list_item(num_id_val, ilvl_val);
 
                 if is_heading {
+                    let marker = marker.label;
                     prefix.push_str(&marker);
                     if !marker.is_empty() {
                         prefix.push(' ');
@@ -596,22 +1527,27 @@ impl ParagraphConverter {
                 } else {
                     let indent = context.list_indent_level(num_id_val, ilvl_val);
                     let indent_str = "  ".repeat(indent);
+                    let number = marker.number;
                     prefix.push_str(&indent_str);
-                    prefix.push_str(&marker);
+                    prefix.push_str(&Self::list_item_marker(marker, context));
                     prefix.push(' ');
+                    context.set_list_marker(indent, number, prefix.clone());
                 }
             }
         }
 
-        let text_for_output = if context.preserve_whitespace() {
-            text.as_str()
-        } else {
-            text.trim()
-        };
-        let final_text = format!("{}{}", prefix, text_for_output);
+        if text.contains(PTABS) {
+            if is_heading || !prefix.is_empty() || context.no_html() {
+                let text = text.replace(PTABS, " ");
+                return Ok(format!("{}{}", prefix, Self::output_text(&text, context)));
+            }
+            return Ok(Self::positional_tab_columns(&text));
+        }
+
+        let final_text = format!("{}{}", prefix, Self::output_text(&text, context));
 
         // Check for text alignment (only if not heading)
-        if !is_heading {
+        if !is_heading && !context.no_html() {
             if let Some(jc) = &effective_props.justification {
                 match &jc.value {
                     rs_docx::formatting::JustificationVal::Center => {
@@ -634,28 +1570,73 @@ impl ParagraphConverter {
         Ok(final_text)
     }
 
+    /// Writes a paragraph split by positional tab placeholders as a one-row
+    /// table, each cell aligned like the tab before it.
+    fn positional_tab_columns(text: &str) -> String {
+        let mut html = String::from("<table><tr>");
+        for (ptab, cell) in split_positional_tabs(text) {
+            let align = match ptab {
+                Some(PTAB_CENTER) => " style=\"text-align: center;\"",
+                Some(PTAB_RIGHT) => " style=\"text-align: right;\"",
+                _ => "",
+            };
+            html.push_str(&format!("<td{}>{}</td>", align, cell.trim()));
+        }
+        html.push_str("</tr></table>");
+        html
+    }
+
     /// Converts a hyperlink to Markdown format.
     fn convert_hyperlink<'a>(
         hyperlink: &Hyperlink<'a>,
+        tooltip: Option<&str>,
         context: &mut ConversionContext<'a>,
         para_style_id: Option<&str>,
     ) -> Result<String> {
-        let mut link_text = String::new();
+        let mut segments = Vec::new();
         let mut field_stack = Vec::new();
 
         for run in &hyperlink.content {
-            let filtered_run = Self::filter_run_by_field_state(run, &mut field_stack);
+            let filtered_run = Self::filter_run_by_field_state(
+                run,
+                &mut field_stack,
+                &mut Vec::new(),
+                &mut Vec::new(),
+            );
             if filtered_run.content.is_empty() {
                 continue;
             }
 
-            let text = RunConverter::convert(&filtered_run, context, para_style_id)?;
-            link_text.push_str(&text);
+            let text = Self::extract_text(&filtered_run, context, para_style_id);
+            if !text.is_empty() {
+                segments.extend(Self::run_to_segment(
+                    &filtered_run,
+                    &text,
+                    context,
+                    para_style_id,
+                ));
+            }
+        }
+
+        // The link is the underline; the `Hyperlink` style underlines it in Word.
+        for seg in &mut segments {
+            seg.has_underline = false;
+        }
+        if let Some(space) = context.cjk_latin_space() {
+            segments = Self::space_cjk_latin_boundaries(segments, space);
+        }
+
+        // Word splits link text into several runs (e.g. around proofing
+        // marks); merge them so the formatting is emitted once.
+        let link_text = Self::segments_to_markdown(&Self::merge_segments(segments), context);
+        if context.plain_text() && !link_text.is_empty() {
+            return Ok(link_text);
         }
 
         // Get target URL from relationship or anchor
         let url = if let Some(anchor) = &hyperlink.anchor {
             // Internal bookmark link (used in TOC entries)
+            let anchor = context.bookmark_slug(anchor).unwrap_or(anchor);
             format!("#{}", escape_markdown_link_destination(anchor))
         } else if let Some(id) = &hyperlink.id {
             // External link via relationship
@@ -668,20 +1649,27 @@ impl ParagraphConverter {
         };
 
         if link_text.is_empty() {
-            Ok(url)
-        } else {
-            Ok(format!(
-                "[{}]({})",
-                escape_markdown_link_text(&link_text),
-                escape_markdown_link_destination(&url)
-            ))
+            return Ok(url);
         }
+        let title = tooltip
+            .map(escape_markdown_link_title)
+            .filter(|title| !title.is_empty())
+            .map(|title| format!(" \"{}\"", title))
+            .unwrap_or_default();
+        Ok(format!(
+            "[{}]({}{})",
+            escape_markdown_link_text(&link_text),
+            escape_markdown_link_destination(&url),
+            title
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::PositionalTabs;
+    use hard_xml::XmlRead;
     use rs_docx::document::{Hyperlink, ParagraphContent, Run, RunContent, Text};
     use std::borrow::Cow;
     use std::collections::HashMap;
@@ -921,6 +1909,189 @@ mod tests {
         assert_eq!(md, "  Keep Surrounding Spaces  ");
     }
 
+    #[test]
+    fn test_leading_tabs_never_indent_a_code_block() {
+        let mut run = Run::default();
+        run.content.push(RunContent::Tab(Default::default()));
+        run.content.push(RunContent::Tab(Default::default()));
+        run.content.push(RunContent::Text(Text {
+            text: "Indented\tfirst line".into(),
+            ..Default::default()
+        }));
+        let para = Paragraph::default().push(ParagraphContent::Run(run));
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |preserve_whitespace, leading_tab| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                preserve_whitespace,
+                leading_tab,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(convert(true, LeadingTab::Strip), "Indented\tfirst line");
+        assert_eq!(
+            convert(true, LeadingTab::Prefix("&emsp;".to_string())),
+            "&emsp;&emsp;Indented\tfirst line"
+        );
+        assert_eq!(
+            convert(false, LeadingTab::Prefix("&emsp;".to_string())),
+            "&emsp;&emsp;Indented\tfirst line"
+        );
+        assert!(LeadingTab::Prefix("    ".to_string()).validate().is_err());
+    }
+
+    #[test]
+    fn test_positional_tabs_separate_left_and_right_text() {
+        let para = Paragraph::from_str(
+            r#"<w:p><w:r><w:rPr><w:b/></w:rPr><w:t>ACME Corp.</w:t><w:ptab w:relativeTo="margin" w:alignment="right" w:leader="none"/><w:t>March 14, 2024</w:t></w:r></w:p>"#,
+        )
+        .expect("paragraph should parse");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |positional_tabs| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                positional_tabs,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(
+            convert(PositionalTabs::Separator(" ".to_string())),
+            "<strong>ACME Corp. March 14, 2024</strong>"
+        );
+        assert_eq!(
+            convert(PositionalTabs::Separator(" | ".to_string())),
+            "<strong>ACME Corp. | March 14, 2024</strong>"
+        );
+        assert_eq!(
+            convert(PositionalTabs::Columns),
+            "<table><tr><td><strong>ACME Corp.</strong></td>\
+             <td style=\"text-align: right;\"><strong>March 14, 2024</strong></td></tr></table>"
+        );
+    }
+
+    #[test]
+    fn test_lettered_list_labels_and_delimiter() {
+        use rs_docx::document::{
+            AbstractNum, AbstractNumId, Level, LevelStart, LevelText, Num, NumFmt, Numbering,
+        };
+
+        let level = |ilvl: isize, fmt: &'static str, text: &'static str| Level {
+            i_level: Some(ilvl),
+            start: Some(LevelStart { value: Some(1) }),
+            number_format: Some(NumFmt {
+                value: Cow::Borrowed(fmt),
+            }),
+            level_text: Some(LevelText {
+                value: Some(Cow::Borrowed(text)),
+            }),
+            ..Default::default()
+        };
+        let docx = rs_docx::Docx {
+            numbering: Some(Numbering {
+                abstract_numberings: vec![AbstractNum {
+                    abstract_num_id: Some(1),
+                    levels: vec![level(0, "decimal", "%1."), level(1, "lowerLetter", "%2)")],
+                    ..Default::default()
+                }],
+                numberings: vec![Num {
+                    num_id: Some(1),
+                    abstract_num_id: Some(AbstractNumId { value: Some(1) }),
+                    ..Default::default()
+                }],
+            }),
+            ..Default::default()
+        };
+        let item = |ilvl: isize, text: &str| {
+            let mut para = Paragraph {
+                property: Some(rs_docx::formatting::ParagraphProperty {
+                    numbering: Some(rs_docx::formatting::NumberingProperty::from((1isize, ilvl))),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let mut run = Run::default();
+            run.content.push(RunContent::Text(Text {
+                text: text.to_string().into(),
+                ..Default::default()
+            }));
+            para.content.push(ParagraphContent::Run(run));
+            para
+        };
+        let paragraphs = [item(0, "One"), item(1, "Alpha"), item(1, "Beta")];
+
+        let convert = |options: crate::ConvertOptions| {
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let style_resolver = super::super::StyleResolver::new(&docx.styles);
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            paragraphs
+                .iter()
+                .map(|p| ParagraphConverter::convert(p, &mut context).expect("Conversion failed"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            convert(crate::ConvertOptions::default()),
+            ["1. One", "  a) Alpha", "  b) Beta"]
+        );
+        assert_eq!(
+            convert(crate::ConvertOptions {
+                ordered_list_delimiter: crate::OrderedListDelimiter::Paren,
+                ..Default::default()
+            }),
+            ["1) One", "  a) Alpha", "  b) Beta"]
+        );
+        assert_eq!(
+            convert(crate::ConvertOptions {
+                list_labels: ListLabels::Markdown,
+                ..Default::default()
+            }),
+            ["1. One", "  1. Alpha", "  2. Beta"]
+        );
+    }
+
     #[test]
     fn test_deep_list_indentation_not_clamped() {
         use rs_docx::document::{
@@ -1158,23 +2329,99 @@ mod tests {
     }
 
     #[test]
-    fn test_extended_run_content_is_preserved() {
+    fn test_url_split_across_runs_stays_contiguous() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p w:rsidR="00C1" w:rsidRDefault="00C1">
+                <w:r w:rsidR="00A1"><w:rPr><w:u w:val="single"/></w:rPr><w:t>https://exam</w:t></w:r>
+                <w:bookmarkStart w:id="0" w:name="_GoBack"/><w:bookmarkEnd w:id="0"/>
+                <w:r w:rsidR="00B2"><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri"/><w:u w:val="single"/><w:lang w:val="en-GB"/></w:rPr><w:t>ple.com/do</w:t></w:r>
+                <w:proofErr w:type="spellStart"/>
+                <w:r w:rsidR="00B3" w:rsidRPr="00D4"><w:rPr><w:noProof/><w:u w:val="single"/></w:rPr><w:t>cs?a=1</w:t></w:r>
+                <w:proofErr w:type="spellEnd"/>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions {
+            underline_handling: Some(UnderlineHandling::Html),
+            ..Default::default()
+        };
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(md, "<u>https://example.com/docs?a=1</u>");
+    }
+
+    #[test]
+    fn test_proof_err_does_not_split_formatting() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p>
+                <w:r><w:rPr><w:b/></w:rPr><w:t>he</w:t></w:r>
+                <w:proofErr w:type="spellStart"/>
+                <w:r><w:rPr><w:b/></w:rPr><w:t>llo</w:t></w:r>
+                <w:proofErr w:type="spellEnd"/>
+                <w:r><w:t xml:space="preserve"> </w:t></w:r>
+                <w:hyperlink w:anchor="target">
+                    <w:r><w:rPr><w:b/></w:rPr><w:t>wor</w:t></w:r>
+                    <w:proofErr w:type="gramStart"/>
+                    <w:r><w:rPr><w:b/></w:rPr><w:t>ld</w:t></w:r>
+                    <w:proofErr w:type="gramEnd"/>
+                </w:hyperlink>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(
+            md,
+            "<strong>hello</strong> [<strong>world</strong>](#target)"
+        );
+    }
+
+    #[test]
+    fn test_column_break_separates_paragraphs_outside_formatting() {
         use hard_xml::XmlRead;
 
         let mut para = Paragraph::default();
         let run = Run::from_str(
-            r#"<w:r>
-                <w:t>A</w:t>
-                <w:noBreakHyphen/>
-                <w:t>B</w:t>
-                <w:softHyphen/>
-                <w:t>C</w:t>
-                <w:sym w:char="2013"/>
-                <w:ptab/>
-                <w:lastRenderedPageBreak/>
-                <w:pgNum/>
-                <w:t>D</w:t>
-            </w:r>"#,
+            r#"<w:r><w:rPr><w:b/></w:rPr><w:t>Left</w:t><w:br w:type="column"/><w:t>Right</w:t></w:r>"#,
         )
         .expect("Failed to parse run XML");
         para.content.push(ParagraphContent::Run(run));
@@ -1198,6 +2445,988 @@ mod tests {
         );
 
         let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
-        assert_eq!(md, "A\u{2011}B\u{00AD}C\u{2013}\t\n\n---\n\n{PAGE}D");
+        assert_eq!(md, "<strong>Left</strong>\n\n<strong>Right</strong>");
+    }
+
+    #[test]
+    fn test_carriage_return_emits_configured_line_break() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        let run = Run::from_str(
+            r#"<w:r><w:rPr><w:b/></w:rPr><w:t>First phrase</w:t><w:cr/><w:t>second phrase</w:t></w:r>"#,
+        )
+        .expect("Failed to parse run XML");
+        para.content.push(ParagraphContent::Run(run));
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        for (line_break, expected) in [
+            (
+                crate::LineBreak::Newline,
+                "<strong>First phrase</strong>\n<strong>second phrase</strong>",
+            ),
+            (
+                crate::LineBreak::Spaces,
+                "<strong>First phrase</strong>  \n<strong>second phrase</strong>",
+            ),
+            (
+                crate::LineBreak::Backslash,
+                "<strong>First phrase</strong>\\\n<strong>second phrase</strong>",
+            ),
+            (
+                crate::LineBreak::Html,
+                "<strong>First phrase</strong><br/>\n<strong>second phrase</strong>",
+            ),
+        ] {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                line_break,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+
+            let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+            assert_eq!(md, expected, "{:?}", line_break);
+        }
+    }
+
+    #[test]
+    fn test_tracked_changes_carry_author_and_date() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p><w:r><w:t xml:space="preserve">Total: </w:t></w:r><w:del w:id="1" w:author="Kim &amp; &quot;Lee&quot;" w:date="2024-05-01T09:30:00Z"><w:r><w:delText>10</w:delText></w:r></w:del><w:ins w:id="2" w:author="Kim &amp; &quot;Lee&quot;" w:date="2024-05-01T09:30:00Z"><w:r><w:t>12</w:t></w:r></w:ins><w:ins w:id="3"><w:r><w:t>!</w:t></w:r></w:ins></w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(
+            md,
+            "Total: <del data-author=\"Kim &amp; &quot;Lee&quot;\" data-date=\"2024-05-01T09:30:00Z\">10</del>\
+             <ins data-author=\"Kim &amp; &quot;Lee&quot;\" data-date=\"2024-05-01T09:30:00Z\">12</ins><ins>!</ins>"
+        );
+    }
+
+    #[test]
+    fn test_tracked_changes_can_be_accepted_or_rejected() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p><w:r><w:t xml:space="preserve">Total: </w:t></w:r><w:del w:id="1" w:author="Kim"><w:r><w:rPr><w:b/></w:rPr><w:delText>10</w:delText></w:r></w:del><w:ins w:id="2" w:author="Kim"><w:r><w:t>12</w:t></w:r></w:ins><w:r><w:t xml:space="preserve"> units</w:t></w:r></w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        for (tracked_changes, expected) in [
+            (crate::TrackedChanges::AcceptAll, "Total: 12 units"),
+            (
+                crate::TrackedChanges::RejectAll,
+                "Total: <strong>10</strong> units",
+            ),
+        ] {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                tracked_changes,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+
+            let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+            assert_eq!(md, expected, "{:?}", tracked_changes);
+        }
+    }
+
+    #[test]
+    fn test_heading_offset_shifts_and_clamps_levels() {
+        use hard_xml::XmlRead;
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        for (style, heading_offset, expected) in [
+            ("Heading5", 2, "###### Deep"),
+            ("Heading1", 2, "### Deep"),
+            ("Heading5", 0, "##### Deep"),
+            ("Heading9", 0, "###### Deep"),
+        ] {
+            let xml = format!(
+                r#"<w:p><w:pPr><w:pStyle w:val="{style}"/></w:pPr><w:r><w:t>Deep</w:t></w:r></w:p>"#
+            );
+            let para = Paragraph::from_str(&xml).expect("Failed to parse paragraph XML");
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                heading_offset,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+
+            let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+            assert_eq!(md, expected, "{style} with offset {heading_offset}");
+        }
+    }
+
+    #[test]
+    fn test_extended_run_content_is_preserved() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        let run = Run::from_str(
+            r#"<w:r>
+                <w:t>A</w:t>
+                <w:noBreakHyphen/>
+                <w:t>B</w:t>
+                <w:softHyphen/>
+                <w:t>C</w:t>
+                <w:sym w:char="2013"/>
+                <w:ptab/>
+                <w:lastRenderedPageBreak/>
+                <w:pgNum/>
+                <w:t>D</w:t>
+            </w:r>"#,
+        )
+        .expect("Failed to parse run XML");
+        para.content.push(ParagraphContent::Run(run));
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(md, "A\u{2011}B\u{00AD}C\u{2013} \n\n---\n\n{PAGE}D");
+    }
+
+    #[test]
+    fn test_preserve_lang_wraps_foreign_runs_in_single_span() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        for xml in [
+            r#"<w:r><w:t xml:space="preserve">Hello </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:lang w:val="fr-FR"/></w:rPr><w:t xml:space="preserve">bonjour </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:b/><w:lang w:val="fr-FR"/></w:rPr><w:t>tout</w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:lang w:val="fr-FR"/></w:rPr><w:t xml:space="preserve"> le monde</w:t></w:r>"#,
+        ] {
+            let run = Run::from_str(xml).expect("Failed to parse run XML");
+            para.content.push(ParagraphContent::Run(run));
+        }
+
+        let styles = rs_docx::styles::Styles::from_str(
+            r#"<w:styles><w:docDefaults><w:rPrDefault><w:rPr><w:lang w:val="en-US"/></w:rPr></w:rPrDefault></w:docDefaults></w:styles>"#,
+        )
+        .expect("Failed to parse styles XML");
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions {
+            preserve_lang: true,
+            ..Default::default()
+        };
+        let style_resolver = super::super::StyleResolver::new(&styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(
+            md,
+            "Hello <span lang=\"fr-FR\">bonjour <strong>tout</strong> le monde</span>"
+        );
+    }
+
+    #[test]
+    fn test_run_fonts_only_affect_symbol_fonts() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        for xml in [
+            r#"<w:r><w:rPr><w:rFonts w:ascii="Zapfino Extra LT" w:eastAsia="HY견명조" w:hAnsi="Zapfino Extra LT" w:asciiTheme="majorHAnsi" w:hAnsiTheme="majorHAnsi" w:cstheme="majorBidi"/></w:rPr><w:t xml:space="preserve">Plain text </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:rFonts w:ascii="Symbol" w:hAnsi="Symbol"/></w:rPr><w:t>a</w:t></w:r>"#,
+            r#"<w:r><w:sym w:font="Wingdings" w:char="F0FC"/></w:r>"#,
+        ] {
+            let run = Run::from_str(xml).expect("Failed to parse run XML");
+            para.content.push(ParagraphContent::Run(run));
+        }
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(md, "Plain text \u{03B1}\u{2714}");
+    }
+
+    #[test]
+    fn test_text_transform_uppercases_caps_and_small_caps_runs() {
+        use hard_xml::XmlRead;
+
+        let styles = rs_docx::styles::Styles::from_str(
+            r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <w:style w:type="character" w:styleId="Acronym"><w:name w:val="Acronym"/><w:rPr><w:smallCaps/></w:rPr></w:style>
+            </w:styles>"#,
+        )
+        .expect("styles should parse");
+        let mut para = Paragraph::default();
+        for xml in [
+            r#"<w:r><w:rPr><w:caps/></w:rPr><w:t xml:space="preserve">Chapter one: </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:rStyle w:val="Acronym"/></w:rPr><w:t xml:space="preserve">Nasa </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:rStyle w:val="Acronym"/><w:smallCaps w:val="0"/></w:rPr><w:t>and friends</w:t></w:r>"#,
+        ] {
+            let run = Run::from_str(xml).expect("Failed to parse run XML");
+            para.content.push(ParagraphContent::Run(run));
+        }
+
+        let convert = |text_transform| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                text_transform,
+                ..Default::default()
+            };
+            let style_resolver = super::super::StyleResolver::new(&styles);
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(
+            convert(crate::TextTransform::Preserve),
+            "Chapter one: Nasa and friends"
+        );
+        assert_eq!(
+            convert(crate::TextTransform::Apply),
+            "CHAPTER ONE: NASA and friends"
+        );
+    }
+
+    #[test]
+    fn test_drop_cap_letter_is_merged_into_following_paragraph() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:framePr w:dropCap="drop" w:lines="3" w:wrap="around" w:vAnchor="text" w:hAnchor="text"/></w:pPr><w:r><w:rPr><w:sz w:val="120"/></w:rPr><w:t>O</w:t></w:r></w:p>
+            <w:p><w:r><w:rPr><w:b/></w:rPr><w:t>nce</w:t></w:r><w:r><w:t xml:space="preserve"> upon a time</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document =
+            rs_docx::document::Document::from_str(xml).expect("Failed to parse document XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+        context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+            xml,
+            &document.body.content,
+        ));
+
+        let converted = document
+            .body
+            .content
+            .iter()
+            .map(|content| match content {
+                rs_docx::document::BodyContent::Paragraph(para) => {
+                    ParagraphConverter::convert(para, &mut context).expect("Conversion failed")
+                }
+                _ => panic!("expected paragraphs"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(converted, vec!["", "O<strong>nce</strong> upon a time"]);
+    }
+
+    #[test]
+    fn test_hidden_text_and_index_entries_follow_hidden_text_option() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        for xml in [
+            r#"<w:r><w:t xml:space="preserve">Paris </w:t></w:r>"#,
+            r#"<w:r><w:rPr><w:vanish/></w:rPr><w:t>(check date)</w:t></w:r>"#,
+            r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r>"#,
+            r#"<w:r><w:instrText xml:space="preserve"> XE "Paris:history" </w:instrText></w:r>"#,
+            r#"<w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+            r#"<w:r><w:t xml:space="preserve"> is old.</w:t></w:r>"#,
+        ] {
+            let run = Run::from_str(xml).expect("Failed to parse run XML");
+            para.content.push(ParagraphContent::Run(run));
+        }
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |hidden_text| {
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                hidden_text,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(convert(crate::HiddenText::Drop), "Paris  is old.");
+        assert_eq!(
+            convert(crate::HiddenText::Include),
+            "Paris (check date)Paris:history is old."
+        );
+        assert_eq!(
+            convert(crate::HiddenText::AsComment),
+            "Paris <!-- (check date) --><!-- Paris:history --> is old."
+        );
+    }
+
+    #[test]
+    fn test_hidden_text_comments_split_runs_of_hyphens() {
+        use hard_xml::XmlRead;
+
+        let mut para = Paragraph::default();
+        let run = Run::from_str(r#"<w:r><w:rPr><w:vanish/></w:rPr><w:t>a---b --</w:t></w:r>"#)
+            .expect("Failed to parse run XML");
+        para.content.push(ParagraphContent::Run(run));
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions {
+            hidden_text: crate::HiddenText::AsComment,
+            ..Default::default()
+        };
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+        let result = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+
+        // No `--` is left in the comment, and the trailing hyphen is kept
+        // apart from the `-->` that closes it.
+        assert_eq!(result, "<!-- a- - -b - - -->");
+    }
+
+    #[test]
+    fn test_equations_follow_math_fallback_option() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math"><w:body>
+            <w:p><w:r><w:t xml:space="preserve">Area is </w:t></w:r><m:oMath><m:r><m:t>πr</m:t></m:r><m:sSup><m:e/><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSup></m:oMath><w:r><w:t>.</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document =
+            rs_docx::document::Document::from_str(xml).expect("Failed to parse document XML");
+        let rs_docx::document::BodyContent::Paragraph(para) = &document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |math_fallback| {
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                math_fallback,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+            ParagraphConverter::convert(para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(convert(crate::MathFallback::RawText), "Area is πr2.");
+        assert_eq!(
+            convert(crate::MathFallback::Placeholder),
+            "Area is [equation]."
+        );
+        // Without a preview image the image fallback degrades to raw text.
+        assert_eq!(convert(crate::MathFallback::Image), "Area is πr2.");
+    }
+
+    #[test]
+    fn test_display_equations_are_on_their_own_line() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math"><w:body>
+            <w:p><w:r><w:t>Since</w:t></w:r><m:oMathPara><m:oMath><m:r><m:t>x=1</m:t></m:r></m:oMath></m:oMathPara><m:oMathPara><m:oMath><m:r><m:t>y=2</m:t></m:r></m:oMath></m:oMathPara><w:r><w:t xml:space="preserve">so </w:t></w:r><m:oMath><m:r><m:t>x&lt;y</m:t></m:r></m:oMath><w:r><w:t>.</w:t></w:r></w:p>
+            <w:p><m:oMathPara><m:oMath><m:r><m:t>z=3</m:t></m:r></m:oMath></m:oMathPara></w:p>
+        </w:body></w:document>"#;
+        let document =
+            rs_docx::document::Document::from_str(xml).expect("Failed to parse document XML");
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let rels = HashMap::new();
+        let convert = |index: usize, line_break| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                line_break,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+            let rs_docx::document::BodyContent::Paragraph(para) = &document.body.content[index]
+            else {
+                panic!("expected a paragraph");
+            };
+            ParagraphConverter::convert(para, &mut context).expect("Conversion failed")
+        };
+
+        // Inline equations stay in the text's line. The default soft line
+        // break would join the lines, so a backslash break is used instead.
+        let default_line_break = crate::ConvertOptions::default().line_break;
+        assert_eq!(
+            convert(0, default_line_break),
+            "Since\\\nx=1\\\ny=2\\\nso x<y."
+        );
+        assert_eq!(
+            convert(0, crate::LineBreak::Html),
+            "Since<br/>\nx=1<br/>\ny=2<br/>\nso x<y."
+        );
+        // A paragraph holding only a display equation gets no breaks.
+        assert_eq!(convert(1, default_line_break), "z=3");
+    }
+
+    #[test]
+    fn test_equation_alt_text_is_emitted_when_not_converted() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:v="urn:schemas-microsoft-com:vml"><w:body>
+            <w:p><w:r><w:t xml:space="preserve">Let </w:t></w:r><mc:AlternateContent>
+                <mc:Choice Requires="m"><m:oMath><m:r><m:t>x&lt;1</m:t></m:r></m:oMath></mc:Choice>
+                <mc:Fallback><w:r><w:pict><v:shape alt="x less than 1"><v:imagedata r:id="rId7"/></v:shape></w:pict></w:r></mc:Fallback>
+            </mc:AlternateContent></w:p>
+        </w:body></w:document>"#;
+        let document =
+            rs_docx::document::Document::from_str(xml).expect("Failed to parse document XML");
+        let rs_docx::document::BodyContent::Paragraph(para) = &document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+
+        let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        archive
+            .start_file(
+                "word/media/image1.png",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        std::io::Write::write_all(&mut archive, b"png").unwrap();
+        let bytes = archive.finish().unwrap().into_inner();
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |math_fallback, no_html| {
+            let rels = HashMap::from([("rId7".to_string(), "media/image1.png".to_string())]);
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor =
+                super::super::ImageExtractor::new_inline_from_bytes(&bytes).unwrap();
+            if no_html {
+                image_extractor = image_extractor.without_html();
+            }
+            let options = crate::ConvertOptions {
+                math_fallback,
+                no_html,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+            ParagraphConverter::convert(para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(
+            convert(crate::MathFallback::Placeholder, false),
+            "Let [equation: x less than 1]"
+        );
+        assert_eq!(
+            convert(crate::MathFallback::Image, true),
+            "Let ![x less than 1](data:image/png;base64,cG5n)"
+        );
+        assert_eq!(
+            convert(crate::MathFallback::Image, false),
+            "Let <img src=\"data:image/png;base64,cG5n\" alt=\"x less than 1\" />"
+        );
+        assert_eq!(convert(crate::MathFallback::RawText, false), "Let x<1");
+    }
+
+    #[test]
+    fn test_localization_spaces_cjk_and_latin_text() {
+        use hard_xml::XmlRead;
+
+        #[derive(Debug)]
+        struct ThinSpaces;
+        impl crate::LocalizationStrategy for ThinSpaces {
+            fn cjk_latin_space(&self) -> Option<&str> {
+                Some("\u{2009}")
+            }
+        }
+
+        let para = Paragraph::from_str(
+            r#"<w:p>
+                <w:r><w:rPr><w:b/></w:rPr><w:t>서울</w:t></w:r>
+                <w:r><w:t xml:space="preserve">Station은 2km, 東京タワーは333m</w:t></w:r>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |options: crate::ConvertOptions| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(
+            convert(crate::ConvertOptions::default()),
+            "<strong>서울</strong>Station은 2km, 東京タワーは333m"
+        );
+        assert_eq!(
+            convert(crate::ConvertOptions {
+                localization: Some(std::sync::Arc::new(ThinSpaces)),
+                ..Default::default()
+            }),
+            "<strong>서울</strong>\u{2009}Station\u{2009}은 2km, 東京タワーは333m"
+        );
+    }
+
+    #[test]
+    fn test_underlines_follow_underline_handling() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p>
+                <w:r><w:rPr><w:u w:val="single"/></w:rPr><w:t>Read</w:t></w:r>
+                <w:r><w:rPr><w:u w:val="none"/></w:rPr><w:t xml:space="preserve"> the </w:t></w:r>
+                <w:hyperlink w:anchor="terms"><w:r><w:rPr><w:u w:val="single"/></w:rPr><w:t>terms</w:t></w:r></w:hyperlink>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        #[allow(deprecated)]
+        let convert = |underline_handling, html_underline, no_html| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                underline_handling,
+                html_underline,
+                no_html,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        assert_eq!(
+            convert(Some(UnderlineHandling::Html), false, false),
+            "<u>Read</u> the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(Some(UnderlineHandling::Ignore), false, false),
+            "Read the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(Some(UnderlineHandling::Emphasis), false, false),
+            "<em>Read</em> the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(Some(UnderlineHandling::Emphasis), false, true),
+            "*Read* the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(Some(UnderlineHandling::Html), false, true),
+            "Read the [terms](#terms)"
+        );
+        // Underlines are ignored by default; the deprecated flag only
+        // applies while `underline_handling` is unset.
+        #[allow(deprecated)]
+        let default_html_underline = crate::ConvertOptions::default().html_underline;
+        assert_eq!(
+            convert(None, default_html_underline, false),
+            "Read the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(None, true, false),
+            "<u>Read</u> the [terms](#terms)"
+        );
+        assert_eq!(
+            convert(Some(UnderlineHandling::Emphasis), true, false),
+            "<em>Read</em> the [terms](#terms)"
+        );
+    }
+
+    #[test]
+    fn test_no_proof_runs_merge_with_neighbours() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p>
+                <w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">Call </w:t></w:r>
+                <w:r><w:rPr><w:i/><w:noProof/></w:rPr><w:t>getUserId</w:t></w:r>
+                <w:proofErr w:type="gramStart"/>
+                <w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve"> first</w:t></w:r>
+                <w:proofErr w:type="gramEnd"/>
+                <w:r><w:rPr><w:noProof/></w:rPr><w:t xml:space="preserve">, then </w:t></w:r>
+                <w:r><w:t>retry.</w:t></w:r>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(md, "<em>Call getUserId first</em>, then retry.");
+    }
+
+    #[test]
+    fn test_extended_syntax_is_escaped_per_flavor() {
+        use hard_xml::XmlRead;
+
+        let para = rs_docx::document::Paragraph::from_str(
+            r#"<w:p xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <w:r><w:t xml:space="preserve">x^2 ~ 3 and a == b </w:t></w:r>
+                <w:r><w:rPr><w:strike/></w:rPr><w:t>old</w:t></w:r>
+            </w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |options: crate::ConvertOptions| {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            ParagraphConverter::convert(&para, &mut context).expect("Conversion failed")
+        };
+
+        let commonmark = crate::ConvertOptions {
+            html_strikethrough: false,
+            ..Default::default()
+        };
+        assert_eq!(convert(commonmark), "x^2 ~ 3 and a == b ~~old~~");
+        assert_eq!(
+            convert(crate::ConvertOptions::github()),
+            "x^2 \\~ 3 and a == b ~~old~~"
+        );
+        assert_eq!(
+            convert(crate::ConvertOptions::pandoc()),
+            "x\\^2 \\~ 3 and a \\=\\= b ~~old~~"
+        );
+    }
+
+    #[test]
+    fn test_chart_renders_cached_data_table() {
+        use hard_xml::XmlRead;
+
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>
+            <w:p><w:r><w:t>Revenue:</w:t></w:r><w:r><w:drawing><wp:inline><wp:docPr id="1" name="Chart 1"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart r:id="rId4"/></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+        </w:body></w:document>"#;
+        let chart = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart><c:plotArea><c:lineChart><c:ser>
+            <c:tx><c:v>Revenue</c:v></c:tx>
+            <c:cat><c:strRef><c:strCache><c:pt idx="0"><c:v>2024</c:v></c:pt></c:strCache></c:strRef></c:cat>
+            <c:val><c:numRef><c:numCache><c:pt idx="0"><c:v>12</c:v></c:pt></c:numCache></c:numRef></c:val>
+        </c:ser></c:lineChart></c:plotArea></c:chart></c:chartSpace>"#;
+        let document =
+            rs_docx::document::Document::from_str(xml).expect("Failed to parse document XML");
+        let rs_docx::document::BodyContent::Paragraph(para) = &document.body.content[0] else {
+            panic!("expected a paragraph");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let convert = |chart_handling, chart_parts: &[(&str, &str)]| {
+            let rels = HashMap::from([("rId4".to_string(), "charts/chart1.xml".to_string())]);
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = crate::ConvertOptions {
+                chart_handling,
+                ..Default::default()
+            };
+            let mut context = super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+            context.set_chart_parts(
+                chart_parts
+                    .iter()
+                    .map(|(path, xml)| (path.to_string(), xml.to_string()))
+                    .collect(),
+            );
+            ParagraphConverter::convert(para, &mut context).expect("Conversion failed")
+        };
+
+        let table = "Revenue:\n\n<table>\n  <tr>\n    <td>&nbsp;</td>\n    <td>Revenue</td>\n  </tr>\n  <tr>\n    <td>2024</td>\n    <td>12</td>\n  </tr>\n</table>";
+        assert_eq!(
+            convert(
+                crate::ChartHandling::Table,
+                &[("word/charts/chart1.xml", chart)]
+            ),
+            table
+        );
+        // Without a preview image the image mode degrades to the data table.
+        assert_eq!(
+            convert(
+                crate::ChartHandling::Image,
+                &[("word/charts/chart1.xml", chart)]
+            ),
+            table
+        );
+        assert_eq!(convert(crate::ChartHandling::Table, &[]), "Revenue:[chart]");
+    }
+
+    #[test]
+    fn test_no_html_uses_markdown_formatting_only() {
+        use hard_xml::XmlRead;
+
+        let para = Paragraph::from_str(
+            r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:bookmarkStart w:id="0" w:name="_Toc1"/><w:r><w:rPr><w:b/></w:rPr><w:t>Total</w:t></w:r><w:r><w:t xml:space="preserve"> a&lt;b </w:t></w:r><w:r><w:rPr><w:u w:val="single"/></w:rPr><w:t>under</w:t></w:r><w:r><w:rPr><w:b/><w:i/></w:rPr><w:t xml:space="preserve"> both </w:t></w:r><w:del w:id="1" w:author="Kim"><w:r><w:delText>10</w:delText></w:r></w:del><w:ins w:id="2" w:author="Kim"><w:r><w:t>12</w:t></w:r></w:ins></w:p>"#,
+        )
+        .expect("Failed to parse paragraph XML");
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = crate::ConvertOptions {
+            no_html: true,
+            ..Default::default()
+        };
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        let mut context = super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let md = ParagraphConverter::convert(&para, &mut context).expect("Conversion failed");
+        assert_eq!(md, "**Total** a\\<b under ***both*** ~~10~~12");
     }
 }
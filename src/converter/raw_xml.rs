@@ -0,0 +1,1669 @@
+//! Side scanner for `word/document.xml` markup that rs_docx does not model.
+//!
+//! rs_docx silently skips elements it has no type for, so properties such as
+//! table captions never reach the parsed tree. This pass tokenizes the raw part,
+//! records those properties per element, and binds them back to the parsed
+//! elements by document order.
+
+use crate::{Error, Result};
+use rs_docx::document::{
+    BodyContent, Paragraph, ParagraphContent, Table, TableCell, TableCellContent, TableRowContent,
+    SDT,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek};
+use std::ops::Range;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// Element names whose presence makes a raw scan worthwhile.
+const INTERESTING_TAGS: &[&str] = &[
+    "w:tblCaption",
+    "w:tblDescription",
+    "w:tblLook",
+    "w:bidiVisual",
+    "w:framePr",
+    "m:oMath",
+    "c:chart",
+    "w:num=",
+    ":svgBlip",
+    "w:object",
+    "v:group",
+    "w:ffData",
+    "w:ruby",
+    "w:tooltip",
+    "w:tblCellMar",
+    "w:tcMar",
+    "w:smartTag",
+    "w:placeholder",
+];
+
+/// Returns whether `xml` holds any element that rs_docx drops and the raw
+/// scan recovers.
+pub(crate) fn has_interesting_tags(xml: &str) -> bool {
+    INTERESTING_TAGS.iter().any(|tag| xml.contains(tag))
+}
+
+/// Office Math objects: the structures of an equation beyond its runs.
+const MATH_OBJECTS: &[&str] = &[
+    "acc",
+    "bar",
+    "borderBox",
+    "box",
+    "d",
+    "eqArr",
+    "f",
+    "func",
+    "groupChr",
+    "limLow",
+    "limUpp",
+    "m",
+    "nary",
+    "phant",
+    "rad",
+    "sPre",
+    "sSub",
+    "sSubSup",
+    "sSup",
+];
+
+/// `w:tblLook w:val` bit for first column emphasis.
+const TBL_LOOK_FIRST_COLUMN: u16 = 0x0080;
+
+/// Table properties that rs_docx drops while parsing `w:tblPr`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawTableProperties {
+    pub caption: Option<String>,
+    pub description: Option<String>,
+    /// The first column is emphasized (`w:tblLook`), making its cells row headers.
+    pub first_column: bool,
+    /// The table is right-to-left (`w:bidiVisual`): its first column is
+    /// displayed on the right.
+    pub bidi_visual: bool,
+    /// Default margins of the table's cells (`w:tblCellMar`).
+    pub cell_margins: RawCellMargins,
+}
+
+impl RawTableProperties {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Cell margins in twips (`w:tblCellMar`/`w:tcMar`), per side. `w:start` and
+/// `w:end` are recorded as left and right; `w:type="nil"` is a zero margin,
+/// and percentage margins are ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RawCellMargins {
+    pub top: Option<u32>,
+    pub left: Option<u32>,
+    pub bottom: Option<u32>,
+    pub right: Option<u32>,
+}
+
+impl RawCellMargins {
+    /// Returns these margins with the sides they leave unset taken from
+    /// `defaults`.
+    pub fn or(self, defaults: Self) -> Self {
+        Self {
+            top: self.top.or(defaults.top),
+            left: self.left.or(defaults.left),
+            bottom: self.bottom.or(defaults.bottom),
+            right: self.right.or(defaults.right),
+        }
+    }
+
+    fn set(&mut self, side: &str, attribute: &str, value: &str) {
+        let side = match side {
+            "top" => &mut self.top,
+            "left" | "start" => &mut self.left,
+            "bottom" => &mut self.bottom,
+            "right" | "end" => &mut self.right,
+            _ => return,
+        };
+        match (attribute, value) {
+            ("w", _) => *side = value.parse().ok().or(*side),
+            ("type", "nil") => *side = Some(0),
+            ("type", "pct") => *side = None,
+            _ => {}
+        }
+    }
+}
+
+/// Table cell properties that rs_docx drops while parsing `w:tcPr`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawCellProperties {
+    /// The cell's own margins (`w:tcMar`).
+    pub margins: RawCellMargins,
+}
+
+impl RawCellProperties {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Paragraph properties that rs_docx drops while parsing `w:pPr`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawParagraphProperties {
+    /// The paragraph is a drop-cap frame (`w:framePr w:dropCap="drop|margin"`).
+    pub drop_cap: bool,
+    /// The paragraph has frame properties (`w:framePr`), drop caps included.
+    pub framed: bool,
+    /// Equations (`m:oMath`/`m:oMathPara`) placed directly in the paragraph.
+    pub equations: Vec<RawEquation>,
+    /// Charts (`c:chart`) drawn in the paragraph's runs.
+    pub charts: Vec<RawChart>,
+    /// VML images (`v:imagedata`) in the paragraph's runs that rs_docx drops:
+    /// previews of embedded objects (`w:object`) and shapes in a `v:group`.
+    pub vml_images: Vec<RawVmlImage>,
+    /// Legacy form fields (`w:fldChar/w:ffData`) in the paragraph's runs.
+    pub form_fields: Vec<RawFormField>,
+    /// Phonetic guides (`w:ruby`) in the paragraph's runs.
+    pub rubies: Vec<RawRuby>,
+    /// Smart tags (`w:smartTag`) placed directly in the paragraph.
+    pub smart_tags: Vec<RawSmartTag>,
+    /// Tooltips (`w:hyperlink w:tooltip`) with the index of their hyperlink
+    /// among the paragraph's `ParagraphContent` items.
+    pub link_tooltips: Vec<(usize, String)>,
+    /// Column count of the section this paragraph ends (`w:pPr/w:sectPr`).
+    pub section_columns: Option<u32>,
+}
+
+/// Office Math equation that rs_docx drops from a paragraph.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawEquation {
+    /// Number of parsed `ParagraphContent` items preceding the equation.
+    pub position: usize,
+    /// Whether this is a display equation (`m:oMathPara`).
+    pub display: bool,
+    /// Concatenated `m:t` text of the equation.
+    pub text: String,
+    /// Relationship id of the preview image in an `mc:Fallback` branch.
+    pub fallback_image: Option<String>,
+    /// Accessible description of that preview image (`wp:docPr descr`, or
+    /// `alt` on a VML shape).
+    pub alt: Option<String>,
+    /// Math objects in the equation (e.g. `m:f`), in document order without
+    /// repeats; its text flattens them.
+    pub constructs: Vec<String>,
+}
+
+/// Chart reference whose relationship id rs_docx drops from `a:graphicData`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawChart {
+    /// Number of parsed `ParagraphContent` items up to and including the
+    /// one holding the chart.
+    pub position: usize,
+    /// Relationship id of the chart part.
+    pub rel_id: String,
+    /// Relationship id of the preview image in an `mc:Fallback` branch.
+    pub fallback_image: Option<String>,
+}
+
+/// VML image whose `w:object` or `v:group` container rs_docx drops.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawVmlImage {
+    /// Number of parsed `ParagraphContent` items up to and including the
+    /// one holding the image.
+    pub position: usize,
+    /// Relationship id of the image part (`r:id`).
+    pub rel_id: String,
+    /// Alternative text of the enclosing shape (`v:shape alt`).
+    pub alt: Option<String>,
+    /// Whether the image previews an equation object, such as a MathType or
+    /// Equation Editor 3.0 equation (`o:OLEObject ProgID="Equation.*"`).
+    pub equation: bool,
+}
+
+/// State of a legacy form field, which rs_docx drops from `w:fldChar`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawFormField {
+    /// Whether a check box (`w:checkBox`) is ticked: `w:checked`, else
+    /// `w:default`. `None` for other fields.
+    pub checked: Option<bool>,
+    /// Entries of a drop-down list (`w:ddList/w:listEntry`).
+    pub entries: Vec<String>,
+    /// Index of the selected entry: `w:result`, else `w:default`.
+    pub selected: Option<usize>,
+}
+
+/// Phonetic guide (`w:ruby`), which rs_docx drops with its base text.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawRuby {
+    /// Number of parsed `ParagraphContent` items up to and including the
+    /// one holding the ruby.
+    pub position: usize,
+    /// Text of `w:rubyBase`.
+    pub base: String,
+    /// Text of `w:rt`, e.g. furigana or pinyin.
+    pub annotation: String,
+}
+
+/// Smart tag (`w:smartTag`), which rs_docx drops with the runs it wraps.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawSmartTag {
+    /// Number of parsed `ParagraphContent` items preceding the smart tag; 0
+    /// for a nested one.
+    pub position: usize,
+    /// Type of the recognized text (`w:element`), e.g. `date` or `place`.
+    pub element: String,
+    /// Runs and nested smart tags, in document order.
+    pub content: Vec<RawSmartTagContent>,
+}
+
+/// Item of a smart tag's content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RawSmartTagContent {
+    /// Byte range of a `w:r` element in the document XML.
+    Run(Range<usize>),
+    Tag(RawSmartTag),
+}
+
+impl RawFormField {
+    /// Returns the selected entry of a drop-down list.
+    pub fn selected_entry(&self) -> Option<&str> {
+        self.entries
+            .get(self.selected.unwrap_or(0))
+            .map(String::as_str)
+    }
+}
+
+impl RawParagraphProperties {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Content control properties that rs_docx drops while parsing `w:sdtPr`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RawSdtProperties {
+    /// Name of the building block holding the control's placeholder content
+    /// (`w:placeholder/w:docPart`), in the glossary document.
+    pub doc_part: Option<String>,
+}
+
+impl RawSdtProperties {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Raw properties keyed by the address of the parsed element they belong to.
+#[derive(Debug, Default)]
+pub(crate) struct RawDocumentIndex {
+    tables: HashMap<usize, RawTableProperties>,
+    cells: HashMap<usize, RawCellProperties>,
+    paragraphs: HashMap<usize, RawParagraphProperties>,
+    sdts: HashMap<usize, RawSdtProperties>,
+    /// Column count of the last section (`w:body/w:sectPr`).
+    final_section_columns: Option<u32>,
+    /// SVG relationship id of each raster image that has an `asvg:svgBlip`.
+    svg_blips: HashMap<String, String>,
+}
+
+impl RawDocumentIndex {
+    /// Scans `xml` and binds what it finds to the elements of the parsed `body`.
+    ///
+    /// Each element kind is left unbound when the raw and parsed trees disagree
+    /// on how many such elements exist, so properties are never attached to the
+    /// wrong element.
+    pub fn build(xml: &str, body: &[BodyContent<'_>]) -> Self {
+        if !has_interesting_tags(xml) {
+            return Self::default();
+        }
+        let Some(raw) = scan(xml) else {
+            return Self::default();
+        };
+
+        let mut parsed = ParsedElements::default();
+        parsed.collect_body(body);
+
+        Self {
+            tables: bind(parsed.tables, raw.tables, RawTableProperties::is_empty),
+            cells: bind(parsed.cells, raw.cells, RawCellProperties::is_empty),
+            paragraphs: bind(
+                parsed.paragraphs,
+                raw.paragraphs,
+                RawParagraphProperties::is_empty,
+            ),
+            sdts: bind(parsed.sdts, raw.sdts, RawSdtProperties::is_empty),
+            final_section_columns: raw.final_section_columns,
+            svg_blips: raw.svg_blips,
+        }
+    }
+
+    /// Returns the raw properties recorded for a parsed table.
+    pub fn table(&self, table: &Table<'_>) -> Option<&RawTableProperties> {
+        self.tables.get(&element_key(table))
+    }
+
+    /// Returns the raw properties recorded for a parsed table cell.
+    pub fn cell(&self, cell: &TableCell<'_>) -> Option<&RawCellProperties> {
+        self.cells.get(&element_key(cell))
+    }
+
+    /// Returns the raw properties recorded for a parsed paragraph.
+    pub fn paragraph(&self, para: &Paragraph<'_>) -> Option<&RawParagraphProperties> {
+        self.paragraphs.get(&element_key(para))
+    }
+
+    /// Returns the raw properties recorded for a parsed content control.
+    pub fn sdt(&self, sdt: &SDT<'_>) -> Option<&RawSdtProperties> {
+        self.sdts.get(&element_key(sdt))
+    }
+
+    /// Returns the column count of the last section of the body.
+    pub fn final_section_columns(&self) -> Option<u32> {
+        self.final_section_columns
+    }
+
+    /// Returns the SVG relationship ids keyed by the raster image they replace.
+    pub fn svg_blips(&self) -> &HashMap<String, String> {
+        &self.svg_blips
+    }
+}
+
+/// Raw parts of a DOCX archive read alongside the rs_docx parse.
+#[derive(Debug, Default)]
+pub(crate) struct RawParts {
+    /// The main document part (`word/document.xml`).
+    pub document: String,
+    /// Chart parts (`word/charts/*.xml`) keyed by archive path.
+    pub charts: HashMap<String, String>,
+    /// Ids of the comments marked resolved in `word/commentsExtended.xml`.
+    pub resolved_comments: HashSet<String>,
+    /// Building blocks of `word/glossary/document.xml` keyed by name, read
+    /// when content controls refer to them.
+    pub glossary: HashMap<String, String>,
+}
+
+/// Reads the main document part, the chart parts and building blocks it may
+/// reference and the status of its comments from a DOCX archive.
+pub(crate) fn read_raw_parts<R: Read + Seek>(reader: R) -> Result<RawParts> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let document = read_document(&mut archive)?;
+    read_raw_parts_from(&mut archive, document)
+}
+
+/// Reads the main document part (`word/document.xml`) of a DOCX archive.
+pub(crate) fn read_document<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Result<String> {
+    let mut document = String::new();
+    archive
+        .by_name("word/document.xml")?
+        .read_to_string(&mut document)?;
+    Ok(document)
+}
+
+/// Like [`read_raw_parts`], with the main document part already read.
+pub(crate) fn read_raw_parts_from<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    document: String,
+) -> Result<RawParts> {
+    let mut parts = RawParts {
+        document,
+        ..Default::default()
+    };
+
+    if parts.document.contains("c:chart") {
+        let chart_names: Vec<String> = archive
+            .file_names()
+            .filter(|name| {
+                name.strip_prefix("word/charts/")
+                    .is_some_and(|file| !file.contains('/') && file.ends_with(".xml"))
+            })
+            .map(str::to_owned)
+            .collect();
+        for name in chart_names {
+            let mut xml = String::new();
+            archive.by_name(&name)?.read_to_string(&mut xml)?;
+            parts.charts.insert(name, xml);
+        }
+    }
+
+    if parts.document.contains("w:placeholder") {
+        let mut glossary = String::new();
+        if archive
+            .by_name(super::glossary::GLOSSARY_DOCUMENT)
+            .and_then(|mut part| Ok(part.read_to_string(&mut glossary)?))
+            .is_ok()
+        {
+            parts.glossary = super::glossary::building_blocks(&glossary);
+        }
+    }
+
+    let mut comments = String::new();
+    let mut extended = String::new();
+    if archive
+        .by_name("word/comments.xml")
+        .and_then(|mut part| Ok(part.read_to_string(&mut comments)?))
+        .is_ok()
+        && archive
+            .by_name("word/commentsExtended.xml")
+            .and_then(|mut part| Ok(part.read_to_string(&mut extended)?))
+            .is_ok()
+    {
+        parts.resolved_comments = super::comments::resolved_comment_ids(&comments, &extended);
+    }
+    Ok(parts)
+}
+
+/// Reads the custom XML data parts (`customXml/item*.xml`) in archive order.
+///
+/// Their `itemProps*.xml` property parts and relationships are skipped. Parts
+/// written as UTF-16 (with a byte order mark) are decoded to UTF-8.
+pub(crate) fn read_custom_xml<R: Read + Seek>(reader: R) -> Result<Vec<(String, String)>> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let names: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            name.strip_prefix("customXml/").is_some_and(|file| {
+                !file.contains('/')
+                    && file.starts_with("item")
+                    && !file.starts_with("itemProps")
+                    && file.ends_with(".xml")
+            })
+        })
+        .map(str::to_owned)
+        .collect();
+
+    let mut parts = Vec::with_capacity(names.len());
+    for name in names {
+        let mut bytes = Vec::new();
+        archive.by_name(&name)?.read_to_end(&mut bytes)?;
+        let xml = decode_xml(&bytes)
+            .ok_or_else(|| Error::DocxParse(format!("{} is not valid UTF-8 or UTF-16", name)))?;
+        parts.push((name, xml));
+    }
+    Ok(parts)
+}
+
+fn decode_xml(bytes: &[u8]) -> Option<String> {
+    let utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).ok()
+    };
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8(bytes.to_vec()).ok(),
+    }
+}
+
+pub(super) fn element_key<T>(element: &T) -> usize {
+    element as *const T as usize
+}
+
+fn bind<T, P>(parsed: Vec<&T>, raw: Vec<P>, is_empty: fn(&P) -> bool) -> HashMap<usize, P> {
+    if parsed.len() != raw.len() {
+        return HashMap::new();
+    }
+    parsed
+        .into_iter()
+        .zip(raw)
+        .filter(|(_, props)| !is_empty(props))
+        .map(|(element, props)| (element_key(element), props))
+        .collect()
+}
+
+/// Returns true when rs_docx parses `child` as content of `parent`, i.e. when
+/// an element at this position also exists in the parsed tree.
+fn is_modelled_child(parent: &str, child: &str) -> bool {
+    matches!(
+        (parent, child),
+        ("document", "body")
+            | ("body" | "sdtContent", "p" | "tbl" | "sdt" | "tc")
+            | ("sdt", "sdtContent")
+            | ("p", "sdt")
+            | ("tbl", "tr")
+            | ("tr", "tc" | "sdt")
+            | ("tc", "p" | "tbl")
+    )
+}
+
+/// Returns true for `w:p` children that rs_docx keeps as `ParagraphContent`.
+fn is_paragraph_content(name: &str) -> bool {
+    matches!(
+        name,
+        "commentRangeStart"
+            | "commentRangeEnd"
+            | "r"
+            | "hyperlink"
+            | "bookmarkStart"
+            | "bookmarkEnd"
+            | "sdt"
+            | "ins"
+            | "del"
+    )
+}
+
+/// Parsed element a raw frame contributes properties to.
+#[derive(Debug, Clone, Copy)]
+enum Owner {
+    Table(usize),
+    Cell(usize),
+    Paragraph(usize),
+    Sdt(usize),
+    Body,
+}
+
+struct Frame<'x> {
+    prefix: &'x str,
+    name: &'x str,
+    modelled: bool,
+    /// Owner of a modelled `w:tbl`/`w:tc`/`w:p`, or of their properties.
+    owner: Option<Owner>,
+    /// Number of `ParagraphContent` children seen so far (modelled `w:p` only).
+    children: usize,
+}
+
+impl Frame<'_> {
+    fn is(&self, prefix: &str, name: &str) -> bool {
+        self.prefix == prefix && self.name == name
+    }
+}
+
+#[derive(Default)]
+struct RawScan {
+    tables: Vec<RawTableProperties>,
+    cells: Vec<RawCellProperties>,
+    paragraphs: Vec<RawParagraphProperties>,
+    sdts: Vec<RawSdtProperties>,
+    final_section_columns: Option<u32>,
+    svg_blips: HashMap<String, String>,
+}
+
+/// Equation being collected, owned by a paragraph.
+struct MathCapture {
+    paragraph: usize,
+    /// Stack depth of the `m:oMath`/`m:oMathPara` element.
+    depth: usize,
+    /// Stack depth of the enclosing `mc:AlternateContent`, if any.
+    alternate_depth: Option<usize>,
+    equation: RawEquation,
+}
+
+/// Element recorded from an `mc:Choice` branch.
+#[derive(Clone, Copy)]
+enum AlternateTarget {
+    Equation(usize),
+    Chart(usize),
+}
+
+/// `mc:AlternateContent` whose `mc:Choice` held an equation or a chart.
+struct Alternate {
+    depth: usize,
+    paragraph: usize,
+    target: AlternateTarget,
+    /// Stack depth of the `mc:Fallback` being scanned for a preview image.
+    fallback_depth: Option<usize>,
+}
+
+#[derive(Default)]
+struct Scanner<'x> {
+    raw: RawScan,
+    stack: Vec<Frame<'x>>,
+    pending: Option<Frame<'x>>,
+    math: Option<MathCapture>,
+    alternate: Option<Alternate>,
+    /// Chart whose `c:chart` element is still receiving attributes.
+    chart: Option<(usize, usize)>,
+    /// Stack depth and raster relationship id of the open `a:blip`.
+    blip: Option<(usize, String)>,
+    /// VML image whose `v:imagedata` element is still receiving attributes.
+    vml_image: Option<(usize, usize)>,
+    /// Stack depth and alternative text of the open `v:shape`.
+    shape_alt: Option<(usize, String)>,
+    /// Stack depth of the open `w:object`, and the paragraph and index of its
+    /// preview image.
+    object: Option<(usize, usize, usize)>,
+    /// Stack depth, paragraph and index of the open `w:ffData`.
+    form_field: Option<(usize, usize, usize)>,
+    /// Stack depth, paragraph and index of the open `w:ruby`.
+    ruby: Option<(usize, usize, usize)>,
+    /// Open smart tags, innermost last, with their stack depth and paragraph.
+    smart_tags: Vec<(usize, usize, RawSmartTag)>,
+    /// Stack depth and start offset of the open `w:r` in a smart tag.
+    smart_tag_run: Option<(usize, usize)>,
+}
+
+/// Collects raw properties in document order, or `None` if the XML cannot be
+/// tokenized.
+fn scan(xml: &str) -> Option<RawScan> {
+    let mut scanner = Scanner::default();
+
+    for token in Tokenizer::from(xml) {
+        match token.ok()? {
+            Token::ElementStart {
+                prefix,
+                local,
+                span,
+            } => {
+                scanner.start_element(prefix.as_str(), local.as_str(), span.start());
+            }
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => scanner.attribute(prefix.as_str(), local.as_str(), value.as_str()),
+            Token::Text { text } => scanner.text(text.as_str()),
+            Token::ElementEnd { end, span } => scanner.end_element(end, span.end()),
+            _ => {}
+        }
+    }
+
+    Some(scanner.raw)
+}
+
+impl<'x> Scanner<'x> {
+    fn start_element(&mut self, prefix: &'x str, name: &'x str, offset: usize) {
+        let depth = self.stack.len();
+        let is_w = prefix == "w";
+        if let Some(parent) = self.stack.last_mut() {
+            if is_w && parent.modelled && parent.is("w", "p") && is_paragraph_content(name) {
+                parent.children += 1;
+            }
+        }
+
+        let parent = self.stack.last();
+        let modelled = is_w
+            && match parent {
+                None => name == "document",
+                Some(parent) => parent.modelled && is_modelled_child(parent.name, name),
+            };
+        let owner = match (parent, is_w, name) {
+            (_, true, "tbl") if modelled => {
+                self.raw.tables.push(RawTableProperties::default());
+                Some(Owner::Table(self.raw.tables.len() - 1))
+            }
+            (_, true, "tc") if modelled => {
+                self.raw.cells.push(RawCellProperties::default());
+                Some(Owner::Cell(self.raw.cells.len() - 1))
+            }
+            (_, true, "p") if modelled => {
+                self.raw.paragraphs.push(RawParagraphProperties::default());
+                Some(Owner::Paragraph(self.raw.paragraphs.len() - 1))
+            }
+            (_, true, "sdt") if modelled => {
+                self.raw.sdts.push(RawSdtProperties::default());
+                Some(Owner::Sdt(self.raw.sdts.len() - 1))
+            }
+            (Some(parent), true, "tblPr" | "tcPr" | "pPr" | "sdtPr") if parent.modelled => {
+                parent.owner
+            }
+            (Some(parent), true, "placeholder") if parent.is("w", "sdtPr") => parent.owner,
+            (Some(parent), true, "tblCellMar") if parent.is("w", "tblPr") => parent.owner,
+            (Some(parent), true, "tcMar") if parent.is("w", "tcPr") => parent.owner,
+            (Some(parent), true, "sectPr") if parent.modelled && parent.is("w", "body") => {
+                self.raw.final_section_columns = Some(1);
+                Some(Owner::Body)
+            }
+            (Some(parent), true, "sectPr") if parent.is("w", "pPr") => {
+                if let Some(Owner::Paragraph(idx)) = parent.owner {
+                    self.raw.paragraphs[idx].section_columns = Some(1);
+                }
+                parent.owner
+            }
+            _ => None,
+        };
+        // `w:bidiVisual` is usually empty; a `w:val` attribute may turn it off.
+        if is_w && name == "bidiVisual" {
+            if let Some(Owner::Table(idx)) = parent
+                .filter(|parent| parent.is("w", "tblPr"))
+                .and_then(|parent| parent.owner)
+            {
+                self.raw.tables[idx].bidi_visual = true;
+            }
+        }
+
+        if prefix == "m" && matches!(name, "oMath" | "oMathPara") && self.math.is_none() {
+            self.start_math(depth, name == "oMathPara");
+        }
+        if let Some(math) = &mut self.math {
+            let construct = format!("m:{}", name);
+            if prefix == "m"
+                && MATH_OBJECTS.contains(&name)
+                && !math.equation.constructs.contains(&construct)
+            {
+                math.equation.constructs.push(construct);
+            }
+        }
+        if prefix == "c" && name == "chart" {
+            self.start_chart();
+        }
+        if prefix == "v" && name == "imagedata" {
+            self.start_vml_image();
+        }
+        if is_w && name == "ruby" && self.ruby.is_none() {
+            self.start_ruby(depth);
+        }
+        if is_w && name == "smartTag" {
+            self.start_smart_tag(depth);
+        } else if is_w && name == "r" && self.in_smart_tag(depth) {
+            self.smart_tag_run = Some((depth, offset));
+        }
+        if is_w && name == "ffData" {
+            self.start_form_field(depth);
+        } else if is_w {
+            self.form_field_element(name);
+        }
+        if let Some(alternate) = &mut self.alternate {
+            if prefix == "mc" && name == "Fallback" && depth == alternate.depth + 1 {
+                alternate.fallback_depth = Some(depth);
+            }
+        }
+
+        self.pending = Some(Frame {
+            prefix,
+            name,
+            modelled,
+            owner,
+            children: 0,
+        });
+    }
+
+    /// Starts collecting an equation placed directly in a paragraph, possibly
+    /// wrapped in `mc:AlternateContent`/`mc:Choice`.
+    fn start_math(&mut self, depth: usize, display: bool) {
+        let mut alternate_depth = None;
+        let mut owner = None;
+        for (idx, frame) in self.stack.iter().enumerate().rev() {
+            if frame.is("mc", "Choice") {
+                continue;
+            }
+            if frame.is("mc", "AlternateContent") {
+                alternate_depth = Some(idx);
+                continue;
+            }
+            if frame.modelled && frame.is("w", "p") {
+                owner = frame.owner.map(|owner| (owner, frame.children));
+            }
+            break;
+        }
+        let Some((Owner::Paragraph(paragraph), position)) = owner else {
+            return;
+        };
+        self.math = Some(MathCapture {
+            paragraph,
+            depth,
+            alternate_depth,
+            equation: RawEquation {
+                position,
+                display,
+                ..Default::default()
+            },
+        });
+    }
+
+    /// Records a chart drawn in a run of a parsed paragraph, possibly wrapped
+    /// in `mc:AlternateContent`/`mc:Choice`.
+    fn start_chart(&mut self) {
+        if self
+            .alternate
+            .as_ref()
+            .is_some_and(|alternate| alternate.fallback_depth.is_some())
+        {
+            // The `mc:Choice` branch already provided the content.
+            return;
+        }
+        let Some(paragraph) = self.stack.iter().rev().find(|frame| frame.is("w", "p")) else {
+            return;
+        };
+        let (true, Some(Owner::Paragraph(idx))) = (paragraph.modelled, paragraph.owner) else {
+            return;
+        };
+        let position = paragraph.children;
+        let charts = &mut self.raw.paragraphs[idx].charts;
+        charts.push(RawChart {
+            position,
+            ..Default::default()
+        });
+        let chart = charts.len() - 1;
+        self.chart = Some((idx, chart));
+
+        let choice = self
+            .stack
+            .iter()
+            .rposition(|frame| frame.is("mc", "Choice"));
+        if let Some(choice) = choice.filter(|&choice| choice > 0) {
+            if self.stack[choice - 1].is("mc", "AlternateContent") {
+                self.alternate = Some(Alternate {
+                    depth: choice - 1,
+                    paragraph: idx,
+                    target: AlternateTarget::Chart(chart),
+                    fallback_depth: None,
+                });
+            }
+        }
+    }
+
+    /// Records a VML image in a run of a parsed paragraph, unless rs_docx
+    /// parses it itself (`w:pict/v:shape/v:imagedata`, `w:pict/v:rect/...`).
+    fn start_vml_image(&mut self) {
+        let [.., pict, shape] = self.stack.as_slice() else {
+            return;
+        };
+        if pict.is("w", "pict") && (shape.is("v", "shape") || shape.is("v", "rect")) {
+            return;
+        }
+        // The alternate content is dropped as a whole; its choice may be a
+        // DrawingML copy of the same image.
+        if self
+            .stack
+            .iter()
+            .any(|frame| frame.is("mc", "AlternateContent"))
+        {
+            return;
+        }
+        let Some(paragraph) = self.stack.iter().rev().find(|frame| frame.is("w", "p")) else {
+            return;
+        };
+        let (true, Some(Owner::Paragraph(idx))) = (paragraph.modelled, paragraph.owner) else {
+            return;
+        };
+        let position = paragraph.children;
+        let object = self.stack.iter().rposition(|frame| frame.is("w", "object"));
+        let images = &mut self.raw.paragraphs[idx].vml_images;
+        images.push(RawVmlImage {
+            position,
+            alt: self.shape_alt.as_ref().map(|(_, alt)| alt.clone()),
+            ..Default::default()
+        });
+        let image = images.len() - 1;
+        self.vml_image = Some((idx, image));
+        if let Some(object) = object {
+            self.object.get_or_insert((object, idx, image));
+        }
+    }
+
+    /// Records the form field data of a field in a run of a parsed paragraph.
+    fn start_form_field(&mut self, depth: usize) {
+        let Some(paragraph) = self.stack.iter().rev().find(|frame| frame.is("w", "p")) else {
+            return;
+        };
+        let (true, Some(Owner::Paragraph(idx))) = (paragraph.modelled, paragraph.owner) else {
+            return;
+        };
+        let fields = &mut self.raw.paragraphs[idx].form_fields;
+        fields.push(RawFormField::default());
+        self.form_field = Some((depth, idx, fields.len() - 1));
+    }
+
+    /// Records a phonetic guide in a run of a parsed paragraph.
+    fn start_ruby(&mut self, depth: usize) {
+        let Some(paragraph) = self.stack.iter().rev().find(|frame| frame.is("w", "p")) else {
+            return;
+        };
+        let (true, Some(Owner::Paragraph(idx))) = (paragraph.modelled, paragraph.owner) else {
+            return;
+        };
+        let position = paragraph.children;
+        let rubies = &mut self.raw.paragraphs[idx].rubies;
+        rubies.push(RawRuby {
+            position,
+            ..Default::default()
+        });
+        self.ruby = Some((depth, idx, rubies.len() - 1));
+    }
+
+    /// Starts recording a smart tag placed directly in a parsed paragraph,
+    /// or in a smart tag being recorded.
+    fn start_smart_tag(&mut self, depth: usize) {
+        let paragraph = match (self.smart_tags.last(), self.stack.last()) {
+            (Some(&(tag, paragraph, _)), _) if tag + 1 == depth => Some((paragraph, 0)),
+            (_, Some(parent)) if parent.modelled && parent.is("w", "p") => match parent.owner {
+                Some(Owner::Paragraph(idx)) => Some((idx, parent.children)),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some((paragraph, position)) = paragraph {
+            let tag = RawSmartTag {
+                position,
+                ..Default::default()
+            };
+            self.smart_tags.push((depth, paragraph, tag));
+        }
+    }
+
+    /// Returns whether an element at `depth` is a child of the smart tag
+    /// being recorded.
+    fn in_smart_tag(&self, depth: usize) -> bool {
+        self.smart_tags
+            .last()
+            .is_some_and(|&(tag, _, _)| tag + 1 == depth)
+    }
+
+    fn finish_smart_tag(&mut self) {
+        let Some((_, paragraph, tag)) = self.smart_tags.pop() else {
+            return;
+        };
+        match self.smart_tags.last_mut() {
+            Some((_, _, parent)) => parent.content.push(RawSmartTagContent::Tag(tag)),
+            None => self.raw.paragraphs[paragraph].smart_tags.push(tag),
+        }
+    }
+
+    fn form_field_element(&mut self, name: &str) {
+        let (Some((_, paragraph, field)), Some(parent)) = (self.form_field, self.stack.last())
+        else {
+            return;
+        };
+        let field = &mut self.raw.paragraphs[paragraph].form_fields[field];
+        match (parent.name, name) {
+            ("ffData", "checkBox") => field.checked = Some(false),
+            // `w:val` defaults to true.
+            ("checkBox", "checked") => field.checked = Some(true),
+            ("ddList", "listEntry") => field.entries.push(String::new()),
+            _ => {}
+        }
+    }
+
+    fn form_field_attribute(&mut self, name: &str, value: &str) {
+        let (Some((_, paragraph, field)), Some(element), Some(parent)) =
+            (self.form_field, &self.pending, self.stack.last())
+        else {
+            return;
+        };
+        if name != "val" {
+            return;
+        }
+        let field = &mut self.raw.paragraphs[paragraph].form_fields[field];
+        let on = !matches!(value, "0" | "false" | "off");
+        match (parent.name, element.name) {
+            ("checkBox", "default" | "checked") => field.checked = Some(on),
+            ("ddList", "result") => field.selected = value.parse().ok(),
+            ("ddList", "default") => {
+                field.selected = field.selected.or_else(|| value.parse().ok());
+            }
+            ("ddList", "listEntry") => {
+                if let Some(entry) = field.entries.last_mut() {
+                    *entry = unescape_xml(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn attribute(&mut self, prefix: &str, name: &str, value: &str) {
+        if prefix == "r" && name == "embed" {
+            self.blip_attribute(value);
+        }
+        if let Some((paragraph, image)) = self.vml_image {
+            if prefix == "r" && name == "id" {
+                self.raw.paragraphs[paragraph].vml_images[image].rel_id = unescape_xml(value);
+            }
+            return;
+        }
+        if let Some(element) = &self.pending {
+            if element.is("w", "smartTag") && prefix == "w" && name == "element" {
+                if let Some((depth, _, tag)) = self.smart_tags.last_mut() {
+                    if *depth == self.stack.len() {
+                        tag.element = unescape_xml(value);
+                    }
+                }
+            } else if element.is("v", "shape") && prefix.is_empty() && name == "alt" {
+                if !value.trim().is_empty() {
+                    self.shape_alt = Some((self.stack.len(), unescape_xml(value)));
+                }
+            } else if element.is("o", "OLEObject") && prefix.is_empty() && name == "ProgID" {
+                if let Some((_, paragraph, image)) = self.object {
+                    self.raw.paragraphs[paragraph].vml_images[image].equation =
+                        value.starts_with("Equation.");
+                }
+            }
+        }
+        if prefix == "r" && matches!(name, "id" | "embed") {
+            if let Some((paragraph, chart)) = self.chart {
+                let chart = &mut self.raw.paragraphs[paragraph].charts[chart];
+                if name == "id" && chart.rel_id.is_empty() {
+                    chart.rel_id = unescape_xml(value);
+                }
+                return;
+            }
+            if let Some(alternate) = &self.alternate {
+                if alternate.fallback_depth.is_some() {
+                    let paragraph = &mut self.raw.paragraphs[alternate.paragraph];
+                    let fallback_image = match alternate.target {
+                        AlternateTarget::Equation(idx) => {
+                            &mut paragraph.equations[idx].fallback_image
+                        }
+                        AlternateTarget::Chart(idx) => &mut paragraph.charts[idx].fallback_image,
+                    };
+                    fallback_image.get_or_insert_with(|| unescape_xml(value));
+                }
+            }
+            return;
+        }
+        if let Some(alternate) = &self.alternate {
+            if let (AlternateTarget::Equation(idx), Some(_), Some(element)) =
+                (alternate.target, alternate.fallback_depth, &self.pending)
+            {
+                let described = (element.is("wp", "docPr") && name == "descr")
+                    || (element.prefix == "v" && name == "alt");
+                if prefix.is_empty() && described && !value.trim().is_empty() {
+                    self.raw.paragraphs[alternate.paragraph].equations[idx]
+                        .alt
+                        .get_or_insert_with(|| unescape_xml(value));
+                }
+            }
+        }
+        if prefix != "w" {
+            return;
+        }
+        if self.form_field.is_some() {
+            self.form_field_attribute(name, value);
+            return;
+        }
+        let (Some(element), Some(parent)) = (&self.pending, self.stack.last()) else {
+            return;
+        };
+        match (parent.name, parent.owner, element.name, name) {
+            ("tblPr", Some(Owner::Table(idx)), "tblCaption", "val") => {
+                self.raw.tables[idx].caption = Some(unescape_xml(value));
+            }
+            ("tblPr", Some(Owner::Table(idx)), "tblDescription", "val") => {
+                self.raw.tables[idx].description = Some(unescape_xml(value));
+            }
+            ("tblPr", Some(Owner::Table(idx)), "tblLook", "val") => {
+                // Legacy bitmask; the explicit attribute below follows it and wins.
+                let look = u16::from_str_radix(value, 16).unwrap_or(0);
+                self.raw.tables[idx].first_column = look & TBL_LOOK_FIRST_COLUMN != 0;
+            }
+            ("tblPr", Some(Owner::Table(idx)), "tblLook", "firstColumn") => {
+                self.raw.tables[idx].first_column = matches!(value, "1" | "true" | "on");
+            }
+            ("tblPr", Some(Owner::Table(idx)), "bidiVisual", "val") => {
+                self.raw.tables[idx].bidi_visual = !matches!(value, "0" | "false" | "off");
+            }
+            ("tblCellMar", Some(Owner::Table(idx)), side, attribute) => {
+                self.raw.tables[idx]
+                    .cell_margins
+                    .set(side, attribute, value);
+            }
+            ("tcMar", Some(Owner::Cell(idx)), side, attribute) => {
+                self.raw.cells[idx].margins.set(side, attribute, value);
+            }
+            ("p", Some(Owner::Paragraph(idx)), "hyperlink", "tooltip") => {
+                let position = parent.children - 1;
+                self.raw.paragraphs[idx]
+                    .link_tooltips
+                    .push((position, unescape_xml(value)));
+            }
+            ("placeholder", Some(Owner::Sdt(idx)), "docPart", "val") => {
+                self.raw.sdts[idx].doc_part = Some(unescape_xml(value));
+            }
+            ("pPr", Some(Owner::Paragraph(idx)), "framePr", attribute) => {
+                self.raw.paragraphs[idx].framed = true;
+                if attribute == "dropCap" {
+                    self.raw.paragraphs[idx].drop_cap = matches!(value, "drop" | "margin");
+                }
+            }
+            ("sectPr", Some(Owner::Paragraph(idx)), "cols", "num") => {
+                self.raw.paragraphs[idx].section_columns = Some(value.parse().unwrap_or(1));
+            }
+            ("sectPr", Some(Owner::Body), "cols", "num") => {
+                self.raw.final_section_columns = Some(value.parse().unwrap_or(1));
+            }
+            _ => {}
+        }
+    }
+
+    /// Pairs an `a:blip` with the `asvg:svgBlip` in its extension list.
+    fn blip_attribute(&mut self, value: &str) {
+        let Some(element) = &self.pending else {
+            return;
+        };
+        if element.is("a", "blip") {
+            self.blip = Some((self.stack.len(), unescape_xml(value)));
+        } else if element.name == "svgBlip" {
+            if let Some((_, raster)) = &self.blip {
+                self.raw
+                    .svg_blips
+                    .insert(raster.clone(), unescape_xml(value));
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let (Some(math), Some(frame)) = (&mut self.math, self.stack.last()) {
+            if frame.is("m", "t") {
+                math.equation.text.push_str(&unescape_xml(text));
+            }
+        }
+        if let (Some((depth, paragraph, ruby)), Some(frame)) = (self.ruby, self.stack.last()) {
+            if !frame.is("w", "t") {
+                return;
+            }
+            let ruby = &mut self.raw.paragraphs[paragraph].rubies[ruby];
+            match self.stack.get(depth + 1).map(|part| part.name) {
+                Some("rubyBase") => ruby.base.push_str(&unescape_xml(text)),
+                Some("rt") => ruby.annotation.push_str(&unescape_xml(text)),
+                _ => {}
+            }
+        }
+    }
+
+    fn end_element(&mut self, end: ElementEnd<'_>, offset: usize) {
+        self.chart = None;
+        self.vml_image = None;
+        match end {
+            ElementEnd::Open => {
+                self.stack.extend(self.pending.take());
+                return;
+            }
+            ElementEnd::Empty => self.pending = None,
+            ElementEnd::Close(..) => {
+                self.stack.pop();
+            }
+        }
+
+        let depth = self.stack.len();
+        if self.blip.as_ref().is_some_and(|(blip, _)| *blip == depth) {
+            self.blip = None;
+        }
+        if self.form_field.is_some_and(|(field, _, _)| field == depth) {
+            self.form_field = None;
+        }
+        if self.ruby.is_some_and(|(ruby, _, _)| ruby == depth) {
+            self.ruby = None;
+        }
+        if let Some((run, start)) = self.smart_tag_run {
+            if run == depth {
+                self.smart_tag_run = None;
+                if let Some((_, _, tag)) = self.smart_tags.last_mut() {
+                    tag.content.push(RawSmartTagContent::Run(start..offset));
+                }
+            }
+        }
+        if self
+            .smart_tags
+            .last()
+            .is_some_and(|&(tag, _, _)| tag == depth)
+        {
+            self.finish_smart_tag();
+        }
+        if self
+            .shape_alt
+            .as_ref()
+            .is_some_and(|(shape, _)| *shape == depth)
+        {
+            self.shape_alt = None;
+        }
+        if self.object.is_some_and(|(object, _, _)| object == depth) {
+            self.object = None;
+        }
+        if self.math.as_ref().is_some_and(|math| math.depth == depth) {
+            self.finish_math();
+        }
+        if let Some(alternate) = &mut self.alternate {
+            if alternate.fallback_depth == Some(depth) {
+                alternate.fallback_depth = None;
+            }
+            if alternate.depth == depth {
+                self.alternate = None;
+            }
+        }
+    }
+
+    fn finish_math(&mut self) {
+        let Some(math) = self.math.take() else {
+            return;
+        };
+        let equations = &mut self.raw.paragraphs[math.paragraph].equations;
+        equations.push(math.equation);
+        if let Some(depth) = math.alternate_depth {
+            self.alternate = Some(Alternate {
+                depth,
+                paragraph: math.paragraph,
+                target: AlternateTarget::Equation(equations.len() - 1),
+                fallback_depth: None,
+            });
+        }
+    }
+}
+
+/// Parsed elements in the same document order as the raw scan.
+#[derive(Default)]
+struct ParsedElements<'t, 'a> {
+    tables: Vec<&'t Table<'a>>,
+    cells: Vec<&'t TableCell<'a>>,
+    paragraphs: Vec<&'t Paragraph<'a>>,
+    sdts: Vec<&'t SDT<'a>>,
+}
+
+impl<'t, 'a> ParsedElements<'t, 'a> {
+    fn collect_body(&mut self, content: &'t [BodyContent<'a>]) {
+        for item in content {
+            match item {
+                BodyContent::Paragraph(para) => self.collect_paragraph(para),
+                BodyContent::Table(table) => self.collect_table(table),
+                BodyContent::Sdt(sdt) => self.collect_sdt(sdt),
+                BodyContent::TableCell(cell) => self.collect_cell(cell),
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_sdt(&mut self, sdt: &'t SDT<'a>) {
+        self.sdts.push(sdt);
+        if let Some(sdt_content) = &sdt.content {
+            self.collect_body(&sdt_content.content);
+        }
+    }
+
+    fn collect_paragraph(&mut self, para: &'t Paragraph<'a>) {
+        self.paragraphs.push(para);
+        for child in &para.content {
+            if let ParagraphContent::SDT(sdt) = child {
+                self.collect_sdt(sdt);
+            }
+        }
+    }
+
+    fn collect_table(&mut self, table: &'t Table<'a>) {
+        self.tables.push(table);
+        for row in &table.rows {
+            for cell in &row.cells {
+                match cell {
+                    TableRowContent::TableCell(cell) => self.collect_cell(cell),
+                    TableRowContent::SDT(sdt) => self.collect_sdt(sdt),
+                }
+            }
+        }
+    }
+
+    fn collect_cell(&mut self, cell: &'t TableCell<'a>) {
+        self.cells.push(cell);
+        for item in &cell.content {
+            match item {
+                TableCellContent::Paragraph(para) => self.collect_paragraph(para),
+                TableCellContent::Table(table) => self.collect_table(table),
+            }
+        }
+    }
+}
+
+/// Resolves the predefined and numeric character references in an attribute value.
+pub(super) fn unescape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end + 1))
+        });
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hard_xml::XmlRead;
+    use rs_docx::document::Document;
+
+    const DOCUMENT: &str = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:wps="http://schemas.microsoft.com/office/word/2010/wordprocessingShape"><w:body>
+        <w:tbl>
+            <w:tblPr><w:tblCaption w:val="Sales &amp; costs"/><w:tblDescription w:val="Quarterly &#x2018;totals&#x2019;"/></w:tblPr><w:tblGrid/>
+            <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p>
+                <w:tbl><w:tblPr><w:tblCaption w:val="Inner"/></w:tblPr><w:tblGrid/><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>
+            </w:tc></w:tr>
+        </w:tbl>
+        <w:p><w:r><w:drawing><wps:txbx><w:txbxContent>
+            <w:tbl><w:tblPr><w:tblCaption w:val="Text box"/></w:tblPr><w:tblGrid/><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>
+        </w:txbxContent></wps:txbx></w:drawing></w:r></w:p>
+        <w:sdt><w:sdtContent>
+            <w:tbl><w:tblPr><w:tblDescription w:val="In SDT"/></w:tblPr><w:tblGrid/><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>
+        </w:sdtContent></w:sdt>
+        <w:p><w:pPr><w:framePr w:dropCap="drop" w:lines="3" w:wrap="around"/></w:pPr><w:r><w:t>O</w:t></w:r></w:p>
+        <w:p><w:r><w:t>nce upon a time</w:t></w:r></w:p>
+    </w:body></w:document>"#;
+
+    fn parsed<'t, 'a>(body: &'t [BodyContent<'a>]) -> ParsedElements<'t, 'a> {
+        let mut parsed = ParsedElements::default();
+        parsed.collect_body(body);
+        parsed
+    }
+
+    #[test]
+    fn test_build_binds_captions_to_parsed_tables_in_document_order() {
+        let document = Document::from_str(DOCUMENT).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(DOCUMENT, body);
+        let tables = parsed(body).tables;
+        assert_eq!(
+            tables.len(),
+            3,
+            "text box table is not part of the parsed tree"
+        );
+
+        assert_eq!(
+            index.table(tables[0]),
+            Some(&RawTableProperties {
+                caption: Some("Sales & costs".to_string()),
+                description: Some("Quarterly \u{2018}totals\u{2019}".to_string()),
+                first_column: false,
+                bidi_visual: false,
+                cell_margins: RawCellMargins::default(),
+            })
+        );
+        assert_eq!(
+            index.table(tables[1]).and_then(|t| t.caption.as_deref()),
+            Some("Inner")
+        );
+        assert_eq!(
+            index
+                .table(tables[2])
+                .and_then(|t| t.description.as_deref()),
+            Some("In SDT")
+        );
+    }
+
+    #[test]
+    fn test_build_marks_drop_cap_paragraphs() {
+        let document = Document::from_str(DOCUMENT).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(DOCUMENT, body);
+        let paragraphs = parsed(body).paragraphs;
+        assert_eq!(
+            paragraphs.len(),
+            6,
+            "text box paragraph is not part of the parsed tree"
+        );
+
+        assert!(index
+            .paragraph(paragraphs[4])
+            .is_some_and(|p| p.drop_cap && p.framed));
+        assert!(index.paragraph(paragraphs[5]).is_none());
+    }
+
+    #[test]
+    fn test_build_records_equations_with_position_and_fallback_image() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:v="urn:schemas-microsoft-com:vml"><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Let </w:t></w:r>
+                <m:oMath><m:r><m:t>x</m:t></m:r></m:oMath>
+                <w:r><w:t xml:space="preserve"> be </w:t></w:r>
+                <mc:AlternateContent>
+                    <mc:Choice Requires="m"><m:oMathPara><m:oMath><m:sSup><m:e><m:r><m:t>x</m:t></m:r></m:e><m:sup><m:f><m:num><m:r><m:t>1</m:t></m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f></m:sup></m:sSup><m:r><m:t>&lt;</m:t></m:r><m:f><m:num><m:r><m:t>1</m:t></m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f></m:oMath></m:oMathPara></mc:Choice>
+                    <mc:Fallback><w:r><w:pict><v:shape alt="root x less than a half"><v:imagedata r:id="rId7"/></v:shape></w:pict></w:r></mc:Fallback>
+                </mc:AlternateContent>
+            </w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let equations = &index.paragraph(paragraphs[0]).expect("equations").equations;
+        assert_eq!(
+            equations,
+            &vec![
+                RawEquation {
+                    position: 1,
+                    display: false,
+                    text: "x".to_string(),
+                    fallback_image: None,
+                    alt: None,
+                    constructs: Vec::new(),
+                },
+                RawEquation {
+                    position: 2,
+                    display: true,
+                    text: "x12<12".to_string(),
+                    fallback_image: Some("rId7".to_string()),
+                    alt: Some("root x less than a half".to_string()),
+                    constructs: vec!["m:sSup".to_string(), "m:f".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_records_charts_with_position_and_fallback_image() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"><w:body>
+            <w:p>
+                <w:r><w:t>Sales</w:t></w:r>
+                <w:r><w:drawing><wp:inline><wp:docPr id="1" name="Chart 1"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart r:id="rId5"/></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>
+            </w:p>
+            <w:p><w:r><mc:AlternateContent>
+                <mc:Choice Requires="c14"><w:drawing><wp:inline><a:graphic><a:graphicData><c:chart r:id="rId6"/></a:graphicData></a:graphic></wp:inline></w:drawing></mc:Choice>
+                <mc:Fallback><w:drawing><wp:inline><a:graphic><a:graphicData><pic:pic><pic:blipFill><a:blip r:embed="rId7"/></pic:blipFill></pic:pic><c:chart r:id="rId8"/></a:graphicData></a:graphic></wp:inline></w:drawing></mc:Fallback>
+            </mc:AlternateContent></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let charts = |idx: usize| {
+            index
+                .paragraph(paragraphs[idx])
+                .expect("charts")
+                .charts
+                .clone()
+        };
+        assert_eq!(
+            charts(0),
+            vec![RawChart {
+                position: 2,
+                rel_id: "rId5".to_string(),
+                fallback_image: None,
+            }]
+        );
+        assert_eq!(
+            charts(1),
+            vec![RawChart {
+                position: 1,
+                rel_id: "rId6".to_string(),
+                fallback_image: Some("rId7".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_records_vml_images_rs_docx_drops() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>
+            <w:p><w:r><w:pict><v:shape id="_x0000_i1025"><v:imagedata r:id="rId4" o:title=""/></v:shape></w:pict></w:r></w:p>
+            <w:p>
+                <w:r><w:t>Object</w:t></w:r>
+                <w:r><w:object><v:shape id="_x0000_i1026"><v:imagedata r:id="rId5"/></v:shape><o:OLEObject Type="Embed" r:id="rId6"/></w:object></w:r>
+                <w:r><w:object><v:shape id="_x0000_i1027" alt="a squared"><v:imagedata r:id="rId9"/></v:shape><o:OLEObject Type="Embed" ProgID="Equation.DSMT4" r:id="rId10"/></w:object></w:r>
+            </w:p>
+            <w:p><w:r><w:pict><v:group><v:shape><v:imagedata r:id="rId7"/></v:shape><v:rect><v:imagedata r:id="rId8"/></v:rect></v:group></w:pict></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let images = |idx: usize| {
+            index
+                .paragraph(paragraphs[idx])
+                .map(|p| p.vml_images.clone())
+                .unwrap_or_default()
+        };
+        let image = |position: usize, rel_id: &str| RawVmlImage {
+            position,
+            rel_id: rel_id.to_string(),
+            ..Default::default()
+        };
+        let equation = RawVmlImage {
+            alt: Some("a squared".to_string()),
+            equation: true,
+            ..image(3, "rId9")
+        };
+        assert_eq!(images(0), vec![]);
+        assert_eq!(images(1), vec![image(2, "rId5"), equation]);
+        assert_eq!(images(2), vec![image(1, "rId7"), image(1, "rId8")]);
+    }
+
+    #[test]
+    fn test_build_records_form_field_data() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:fldChar w:fldCharType="begin"><w:ffData><w:name w:val="Check1"/><w:checkBox><w:default w:val="1"/><w:checked w:val="false"/></w:checkBox></w:ffData></w:fldChar></w:r>
+                <w:r><w:fldChar w:fldCharType="begin"><w:ffData><w:checkBox><w:default w:val="0"/><w:checked/></w:checkBox></w:ffData></w:fldChar></w:r>
+                <w:r><w:fldChar w:fldCharType="begin"><w:ffData><w:textInput><w:default w:val="Name"/></w:textInput></w:ffData></w:fldChar></w:r>
+            </w:p>
+            <w:p><w:r><w:fldChar w:fldCharType="begin"><w:ffData><w:ddList><w:default w:val="1"/><w:listEntry w:val="A &amp; B"/><w:listEntry w:val="C"/></w:ddList></w:ffData></w:fldChar></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let fields = |idx: usize| {
+            index
+                .paragraph(paragraphs[idx])
+                .expect("form fields")
+                .form_fields
+                .clone()
+        };
+        let checked: Vec<Option<bool>> = fields(0).iter().map(|field| field.checked).collect();
+        assert_eq!(checked, [Some(false), Some(true), None]);
+        let drop_down = &fields(1)[0];
+        assert_eq!(drop_down.entries, ["A & B", "C"]);
+        assert_eq!(drop_down.selected_entry(), Some("C"));
+    }
+
+    #[test]
+    fn test_build_records_ruby_base_and_annotation() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:t>Read </w:t></w:r>
+                <w:r><w:ruby><w:rubyPr><w:rubyAlign w:val="distributeSpace"/><w:hps w:val="10"/></w:rubyPr><w:rt><w:r><w:rPr><w:sz w:val="10"/></w:rPr><w:t>かん</w:t></w:r></w:rt><w:rubyBase><w:r><w:t>漢</w:t></w:r></w:rubyBase></w:ruby></w:r>
+                <w:r><w:ruby><w:rt><w:r><w:t>じ</w:t></w:r></w:rt><w:rubyBase><w:r><w:t>字</w:t></w:r></w:rubyBase></w:ruby></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let ruby = |position: usize, base: &str, annotation: &str| RawRuby {
+            position,
+            base: base.to_string(),
+            annotation: annotation.to_string(),
+        };
+        assert_eq!(
+            index.paragraph(paragraphs[0]).map(|p| p.rubies.clone()),
+            Some(vec![ruby(2, "漢", "かん"), ruby(3, "字", "じ")])
+        );
+    }
+
+    #[test]
+    fn test_build_records_nested_smart_tags_with_their_runs() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">In </w:t></w:r>
+                <w:smartTag w:uri="urn:schemas-microsoft-com:office:smarttags" w:element="place"><w:smartTag w:element="City"><w:r><w:t>Seoul</w:t></w:r></w:smartTag><w:r><w:t>, Korea</w:t></w:r></w:smartTag>
+            </w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(xml, body);
+        let paragraphs = parsed(body).paragraphs;
+
+        let tags = &index
+            .paragraph(paragraphs[0])
+            .expect("smart tags")
+            .smart_tags;
+        let run = |text: &str| {
+            let start = xml.find(&format!("<w:r><w:t>{}", text)).expect("run");
+            RawSmartTagContent::Run(start..start + xml[start..].find("</w:r>").expect("end") + 6)
+        };
+        assert_eq!(
+            tags,
+            &vec![RawSmartTag {
+                position: 1,
+                element: "place".to_string(),
+                content: vec![
+                    RawSmartTagContent::Tag(RawSmartTag {
+                        position: 0,
+                        element: "City".to_string(),
+                        content: vec![run("Seoul")],
+                    }),
+                    run(", Korea"),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_records_svg_blips() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:asvg="http://schemas.microsoft.com/office/drawing/2016/SVG/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>
+            <w:p><w:r><w:drawing><a:blip r:embed="rId4"><a:extLst><a:ext uri="{96DAC541-7B7A-43D3-8B79-37D633B846F1}"><asvg:svgBlip r:embed="rId5"/></a:ext></a:extLst></a:blip></w:drawing></w:r></w:p>
+            <w:p><w:r><w:drawing><a:blip r:embed="rId6"/></w:drawing></w:r><w:r><w:drawing><asvg:svgBlip r:embed="rId7"/></w:drawing></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_str(xml).expect("document should parse");
+        let index = RawDocumentIndex::build(xml, &document.body.content);
+
+        assert_eq!(
+            index.svg_blips(),
+            &HashMap::from([("rId4".to_string(), "rId5".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_build_is_empty_when_table_counts_disagree() {
+        let document = Document::from_str(DOCUMENT).expect("document should parse");
+        let body = &document.body.content;
+        let index = RawDocumentIndex::build(DOCUMENT, &body[..1]);
+        assert!(index.tables.is_empty());
+    }
+
+    #[test]
+    fn test_decode_xml_handles_byte_order_marks() {
+        let xml = "<root>é</root>";
+        assert_eq!(decode_xml(xml.as_bytes()).as_deref(), Some(xml));
+        assert_eq!(
+            decode_xml(&[b"\xEF\xBB\xBF".as_slice(), xml.as_bytes()].concat()).as_deref(),
+            Some(xml)
+        );
+
+        let utf16le: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(xml.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(decode_xml(&utf16le).as_deref(), Some(xml));
+        let utf16be: Vec<u8> = [0xFE, 0xFF]
+            .into_iter()
+            .chain(xml.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect();
+        assert_eq!(decode_xml(&utf16be).as_deref(), Some(xml));
+
+        assert_eq!(decode_xml(&[0xC3, 0x28]), None);
+    }
+}
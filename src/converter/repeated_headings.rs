@@ -0,0 +1,131 @@
+//! Repeated headings - a heading directly followed, with no other heading in
+//! between, by headings of the same level and text.
+//!
+//! Runs of repeated headings are found before conversion: the first heading
+//! of a run reports them with `RepeatedHeadings::Warn` and `Merge`, and the
+//! repeats are dropped with `Merge`, joining their sections to the first.
+
+use super::raw_xml::element_key;
+use super::{ConversionContext, ConversionWarning};
+use rs_docx::document::{BodyContent, Paragraph, TableCellContent, TableRowContent};
+use std::collections::{HashMap, HashSet};
+
+/// Runs of repeated headings, keyed by their first heading.
+#[derive(Debug, Default)]
+pub(crate) struct RepeatedHeadingRuns {
+    runs: HashMap<usize, ConversionWarning>,
+    repeats: HashSet<usize>,
+}
+
+/// The heading a run started with, and the run so far.
+struct Run {
+    key: usize,
+    level: usize,
+    text: String,
+    paragraphs: Vec<usize>,
+}
+
+impl RepeatedHeadingRuns {
+    /// Finds the runs of repeated headings, counting paragraphs in document
+    /// order, including those in tables and content controls.
+    pub fn build<'a>(body: &[BodyContent<'a>], context: &ConversionContext<'a>) -> Self {
+        let mut headings = Self::default();
+        let mut run = None;
+        let mut paragraph = 0;
+        headings.visit(body, context, &mut run, &mut paragraph);
+        headings.close(run);
+        headings
+    }
+
+    /// Returns the warning of the run a heading starts, if it starts one.
+    pub fn run(&self, para: &Paragraph<'_>) -> Option<&ConversionWarning> {
+        self.runs.get(&element_key(para))
+    }
+
+    /// Returns true when a heading repeats the heading before it.
+    pub fn is_repeat(&self, para: &Paragraph<'_>) -> bool {
+        self.repeats.contains(&element_key(para))
+    }
+
+    fn visit<'a>(
+        &mut self,
+        body: &[BodyContent<'a>],
+        context: &ConversionContext<'a>,
+        run: &mut Option<Run>,
+        paragraph: &mut usize,
+    ) {
+        for content in body {
+            match content {
+                BodyContent::Paragraph(para) => self.add_paragraph(para, context, run, paragraph),
+                BodyContent::Sdt(sdt) => {
+                    if let Some(sdt_content) = &sdt.content {
+                        self.visit(&sdt_content.content, context, run, paragraph);
+                    }
+                }
+                BodyContent::Table(table) => {
+                    for row in &table.rows {
+                        for cell in &row.cells {
+                            let TableRowContent::TableCell(cell) = cell else {
+                                continue;
+                            };
+                            for item in &cell.content {
+                                if let TableCellContent::Paragraph(para) = item {
+                                    self.add_paragraph(para, context, run, paragraph);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_paragraph<'a>(
+        &mut self,
+        para: &Paragraph<'a>,
+        context: &ConversionContext<'a>,
+        run: &mut Option<Run>,
+        paragraph: &mut usize,
+    ) {
+        let index = *paragraph;
+        *paragraph += 1;
+        let Some(level) = context.paragraph_heading_level(para) else {
+            return;
+        };
+        let text = para.text();
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(current) = run {
+            if current.level == level && current.text == text {
+                current.paragraphs.push(index);
+                self.repeats.insert(element_key(para));
+                return;
+            }
+        }
+        let next = Run {
+            key: element_key(para),
+            level,
+            text: text.to_string(),
+            paragraphs: vec![index],
+        };
+        self.close(run.replace(next));
+    }
+
+    fn close(&mut self, run: Option<Run>) {
+        let Some(run) = run.filter(|run| run.paragraphs.len() > 1) else {
+            return;
+        };
+        self.runs.insert(
+            run.key,
+            ConversionWarning::RepeatedHeading {
+                text: run.text,
+                level: run.level,
+                paragraphs: run.paragraphs,
+            },
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! Run element converter - handles text runs with formatting.
 
+use super::context::{is_hidden, is_underlined};
 use super::ConversionContext;
-use crate::Result;
+use crate::render::escape_html_attr;
+use crate::{Result, UnderlineHandling};
 use rs_docx::document::{BreakType, Run, RunContent};
 
 /// Converter for Run elements.
@@ -15,23 +17,34 @@ impl RunConverter {
         para_style_id: Option<&str>,
     ) -> Result<String> {
         let mut text = String::new();
+        let symbols = context.symbol_overrides();
+        let symbol_font = symbols.text_font(run.property.as_ref());
+        let uppercase = context.uppercase_run(run, para_style_id);
 
         // Extract text from run content
         for content in &run.content {
             match content {
-                RunContent::Text(t) => {
-                    text.push_str(&t.text);
-                }
+                RunContent::Text(t) => match symbol_font {
+                    Some(font) => {
+                        text.push_str(&context.document_text(&symbols.map_text(font, &t.text)))
+                    }
+                    None if uppercase => {
+                        text.push_str(&context.document_text(&t.text.to_uppercase()))
+                    }
+                    None => text.push_str(&context.document_text(&t.text)),
+                },
+                // rs_docx drops `w:clear`: text flowing below floating
+                // objects has no Markdown equivalent, so it is a plain break.
                 RunContent::Break(br) => match br.ty {
-                    Some(BreakType::Page) => text.push_str("\n\n---\n\n"),
+                    Some(BreakType::Page) => text.push_str(page_break(context)),
                     Some(BreakType::Column) => text.push_str("\n\n"),
-                    _ => text.push('\n'),
+                    _ => text.push_str(context.line_break()),
                 },
                 RunContent::Tab(_) => {
                     text.push('\t');
                 }
                 RunContent::CarriageReturn(_) => {
-                    text.push('\n');
+                    text.push_str(context.line_break());
                 }
                 RunContent::NoBreakHyphen(_) => {
                     text.push('\u{2011}');
@@ -52,15 +65,8 @@ impl RunConverter {
                     }
                 }
                 RunContent::Sym(sym) => {
-                    // Symbol character - use Unicode if possible
-                    if let Some(char_code) = &sym.char {
-                        // Try to decode hex char code
-                        if let Ok(code) = u32::from_str_radix(char_code, 16) {
-                            if let Some(c) = char::from_u32(code) {
-                                text.push(c);
-                            }
-                        }
-                    }
+                    // Symbol character - mapped to Unicode for known symbol fonts
+                    text.extend(symbols.sym_text(sym.font.as_deref(), sym.char.as_deref()));
                 }
                 RunContent::FootnoteReference(fnref) => {
                     if let Some(id_str) = &fnref.id {
@@ -85,11 +91,11 @@ impl RunConverter {
                         text.push_str(&marker);
                     }
                 }
-                RunContent::PTab(_) => {
-                    text.push('\t');
+                RunContent::PTab(ptab) => {
+                    text.push_str(context.positional_tab(ptab, false));
                 }
-                RunContent::LastRenderedPageBreak(_) => {
-                    text.push_str("\n\n---\n\n");
+                RunContent::LastRenderedPageBreak(_) if !context.page_boundaries() => {
+                    text.push_str(page_break(context));
                 }
                 RunContent::PgNum(_) => {
                     text.push_str("{PAGE}");
@@ -120,6 +126,18 @@ impl RunConverter {
         let effective_props =
             context.resolve_run_property(run.property.as_ref(), run_style_id, para_style_id);
 
+        if is_hidden(&effective_props) {
+            if let Some(replacement) = context.hidden_text_replacement(&text) {
+                return Ok(replacement);
+            }
+        }
+
+        if context.plain_text() {
+            return Ok(text);
+        }
+        if context.is_kbd_style(run_style_id) {
+            text = format!("<kbd>{}</kbd>", text);
+        }
         text = Self::apply_formatting(&text, &effective_props, context);
 
         Ok(text)
@@ -147,8 +165,10 @@ impl RunConverter {
             .map(|i| i.value.unwrap_or(true))
             .unwrap_or(false);
 
-        // Check for underline
-        let has_underline = props.underline.is_some();
+        // Check for underline; as emphasis it reads as italics
+        let has_underline = is_underlined(props);
+        let is_italic = is_italic
+            || (has_underline && context.underline_handling() == UnderlineHandling::Emphasis);
 
         // Check for strikethrough
         let has_strike = props
@@ -171,13 +191,94 @@ impl RunConverter {
         }
 
         if is_bold && is_italic {
-            result = format!("<strong>*{}*</strong>", result);
+            result = if context.no_html() {
+                format!("***{}***", result)
+            } else {
+                format!("<strong>*{}*</strong>", result)
+            };
         } else if is_bold {
-            result = format!("<strong>{}</strong>", result);
+            result = if context.no_html() {
+                format!("**{}**", result)
+            } else {
+                format!("<strong>{}</strong>", result)
+            };
         } else if is_italic {
             result = format!("*{}*", result);
         }
 
+        if let Some(lang) = context.run_language(props, text) {
+            result = format!(
+                "<span lang=\"{}\">{}</span>",
+                escape_html_attr(&lang),
+                result
+            );
+        }
+
         result
     }
 }
+
+/// Returns the text a page break is written as: a rule, or a blank line in
+/// plain text.
+fn page_break(context: &ConversionContext<'_>) -> &'static str {
+    if context.plain_text() {
+        "\n\n"
+    } else {
+        "\n\n---\n\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converter::{ImageExtractor, NumberingResolver, ParagraphConverter, StyleResolver};
+    use hard_xml::XmlRead;
+    use rs_docx::document::{Paragraph, ParagraphContent};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_clear_breaks_are_plain_line_breaks() {
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = NumberingResolver::new(&docx);
+        let mut image_extractor = ImageExtractor::new_skip();
+        let options = crate::ConvertOptions::default();
+        let style_resolver = StyleResolver::new(&docx.styles);
+        let mut context = ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let cases = [
+            (
+                r#"<w:r><w:t>Above</w:t><w:br w:clear="all"/><w:t>Below</w:t></w:r>"#,
+                "Above\nBelow",
+                "Above\nBelow",
+            ),
+            (
+                r#"<w:r><w:t>Above</w:t><w:br w:type="textWrapping" w:clear="left"/></w:r>"#,
+                "Above\n",
+                "Above",
+            ),
+        ];
+        for (xml, run_expected, para_expected) in cases {
+            let run = Run::from_str(xml).expect("Failed to parse run XML");
+            assert_eq!(
+                RunConverter::convert(&run, &mut context, None).unwrap(),
+                run_expected
+            );
+            let mut para = Paragraph::default();
+            para.content.push(ParagraphContent::Run(run));
+            assert_eq!(
+                ParagraphConverter::convert(&para, &mut context).unwrap(),
+                para_expected
+            );
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Heading slugs - anchors for headings named by `ConvertOptions::slug_fn`.
+//!
+//! Slugs are assigned before conversion, so links to a heading's bookmarks
+//! (table of contents entries come before the headings they point at) can
+//! already be rewritten to the heading's slug.
+
+use super::raw_xml::element_key;
+use super::ConversionContext;
+use crate::SlugFn;
+use rs_docx::document::{
+    BodyContent, Paragraph, ParagraphContent, TableCellContent, TableRowContent,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Slugs of the headings in a document body.
+#[derive(Debug, Default)]
+pub(crate) struct HeadingSlugs {
+    by_paragraph: HashMap<usize, String>,
+    by_bookmark: HashMap<String, String>,
+    /// Slug of the last heading, which merged headings share.
+    last: Option<String>,
+}
+
+impl HeadingSlugs {
+    /// Assigns every non-empty heading a slug, in document order.
+    ///
+    /// A slug already taken gets a `-1`, `-2`, ... suffix. Bookmarks inside
+    /// a heading, and body-level bookmarks directly before it, map to its slug.
+    pub fn build<'a>(
+        body: &[BodyContent<'a>],
+        context: &ConversionContext<'a>,
+        slug_fn: &SlugFn,
+    ) -> Self {
+        let mut slugs = Self::default();
+        let mut used = HashSet::new();
+        let mut pending_bookmarks = Vec::new();
+        slugs.visit(body, context, slug_fn, &mut used, &mut pending_bookmarks);
+        slugs
+    }
+
+    pub fn paragraph(&self, para: &Paragraph<'_>) -> Option<&str> {
+        self.by_paragraph
+            .get(&element_key(para))
+            .map(String::as_str)
+    }
+
+    pub fn bookmark(&self, name: &str) -> Option<&str> {
+        self.by_bookmark.get(name).map(String::as_str)
+    }
+
+    fn visit<'a>(
+        &mut self,
+        body: &[BodyContent<'a>],
+        context: &ConversionContext<'a>,
+        slug_fn: &SlugFn,
+        used: &mut HashSet<String>,
+        pending_bookmarks: &mut Vec<String>,
+    ) {
+        for content in body {
+            match content {
+                BodyContent::Paragraph(para) => {
+                    self.add_paragraph(para, context, slug_fn, used, pending_bookmarks);
+                }
+                BodyContent::BookmarkStart(bookmark) => {
+                    pending_bookmarks.extend(bookmark.name.as_deref().map(str::to_owned));
+                }
+                BodyContent::Sdt(sdt) => {
+                    if let Some(sdt_content) = &sdt.content {
+                        self.visit(
+                            &sdt_content.content,
+                            context,
+                            slug_fn,
+                            used,
+                            pending_bookmarks,
+                        );
+                    }
+                }
+                BodyContent::Table(table) => {
+                    pending_bookmarks.clear();
+                    for row in &table.rows {
+                        for cell in &row.cells {
+                            let TableRowContent::TableCell(cell) = cell else {
+                                continue;
+                            };
+                            for item in &cell.content {
+                                if let TableCellContent::Paragraph(para) = item {
+                                    self.add_paragraph(
+                                        para,
+                                        context,
+                                        slug_fn,
+                                        used,
+                                        &mut Vec::new(),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_paragraph<'a>(
+        &mut self,
+        para: &Paragraph<'a>,
+        context: &ConversionContext<'a>,
+        slug_fn: &SlugFn,
+        used: &mut HashSet<String>,
+        pending_bookmarks: &mut Vec<String>,
+    ) {
+        let bookmarks = std::mem::take(pending_bookmarks);
+        let Some(level) = context.paragraph_heading_level(para) else {
+            return;
+        };
+        let inner_bookmarks = para.content.iter().filter_map(|content| match content {
+            ParagraphContent::BookmarkStart(bookmark) => bookmark.name.as_deref(),
+            _ => None,
+        });
+        if let Some(slug) = self
+            .last
+            .clone()
+            .filter(|_| context.is_merged_heading(para))
+        {
+            for name in bookmarks.iter().map(String::as_str).chain(inner_bookmarks) {
+                self.by_bookmark.insert(name.to_string(), slug.clone());
+            }
+            return;
+        }
+        let text = para.text();
+        if text.trim().is_empty() {
+            return;
+        }
+
+        let base = slug_fn.slug(text.trim(), level as u8);
+        let mut slug = base.clone();
+        let mut suffix = 0;
+        while !used.insert(slug.clone()) {
+            suffix += 1;
+            slug = format!("{}-{}", base, suffix);
+        }
+
+        for name in bookmarks.iter().map(String::as_str).chain(inner_bookmarks) {
+            self.by_bookmark.insert(name.to_string(), slug.clone());
+        }
+        self.by_paragraph.insert(element_key(para), slug.clone());
+        self.last = Some(slug);
+    }
+}
+
+/// GitHub's heading slug: lowercase, punctuation removed, whitespace turned
+/// into hyphens.
+pub(crate) fn github_slug(text: &str, _level: u8) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_whitespace() => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_slug() {
+        assert_eq!(github_slug("1.2 Scope & Goals", 2), "12-scope--goals");
+        assert_eq!(github_slug("  Überblick_v2 ", 1), "überblick_v2");
+        assert_eq!(github_slug("시작하기 (개요)", 1), "시작하기-개요");
+    }
+}
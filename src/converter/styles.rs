@@ -1,9 +1,34 @@
 //! Style resolver - handles style inheritance and property merging.
 
+use crate::{Callout, CalloutStyles};
+use rs_docx::document::Run;
 use rs_docx::formatting::{CharacterProperty, ParagraphProperty};
 use rs_docx::styles::Style;
 use std::collections::HashMap;
 
+/// Fixed-pitch font families commonly used for code.
+const MONOSPACE_FONTS: &[&str] = &[
+    "Andale Mono",
+    "Cascadia Code",
+    "Cascadia Mono",
+    "Consolas",
+    "Courier",
+    "Courier New",
+    "DejaVu Sans Mono",
+    "Fira Code",
+    "Inconsolata",
+    "JetBrains Mono",
+    "Liberation Mono",
+    "Lucida Console",
+    "Lucida Sans Typewriter",
+    "Menlo",
+    "Monaco",
+    "Roboto Mono",
+    "SF Mono",
+    "Source Code Pro",
+    "Ubuntu Mono",
+];
+
 /// Resolver for DOCX styles and inheritance.
 pub struct StyleResolver<'a> {
     styles: &'a rs_docx::styles::Styles<'a>,
@@ -59,6 +84,88 @@ impl<'a> StyleResolver<'a> {
         merged
     }
 
+    /// Returns true when the run's effective Latin font is monospace.
+    ///
+    /// The font is resolved through the same hierarchy as
+    /// [`resolve_run_property`](Self::resolve_run_property), so a code font set
+    /// only on the paragraph style, a character style, or `docDefaults` counts.
+    /// Theme font references are not resolved.
+    pub fn effective_monospace(&self, run: &Run<'a>, para_style_id: Option<&str>) -> bool {
+        let run_style_id = run
+            .property
+            .as_ref()
+            .and_then(|props| props.style_id.as_ref())
+            .map(|style| style.value.as_ref());
+        let props = self.resolve_run_property(run.property.as_ref(), run_style_id, para_style_id);
+        props
+            .fonts
+            .as_ref()
+            .and_then(|fonts| fonts.ascii.as_deref().or(fonts.h_ansi.as_deref()))
+            .is_some_and(is_monospace_font)
+    }
+
+    /// Returns the callout of a paragraph style, checking the style's name and
+    /// ID, then those of the styles it is based on. Each is looked up in
+    /// `styles`, then in the `localized` names.
+    pub fn callout(
+        &self,
+        para_style_id: Option<&str>,
+        styles: &CalloutStyles,
+        localized: &[(&str, Callout)],
+    ) -> Option<Callout> {
+        let get = |name: &str| {
+            styles
+                .get(name)
+                .or_else(|| crate::find_callout(localized.iter().copied(), name))
+        };
+        self.style_chain(para_style_id)
+            .find_map(|(id, name)| name.and_then(get).or_else(|| get(id)))
+    }
+
+    /// Returns true when a paragraph style, or a style it is based on, has
+    /// `name` as its name or ID (ignoring case).
+    pub fn has_style(&self, para_style_id: Option<&str>, name: &str) -> bool {
+        self.style_chain(para_style_id).any(|(id, style_name)| {
+            id.eq_ignore_ascii_case(name)
+                || style_name.is_some_and(|style_name| style_name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Returns the level (1-9) of a table of contents entry style (`TOC 1`,
+    /// ID `TOC1`, ...), looking through the styles it is based on.
+    pub fn toc_level(&self, para_style_id: Option<&str>) -> Option<usize> {
+        self.style_chain(para_style_id).find_map(|(id, name)| {
+            name.and_then(parse_toc_style)
+                .or_else(|| parse_toc_style(id))
+        })
+    }
+
+    /// Returns true for the styles of a generated index: entries (`index 1`,
+    /// ID `Index1`, ...) and letter headings (`index heading`), looking
+    /// through the styles they are based on.
+    pub fn is_index_style(&self, para_style_id: Option<&str>) -> bool {
+        self.style_chain(para_style_id)
+            .any(|(id, name)| name.is_some_and(is_index_style) || is_index_style(id))
+    }
+
+    /// Walks a style and its `basedOn` ancestors, yielding each ID with the
+    /// style's name. An unknown ID is yielded without a name and ends the walk.
+    fn style_chain<'s>(
+        &'s self,
+        style_id: Option<&'s str>,
+    ) -> impl Iterator<Item = (&'s str, Option<&'s str>)> + 's {
+        let mut current_id = style_id;
+        // The depth limit guards against `basedOn` cycles.
+        (0..self.style_map.len().max(1)).map_while(move |_| {
+            let id = current_id?;
+            let style = self.style_map.get(id);
+            current_id =
+                style.and_then(|style| style.base.as_ref().map(|base| base.value.as_ref()));
+            let name = style.and_then(|style| style.name.as_ref().map(|name| name.value.as_ref()));
+            Some((id, name))
+        })
+    }
+
     /// Resolves the effective paragraph properties.
     pub fn resolve_paragraph_property(
         &self,
@@ -156,6 +263,78 @@ fn merge_char_props_mut<'a>(target: &mut CharacterProperty<'a>, overlay: &Charac
     if overlay.underline.is_some() {
         target.underline = overlay.underline.clone();
     }
+    if overlay.vanish.is_some() {
+        target.vanish = overlay.vanish.clone();
+    }
+    if overlay.caps.is_some() {
+        target.caps = overlay.caps.clone();
+    }
+    if overlay.small_caps.is_some() {
+        target.small_caps = overlay.small_caps.clone();
+    }
+    if overlay.rtl.is_some() {
+        target.rtl = overlay.rtl.clone();
+    }
+    if let Some(fonts) = &overlay.fonts {
+        // Like `w:lang`, each font slot is inherited independently.
+        let merged = target.fonts.get_or_insert_with(|| fonts.clone());
+        if fonts.ascii.is_some() {
+            merged.ascii = fonts.ascii.clone();
+        }
+        if fonts.east_asia.is_some() {
+            merged.east_asia = fonts.east_asia.clone();
+        }
+        if fonts.h_ansi.is_some() {
+            merged.h_ansi = fonts.h_ansi.clone();
+        }
+        if fonts.custom.is_some() {
+            merged.custom = fonts.custom.clone();
+        }
+    }
+    if let Some(lang) = &overlay.lang {
+        // Each script slot (latin, East Asian, bidi) can be overridden independently.
+        let merged = target.lang.get_or_insert_with(|| lang.clone());
+        if lang.val.is_some() {
+            merged.val = lang.val.clone();
+        }
+        if lang.east_asia.is_some() {
+            merged.east_asia = lang.east_asia.clone();
+        }
+        if lang.bidi.is_some() {
+            merged.bidi = lang.bidi.clone();
+        }
+    }
+}
+
+fn is_monospace_font(name: &str) -> bool {
+    let name = name.trim();
+    MONOSPACE_FONTS
+        .iter()
+        .any(|font| font.eq_ignore_ascii_case(name))
+}
+
+/// Parses a TOC entry style name or ID: "toc 1" or "TOC1" to "toc 9".
+fn parse_toc_style(style: &str) -> Option<usize> {
+    if !style.get(..3)?.eq_ignore_ascii_case("toc") {
+        return None;
+    }
+    style[3..]
+        .trim()
+        .parse()
+        .ok()
+        .filter(|level| (1..=9).contains(level))
+}
+
+/// Matches an index style name or ID: "index 1" or "Index1" to "index 9",
+/// and "index heading" or "IndexHeading".
+fn is_index_style(style: &str) -> bool {
+    let Some(prefix) = style.get(..5) else {
+        return false;
+    };
+    let rest = style[5..].trim();
+    prefix.eq_ignore_ascii_case("index")
+        && (rest.eq_ignore_ascii_case("heading")
+            || rest.parse().is_ok_and(|level: u8| (1..=9).contains(&level)))
 }
 
 // Helper to merge paragraph properties (in-place mutation)
@@ -169,4 +348,138 @@ fn merge_para_props_mut<'a>(target: &mut ParagraphProperty<'a>, overlay: &Paragr
     if overlay.style_id.is_some() {
         target.style_id = overlay.style_id.clone();
     }
+    if let Some(spacing) = &overlay.spacing {
+        // Each attribute overrides the inherited one on its own.
+        let merged = target.spacing.get_or_insert_with(Default::default);
+        merged.before = spacing.before.or(merged.before);
+        merged.before_lines = spacing.before_lines.or(merged.before_lines);
+        merged.before_auto_spacing = spacing.before_auto_spacing.or(merged.before_auto_spacing);
+        merged.after = spacing.after.or(merged.after);
+        merged.after_lines = spacing.after_lines.or(merged.after_lines);
+        merged.after_auto_spacing = spacing.after_auto_spacing.or(merged.after_auto_spacing);
+    }
+    if overlay.contextual_spacing.is_some() {
+        target.contextual_spacing = overlay.contextual_spacing.clone();
+    }
+    if overlay.outline_lvl.is_some() {
+        target.outline_lvl = overlay.outline_lvl.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hard_xml::XmlRead;
+    use rs_docx::document::Paragraph;
+    use rs_docx::styles::Styles;
+
+    const STYLES: &str = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+        <w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri"/></w:rPr></w:rPrDefault></w:docDefaults>
+        <w:style w:type="paragraph" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+        <w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas"/></w:rPr></w:style>
+        <w:style w:type="paragraph" w:styleId="CodeBlock"><w:name w:val="Code Block"/><w:basedOn w:val="Code"/><w:rPr><w:b/></w:rPr></w:style>
+        <w:style w:type="paragraph" w:styleId="a5"><w:name w:val="경고"/><w:basedOn w:val="Normal"/></w:style>
+        <w:style w:type="paragraph" w:styleId="RedWarning"><w:name w:val="Red Warning"/><w:basedOn w:val="a5"/></w:style>
+        <w:style w:type="paragraph" w:styleId="IntenseQuote"><w:name w:val="Custom"/></w:style>
+        <w:style w:type="paragraph" w:styleId="21"><w:name w:val="toc 2"/><w:basedOn w:val="Normal"/></w:style>
+        <w:style w:type="paragraph" w:styleId="MyToc"><w:name w:val="My Toc"/><w:basedOn w:val="21"/></w:style>
+    </w:styles>"#;
+
+    fn paragraph(xml: &str) -> Paragraph<'_> {
+        Paragraph::from_str(xml).expect("paragraph should parse")
+    }
+
+    #[test]
+    fn test_effective_monospace_uses_paragraph_style_font() {
+        let styles = Styles::from_str(STYLES).expect("styles should parse");
+        let resolver = StyleResolver::new(&styles);
+        let para = paragraph(
+            r#"<w:p><w:r><w:t>let x = 1;</w:t></w:r><w:r><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri"/></w:rPr><w:t>prose</w:t></w:r></w:p>"#,
+        );
+        let rs_docx::document::ParagraphContent::Run(code) = &para.content[0] else {
+            panic!("expected run");
+        };
+        let rs_docx::document::ParagraphContent::Run(prose) = &para.content[1] else {
+            panic!("expected run");
+        };
+
+        assert!(resolver.effective_monospace(code, Some("Code")));
+        assert!(resolver.effective_monospace(code, Some("CodeBlock")));
+        assert!(!resolver.effective_monospace(code, Some("Normal")));
+        assert!(!resolver.effective_monospace(code, None));
+        assert!(!resolver.effective_monospace(prose, Some("Code")));
+    }
+
+    #[test]
+    fn test_callout_matches_style_name_id_and_base_style() {
+        let styles = Styles::from_str(STYLES).expect("styles should parse");
+        let resolver = StyleResolver::new(&styles);
+        let callouts = CalloutStyles::default();
+        let localized = crate::localization::CALLOUT_STYLE_NAMES;
+
+        let warning = Some(Callout::Alert(crate::AlertKind::Warning));
+        assert_eq!(resolver.callout(Some("a5"), &callouts, localized), warning);
+        assert_eq!(
+            resolver.callout(Some("RedWarning"), &callouts, localized),
+            warning
+        );
+        assert_eq!(
+            resolver.callout(Some("IntenseQuote"), &callouts, localized),
+            Some(Callout::Quote)
+        );
+        assert_eq!(
+            resolver.callout(Some("Note"), &callouts, localized),
+            Some(Callout::Alert(crate::AlertKind::Note))
+        );
+        assert_eq!(resolver.callout(Some("Code"), &callouts, localized), None);
+        assert_eq!(resolver.callout(None, &callouts, localized), None);
+
+        // Localized names only match when listed.
+        assert_eq!(resolver.callout(Some("a5"), &callouts, &[]), None);
+        assert_eq!(
+            resolver.callout(
+                Some("RedWarning"),
+                &callouts,
+                &[("Red Warning", Callout::Quote)]
+            ),
+            Some(Callout::Quote)
+        );
+    }
+
+    #[test]
+    fn test_has_style_matches_name_or_id_along_base_styles() {
+        let styles = Styles::from_str(STYLES).expect("styles should parse");
+        let resolver = StyleResolver::new(&styles);
+
+        assert!(resolver.has_style(Some("CodeBlock"), "code block"));
+        assert!(resolver.has_style(Some("CodeBlock"), "Normal"));
+        assert!(resolver.has_style(Some("Caption"), "caption"));
+        assert!(!resolver.has_style(Some("Code"), "Code Block"));
+        assert!(!resolver.has_style(None, "Normal"));
+    }
+
+    #[test]
+    fn test_toc_level_matches_toc_styles() {
+        let styles = Styles::from_str(STYLES).expect("styles should parse");
+        let resolver = StyleResolver::new(&styles);
+
+        assert_eq!(resolver.toc_level(Some("TOC1")), Some(1));
+        assert_eq!(resolver.toc_level(Some("21")), Some(2));
+        assert_eq!(resolver.toc_level(Some("MyToc")), Some(2));
+        assert_eq!(resolver.toc_level(Some("TOCHeading")), None);
+        assert_eq!(resolver.toc_level(Some("TOC10")), None);
+        assert_eq!(resolver.toc_level(Some("Normal")), None);
+    }
+
+    #[test]
+    fn test_is_index_style_matches_index_styles() {
+        let styles = Styles::from_str(STYLES).expect("styles should parse");
+        let resolver = StyleResolver::new(&styles);
+
+        assert!(resolver.is_index_style(Some("Index1")));
+        assert!(resolver.is_index_style(Some("IndexHeading")));
+        assert!(!resolver.is_index_style(Some("Index")));
+        assert!(!resolver.is_index_style(Some("Index10")));
+        assert!(!resolver.is_index_style(Some("TOC1")));
+    }
 }
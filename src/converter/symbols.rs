@@ -0,0 +1,346 @@
+//! Symbol font mapping - translates legacy symbol-font code points to Unicode.
+//!
+//! Fonts such as Symbol and Wingdings draw arbitrary glyphs for ordinary
+//! byte codes, so their text only reads correctly once mapped. This is the
+//! only place where run fonts (`w:rFonts`) influence the output; every other
+//! font directive, including theme fonts, is ignored. Glyphs of other fonts,
+//! such as icon fonts of corporate templates, are mapped by
+//! `symbol_overrides`, which also takes precedence over the built-in tables.
+
+use rs_docx::formatting::CharacterProperty;
+use std::collections::HashMap;
+
+/// Fonts whose code points do not correspond to Unicode text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolFont {
+    Symbol,
+    Wingdings,
+}
+
+impl SymbolFont {
+    /// Recognizes a symbol font by its family name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("Symbol") {
+            Some(Self::Symbol)
+        } else if name.eq_ignore_ascii_case("Wingdings") {
+            Some(Self::Wingdings)
+        } else {
+            None
+        }
+    }
+
+    /// Maps a font code point, either `0xXX` or its `0xF0XX` private-use alias.
+    pub fn map(self, code: u32) -> Option<char> {
+        let code = match code {
+            0xF000..=0xF0FF => code - 0xF000,
+            0x00..=0xFF => code,
+            _ => return None,
+        };
+        let mapped = match self {
+            Self::Symbol => symbol_to_unicode(code),
+            Self::Wingdings => wingdings_to_unicode(code),
+        };
+        mapped.and_then(char::from_u32)
+    }
+
+    /// Maps every character of a run's text, keeping characters without a mapping.
+    pub fn map_text(self, text: &str) -> String {
+        text.chars()
+            .map(|c| self.map(c as u32).unwrap_or(c))
+            .collect()
+    }
+}
+
+/// Returns the font explicitly set on a run, if any.
+///
+/// Only the literal `w:ascii`/`w:hAnsi` names are consulted; theme font
+/// references never resolve to a symbol font.
+pub(crate) fn run_font<'p>(props: Option<&'p CharacterProperty<'_>>) -> Option<&'p str> {
+    let fonts = props?.fonts.as_ref()?;
+    fonts.ascii.as_deref().or(fonts.h_ansi.as_deref())
+}
+
+/// Glyph replacements of `symbol_overrides`, keyed by font name and code.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SymbolOverrides<'a>(&'a HashMap<(String, u32), String>);
+
+impl<'a> SymbolOverrides<'a> {
+    pub fn new(overrides: &'a HashMap<(String, u32), String>) -> Self {
+        Self(overrides)
+    }
+
+    /// Returns the replacement of a font's code point, matching the font
+    /// name ignoring ASCII case and the code or its `0xF0XX` alias.
+    pub fn get(self, font: &str, code: u32) -> Option<&'a str> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let font = font.trim();
+        let alias = match code {
+            0xF000..=0xF0FF => code - 0xF000,
+            0x00..=0xFF => code + 0xF000,
+            _ => code,
+        };
+        self.0
+            .iter()
+            .find(|((name, c), _)| (*c == code || *c == alias) && name.eq_ignore_ascii_case(font))
+            .map(|(_, replacement)| replacement.as_str())
+    }
+
+    /// Returns the font of a run when its text needs mapping: a built-in
+    /// symbol font or a font with overrides.
+    pub fn text_font<'p>(self, props: Option<&'p CharacterProperty<'_>>) -> Option<&'p str> {
+        run_font(props).filter(|font| {
+            SymbolFont::from_name(font).is_some()
+                || self
+                    .0
+                    .keys()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(font.trim()))
+        })
+    }
+
+    /// Maps every character of text in `font`, overrides first, keeping
+    /// characters without a mapping.
+    pub fn map_text(self, font: &str, text: &str) -> String {
+        let symbol_font = SymbolFont::from_name(font);
+        if self.0.is_empty() {
+            return symbol_font.map_or_else(|| text.to_string(), |f| f.map_text(text));
+        }
+        let mut mapped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.get(font, c as u32) {
+                Some(replacement) => mapped.push_str(replacement),
+                None => mapped.push(symbol_font.and_then(|f| f.map(c as u32)).unwrap_or(c)),
+            }
+        }
+        mapped
+    }
+
+    /// Resolves a `w:sym` element like `sym_to_char`, overrides first.
+    pub fn sym_text(self, font: Option<&str>, char_code: Option<&str>) -> Option<String> {
+        let replacement = font.zip(char_code).and_then(|(font, code)| {
+            let code = u32::from_str_radix(code, 16).ok()?;
+            self.get(font, code)
+        });
+        match replacement {
+            Some(replacement) => Some(replacement.to_string()),
+            None => sym_to_char(font, char_code).map(String::from),
+        }
+    }
+}
+
+/// Resolves a `w:sym` element (font and hex character code) to the character it displays.
+pub(crate) fn sym_to_char(font: Option<&str>, char_code: Option<&str>) -> Option<char> {
+    let code = u32::from_str_radix(char_code?, 16).ok()?;
+    font.and_then(SymbolFont::from_name)
+        .and_then(|font| font.map(code))
+        .or_else(|| char::from_u32(code))
+}
+
+fn symbol_to_unicode(code: u32) -> Option<u32> {
+    let mapped = match code {
+        0x22 => 0x2200,
+        0x24 => 0x2203,
+        0x27 => 0x220B,
+        0x2A => 0x2217,
+        0x2D => 0x2212,
+        0x40 => 0x2245,
+        0x41 => 0x0391,
+        0x42 => 0x0392,
+        0x43 => 0x03A7,
+        0x44 => 0x0394,
+        0x45 => 0x0395,
+        0x46 => 0x03A6,
+        0x47 => 0x0393,
+        0x48 => 0x0397,
+        0x49 => 0x0399,
+        0x4A => 0x03D1,
+        0x4B => 0x039A,
+        0x4C => 0x039B,
+        0x4D => 0x039C,
+        0x4E => 0x039D,
+        0x4F => 0x039F,
+        0x50 => 0x03A0,
+        0x51 => 0x0398,
+        0x52 => 0x03A1,
+        0x53 => 0x03A3,
+        0x54 => 0x03A4,
+        0x55 => 0x03A5,
+        0x56 => 0x03C2,
+        0x57 => 0x03A9,
+        0x58 => 0x039E,
+        0x59 => 0x03A8,
+        0x5A => 0x0396,
+        0x5C => 0x2234,
+        0x5E => 0x22A5,
+        0x61 => 0x03B1,
+        0x62 => 0x03B2,
+        0x63 => 0x03C7,
+        0x64 => 0x03B4,
+        0x65 => 0x03B5,
+        0x66 => 0x03C6,
+        0x67 => 0x03B3,
+        0x68 => 0x03B7,
+        0x69 => 0x03B9,
+        0x6A => 0x03D5,
+        0x6B => 0x03BA,
+        0x6C => 0x03BB,
+        0x6D => 0x03BC,
+        0x6E => 0x03BD,
+        0x6F => 0x03BF,
+        0x70 => 0x03C0,
+        0x71 => 0x03B8,
+        0x72 => 0x03C1,
+        0x73 => 0x03C3,
+        0x74 => 0x03C4,
+        0x75 => 0x03C5,
+        0x76 => 0x03D6,
+        0x77 => 0x03C9,
+        0x78 => 0x03BE,
+        0x79 => 0x03C8,
+        0x7A => 0x03B6,
+        0x7E => 0x223C,
+        0x20..=0x7D => code,
+        0xA0 => 0x20AC,
+        0xA1 => 0x03D2,
+        0xA2 => 0x2032,
+        0xA3 => 0x2264,
+        0xA4 => 0x2044,
+        0xA5 => 0x221E,
+        0xA6 => 0x0192,
+        0xA7 => 0x2663,
+        0xA8 => 0x2666,
+        0xA9 => 0x2665,
+        0xAA => 0x2660,
+        0xAB => 0x2194,
+        0xAC => 0x2190,
+        0xAD => 0x2191,
+        0xAE => 0x2192,
+        0xAF => 0x2193,
+        0xB0 => 0x00B0,
+        0xB1 => 0x00B1,
+        0xB2 => 0x2033,
+        0xB3 => 0x2265,
+        0xB4 => 0x00D7,
+        0xB5 => 0x221D,
+        0xB6 => 0x2202,
+        0xB7 => 0x2022,
+        0xB8 => 0x00F7,
+        0xB9 => 0x2260,
+        0xBA => 0x2261,
+        0xBB => 0x2248,
+        0xBC => 0x2026,
+        0xBF => 0x21B5,
+        0xC0 => 0x2135,
+        0xC1 => 0x2111,
+        0xC2 => 0x211C,
+        0xC3 => 0x2118,
+        0xC4 => 0x2297,
+        0xC5 => 0x2295,
+        0xC6 => 0x2205,
+        0xC7 => 0x2229,
+        0xC8 => 0x222A,
+        0xC9 => 0x2283,
+        0xCA => 0x2287,
+        0xCB => 0x2284,
+        0xCC => 0x2282,
+        0xCD => 0x2286,
+        0xCE => 0x2208,
+        0xCF => 0x2209,
+        0xD0 => 0x2220,
+        0xD1 => 0x2207,
+        0xD2 => 0x00AE,
+        0xD3 => 0x00A9,
+        0xD4 => 0x2122,
+        0xD5 => 0x220F,
+        0xD6 => 0x221A,
+        0xD7 => 0x22C5,
+        0xD8 => 0x00AC,
+        0xD9 => 0x2227,
+        0xDA => 0x2228,
+        0xDB => 0x21D4,
+        0xDC => 0x21D0,
+        0xDD => 0x21D1,
+        0xDE => 0x21D2,
+        0xDF => 0x21D3,
+        0xE0 => 0x25CA,
+        0xE1 => 0x2329,
+        0xE5 => 0x2211,
+        0xF1 => 0x232A,
+        0xF2 => 0x222B,
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+fn wingdings_to_unicode(code: u32) -> Option<u32> {
+    let mapped = match code {
+        0x6C => 0x25CF,
+        0x6E => 0x25A0,
+        0x6F => 0x25A1,
+        0x71 => 0x2751,
+        0x75 => 0x25C6,
+        0x76 => 0x2756,
+        0x9F => 0x2022,
+        0xA1 => 0x25CB,
+        0xA7 => 0x25AA,
+        0xA8 => 0x25FB,
+        0xD8 => 0x27A2,
+        0xE8 => 0x2794,
+        0xFB => 0x2718,
+        0xFC => 0x2714,
+        0xFD => 0x2612,
+        0xFE => 0x2611,
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sym_maps_private_use_codes_for_symbol_fonts() {
+        assert_eq!(sym_to_char(Some("Symbol"), Some("F0B7")), Some('\u{2022}'));
+        assert_eq!(
+            sym_to_char(Some("Wingdings"), Some("F0FC")),
+            Some('\u{2714}')
+        );
+        assert_eq!(
+            sym_to_char(Some("Times New Roman"), Some("2013")),
+            Some('\u{2013}')
+        );
+        assert_eq!(sym_to_char(None, Some("zz")), None);
+    }
+
+    #[test]
+    fn test_symbol_font_text_maps_greek_letters() {
+        assert_eq!(SymbolFont::Symbol.map_text("a+b=p"), "α+β=π");
+    }
+
+    #[test]
+    fn test_symbol_overrides_come_before_built_in_tables() {
+        let overrides = HashMap::from([
+            (("Corporate Icons".to_string(), 0x41), ":phone:".to_string()),
+            (("Wingdings".to_string(), 0xF0FC), "[x]".to_string()),
+        ]);
+        let overrides = SymbolOverrides::new(&overrides);
+
+        assert_eq!(
+            overrides.sym_text(Some("corporate icons"), Some("F041")),
+            Some(":phone:".to_string())
+        );
+        assert_eq!(
+            overrides.sym_text(Some("Wingdings"), Some("FC")),
+            Some("[x]".to_string())
+        );
+        assert_eq!(
+            overrides.sym_text(Some("Symbol"), Some("F0B7")),
+            Some("\u{2022}".to_string())
+        );
+        assert_eq!(overrides.map_text("Corporate Icons", "AB"), ":phone:B");
+        assert_eq!(overrides.map_text("Wingdings", "\u{FC}l"), "[x]\u{25CF}");
+    }
+}
@@ -0,0 +1,231 @@
+//! Tab table detector - renders runs of tab-aligned paragraphs as tables.
+//!
+//! Documents sometimes fake tables with tab stops. Consecutive paragraphs that
+//! split into the same number of tab-separated columns form a candidate
+//! region, which becomes a table when it is long and regular enough.
+
+use super::table_grid::{render_grid, render_pipe_table, CellStatus};
+use crate::core::ast::BlockNode;
+use crate::TabTables;
+use std::collections::HashMap;
+
+/// A paragraph split at its tab stops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TabRow {
+    cells: Vec<String>,
+    /// Number of tabs between each pair of adjacent cells.
+    gaps: Vec<usize>,
+}
+
+impl TabRow {
+    /// Splits a single-line paragraph with at least one inner tab.
+    ///
+    /// Leading and trailing tabs are indentation; a run of tabs is one
+    /// separator, so columns aligned with several tabs still line up.
+    fn parse(block: &BlockNode) -> Option<Self> {
+        let BlockNode::Paragraph(text) = block else {
+            return None;
+        };
+        let text = text.trim_matches('\t');
+        if !text.contains('\t') || text.contains('\n') || text.starts_with(['#', '>']) {
+            return None;
+        }
+
+        let mut row = Self {
+            cells: Vec::new(),
+            gaps: Vec::new(),
+        };
+        let mut rest = text;
+        while let Some(pos) = rest.find('\t') {
+            row.cells.push(rest[..pos].trim().to_string());
+            let after = &rest[pos..];
+            let separator = after.trim_start_matches('\t');
+            row.gaps.push(after.len() - separator.len());
+            rest = separator;
+        }
+        row.cells.push(rest.trim().to_string());
+        Some(row)
+    }
+}
+
+/// Replaces qualifying runs of tab-aligned paragraphs with HTML tables.
+///
+/// Each table is preceded by a `<!-- tab table: confidence N -->` comment.
+/// With `no_html`, tables are pipe tables and the comment is left out.
+pub(crate) fn convert_blocks(
+    blocks: Vec<BlockNode>,
+    settings: TabTables,
+    empty_cell: &str,
+    no_html: bool,
+) -> Vec<BlockNode> {
+    let rows: Vec<Option<TabRow>> = blocks.iter().map(TabRow::parse).collect();
+    let mut output = Vec::with_capacity(blocks.len());
+    let mut blocks = blocks.into_iter();
+
+    let mut start = 0;
+    while start < rows.len() {
+        let Some(first) = &rows[start] else {
+            output.extend(blocks.next());
+            start += 1;
+            continue;
+        };
+        let columns = first.cells.len();
+        let end = rows[start..]
+            .iter()
+            .position(|row| row.as_ref().map_or(true, |row| row.cells.len() != columns))
+            .map_or(rows.len(), |len| start + len);
+
+        let region: Vec<&TabRow> = rows[start..end].iter().flatten().collect();
+        let confidence = confidence(&region);
+        if region.len() >= settings.min_rows.max(1) && confidence >= settings.min_confidence {
+            if no_html {
+                output.push(BlockNode::TableHtml(render_pipe_table(grid(&region), None)));
+            } else {
+                output.push(BlockNode::RawHtml(format!(
+                    "<!-- tab table: confidence {:.2} -->",
+                    confidence
+                )));
+                output.push(BlockNode::TableHtml(render_grid(
+                    grid(&region),
+                    None,
+                    None,
+                    &[],
+                    empty_cell,
+                    false,
+                    false,
+                )));
+            }
+            blocks.by_ref().take(region.len()).for_each(drop);
+        } else {
+            output.extend(blocks.by_ref().take(region.len()));
+        }
+        start = end;
+    }
+    output
+}
+
+/// Scores a region from 0.0 to 1.0.
+///
+/// The score is the share of rows using the most common tab layout, times the
+/// share of non-blank cells.
+fn confidence(region: &[&TabRow]) -> f32 {
+    let mut layouts: HashMap<&[usize], usize> = HashMap::new();
+    for row in region {
+        *layouts.entry(row.gaps.as_slice()).or_default() += 1;
+    }
+    let Some(dominant) = layouts.values().max() else {
+        return 0.0;
+    };
+    let agreement = *dominant as f32 / region.len() as f32;
+
+    let cells: Vec<&String> = region.iter().flat_map(|row| &row.cells).collect();
+    let filled = cells.iter().filter(|cell| !cell.is_empty()).count();
+    agreement * filled as f32 / cells.len() as f32
+}
+
+fn grid(region: &[&TabRow]) -> Vec<Vec<CellStatus>> {
+    region
+        .iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| CellStatus::Occupied {
+                    content: cell.clone(),
+                    style: None,
+                    rowspan: 1,
+                    colspan: 1,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraphs(lines: &[&str]) -> Vec<BlockNode> {
+        lines
+            .iter()
+            .map(|line| BlockNode::Paragraph(line.to_string()))
+            .collect()
+    }
+
+    fn texts(blocks: &[BlockNode]) -> Vec<&str> {
+        blocks
+            .iter()
+            .map(|block| match block {
+                BlockNode::Paragraph(text)
+                | BlockNode::TableHtml(text)
+                | BlockNode::RawHtml(text) => text.as_str(),
+                BlockNode::PageBreak => "",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_consistent_tab_rows_become_a_table() {
+        let blocks = paragraphs(&[
+            "Prices:",
+            "Item\t\tQty\tPrice",
+            "\tApple\t\t3\t<strong>1.20</strong>",
+            "Pear\t\t10\t0.80",
+            "Total follows.",
+        ]);
+        let converted = convert_blocks(blocks, TabTables::default(), "&nbsp;", false);
+
+        assert_eq!(
+            texts(&converted),
+            [
+                "Prices:",
+                "<!-- tab table: confidence 1.00 -->",
+                "<table>\n\
+                 \x20 <tr>\n    <td>Item</td>\n    <td>Qty</td>\n    <td>Price</td>\n  </tr>\n\
+                 \x20 <tr>\n    <td>Apple</td>\n    <td>3</td>\n    <td><strong>1.20</strong></td>\n  </tr>\n\
+                 \x20 <tr>\n    <td>Pear</td>\n    <td>10</td>\n    <td>0.80</td>\n  </tr>\n\
+                 </table>",
+                "Total follows.",
+            ]
+        );
+
+        let blocks = paragraphs(&["Item\tQty", "Apple\t3", "Pear\t10"]);
+        assert_eq!(
+            texts(&convert_blocks(
+                blocks,
+                TabTables::default(),
+                "&nbsp;",
+                true
+            )),
+            ["| Item | Qty |\n| --- | --- |\n| Apple | 3 |\n| Pear | 10 |"]
+        );
+    }
+
+    #[test]
+    fn test_short_or_irregular_regions_stay_paragraphs() {
+        let settings = TabTables {
+            min_rows: 3,
+            min_confidence: 0.8,
+        };
+
+        let short = paragraphs(&["a\tb", "c\td", "e\tf\tg"]);
+        assert_eq!(
+            texts(&convert_blocks(short, settings, "&nbsp;", false)),
+            ["a\tb", "c\td", "e\tf\tg"]
+        );
+
+        // Half of the rows use a different tab layout.
+        let irregular = paragraphs(&["a\tb", "c\t\td", "e\t\tf", "g\th"]);
+        let rows: Vec<TabRow> = irregular.iter().filter_map(TabRow::parse).collect();
+        assert_eq!(confidence(&rows.iter().collect::<Vec<_>>()), 0.5);
+        assert_eq!(
+            texts(&convert_blocks(irregular, settings, "&nbsp;", false)),
+            ["a\tb", "c\t\td", "e\t\tf", "g\th"]
+        );
+
+        let blank = TabRow::parse(&BlockNode::Paragraph("e\t \tf".to_string()));
+        assert_eq!(
+            blank.map(|row| row.cells),
+            Some(vec!["e".into(), "".into(), "f".into()])
+        );
+    }
+}
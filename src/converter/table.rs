@@ -1,32 +1,134 @@
-//! Table converter - converts tables to HTML with merge support.
+//! Table converter - converts tables to HTML with merge support, or to pipe tables.
 
+use super::figure::image_count;
+use super::raw_xml::RawCellMargins;
 use super::table_grid;
 use super::{ConversionContext, ParagraphConverter};
-use crate::Result;
+use crate::{Result, RtlTableOrder};
 use rs_docx::document::{Table, TableCell, TableCellContent};
 
 /// Converter for Table elements.
 pub struct TableConverter;
 
 impl TableConverter {
-    /// Converts a Table to HTML format with correct merge handling.
+    /// Converts a Table to HTML format with correct merge handling, to a
+    /// Markdown pipe table with `no_html`, or to tab-separated rows in plain
+    /// text.
     pub fn convert<'a>(table: &Table<'a>, context: &mut ConversionContext<'a>) -> Result<String> {
-        let grid = table_grid::build_grid(table, |cell| Self::convert_cell_content(cell, context))?;
-        Ok(table_grid::render_grid(grid))
+        let table_margins = context
+            .raw_table_properties(table)
+            .map(|p| p.cell_margins)
+            .unwrap_or_default();
+        let mut grid = table_grid::build_grid(table, |cell| {
+            context.enter_html_cell();
+            let content = Self::convert_cell_content(cell, context);
+            context.leave_html_cell();
+            let padding = Self::cell_padding(cell, table_margins, context);
+            content.map(|content| (content, padding))
+        })?;
+        let props = context.raw_table_properties(table);
+        // Cells are in document order; only reverse them for display order.
+        let right_to_left = context.rtl_table_order() == RtlTableOrder::Visual
+            && props.is_some_and(|p| p.bidi_visual);
+        if context.plain_text() {
+            if right_to_left {
+                table_grid::reverse_columns(&mut grid);
+            }
+            return Ok(table_grid::render_text_table(
+                grid,
+                props.and_then(|p| p.caption.as_deref()),
+            ));
+        }
+        if context.no_html() {
+            if context.pipe_table_row_headers() && props.is_some_and(|p| p.first_column) {
+                table_grid::bold_first_column(&mut grid);
+            }
+            if right_to_left {
+                table_grid::reverse_columns(&mut grid);
+            }
+            let caption = props
+                .and_then(|p| p.caption.as_deref())
+                .map(|caption| context.document_text(caption));
+            return Ok(table_grid::render_pipe_table(grid, caption.as_deref()));
+        }
+        let column_widths = if context.table_column_widths() {
+            table_grid::column_percentages(&table.grids)
+        } else {
+            Vec::new()
+        };
+        let html = table_grid::render_grid(
+            grid,
+            props.and_then(|p| p.caption.as_deref()),
+            props.and_then(|p| p.description.as_deref()),
+            &column_widths,
+            context.empty_table_cell(),
+            context.table_row_headers() && props.is_some_and(|p| p.first_column),
+            right_to_left,
+        );
+        if context.pretty_html() {
+            return Ok(table_grid::indent_nested_tables(&html));
+        }
+        Ok(html)
+    }
+
+    /// Returns the `padding` style for a cell's margins, its own overriding
+    /// the table's, with `table_cell_padding`.
+    fn cell_padding(
+        cell: &TableCell<'_>,
+        table_margins: RawCellMargins,
+        context: &ConversionContext<'_>,
+    ) -> Option<String> {
+        if !context.table_cell_padding() || context.no_html() {
+            return None;
+        }
+        let margins = context
+            .raw_cell_properties(cell)
+            .map(|p| p.margins)
+            .unwrap_or_default()
+            .or(table_margins);
+        let sides = [
+            ("top", margins.top),
+            ("right", margins.right),
+            ("bottom", margins.bottom),
+            ("left", margins.left),
+        ];
+        if sides.iter().all(|(_, twips)| twips.is_some()) {
+            let values: Vec<String> = sides
+                .iter()
+                .map(|(_, twips)| twips_to_px(twips.unwrap_or(0)))
+                .collect();
+            return Some(format!("padding:{}", values.join(" ")));
+        }
+        let declarations: Vec<String> = sides
+            .iter()
+            .filter_map(|(side, twips)| {
+                twips.map(|twips| format!("padding-{}:{}", side, twips_to_px(twips)))
+            })
+            .collect();
+        (!declarations.is_empty()).then(|| declarations.join(";"))
     }
 
     fn convert_cell_content<'a>(
         cell: &TableCell<'a>,
         context: &mut ConversionContext<'a>,
     ) -> Result<String> {
+        if let Some(images) = Self::convert_image_cell(cell, context)? {
+            return Ok(images);
+        }
+        if context.table_cell_blocks() {
+            return Self::convert_cell_blocks(cell, context);
+        }
         let mut content = String::new();
         for item in &cell.content {
             match item {
                 TableCellContent::Paragraph(para) => {
-                    let para_content = ParagraphConverter::convert(para, context)?;
+                    let mut para_content = ParagraphConverter::convert(para, context)?;
+                    if context.trim_table_cells() {
+                        para_content = para_content.trim().to_string();
+                    }
                     if !para_content.is_empty() {
                         if !content.is_empty() {
-                            content.push_str("<br/>");
+                            content.push_str(if context.no_html() { " " } else { "<br/>" });
                         }
                         content.push_str(&para_content);
                     }
@@ -39,6 +141,169 @@ impl TableConverter {
         }
         Ok(content)
     }
+
+    /// Converts a cell holding nothing but images, in image-only and blank
+    /// paragraphs, to the images alone, without the whitespace and blank
+    /// paragraphs around them. Returns `None` for any other cell.
+    fn convert_image_cell<'a>(
+        cell: &TableCell<'a>,
+        context: &mut ConversionContext<'a>,
+    ) -> Result<Option<String>> {
+        let mut paragraphs = Vec::new();
+        for item in &cell.content {
+            match item {
+                TableCellContent::Paragraph(para) => match image_count(para) {
+                    Some(0) => {}
+                    Some(_) => paragraphs.push(para),
+                    None => return Ok(None),
+                },
+                TableCellContent::Table(_) => return Ok(None),
+            }
+        }
+        if paragraphs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut images = Vec::new();
+        for para in paragraphs {
+            let converted = ParagraphConverter::convert(para, context)?;
+            let converted = converted.trim();
+            if converted.is_empty() {
+                continue;
+            }
+            images.push(converted.to_string());
+        }
+        Ok(Some(images.join(if context.no_html() {
+            " "
+        } else {
+            "<br/>"
+        })))
+    }
+
+    /// Converts a cell's content keeping its blocks, with `table_cell_blocks`.
+    fn convert_cell_blocks<'a>(
+        cell: &TableCell<'a>,
+        context: &mut ConversionContext<'a>,
+    ) -> Result<String> {
+        let mut blocks = Vec::new();
+        for item in &cell.content {
+            match item {
+                TableCellContent::Paragraph(para) => {
+                    context.take_list_marker();
+                    let mut converted = ParagraphConverter::convert(para, context)?;
+                    let marker = context.take_list_marker();
+                    if let Some(item) =
+                        marker.and_then(|marker| CellBlock::list_item(marker, &converted))
+                    {
+                        blocks.push(item);
+                        continue;
+                    }
+                    if context.trim_table_cells() {
+                        converted = converted.trim().to_string();
+                    }
+                    if !converted.is_empty() {
+                        blocks.push(CellBlock::Paragraph(converted));
+                    }
+                }
+                TableCellContent::Table(table) => {
+                    blocks.push(CellBlock::Table(TableConverter::convert(table, context)?));
+                }
+            }
+        }
+        Ok(render_cell_blocks(blocks))
+    }
+}
+
+/// A block of a table cell's content, with `table_cell_blocks`.
+enum CellBlock {
+    Paragraph(String),
+    ListItem {
+        level: usize,
+        ordered: bool,
+        start: Option<i32>,
+        text: String,
+    },
+    Table(String),
+}
+
+impl CellBlock {
+    /// Returns the list item a paragraph was converted to, from its marker
+    /// (level, counter value and written prefix).
+    fn list_item(marker: (usize, Option<i32>, String), converted: &str) -> Option<Self> {
+        let (level, number, prefix) = marker;
+        let text = converted.strip_prefix(&prefix)?;
+        Some(Self::ListItem {
+            level,
+            start: number.filter(|&number| number != 1),
+            ordered: number.is_some(),
+            text: text.trim().to_string(),
+        })
+    }
+}
+
+/// Writes the blocks of a cell: a single paragraph as it is, otherwise each
+/// paragraph in a `<p>` and runs of list items as nested `<ul>`/`<ol>`.
+fn render_cell_blocks(blocks: Vec<CellBlock>) -> String {
+    if let [CellBlock::Paragraph(text)] = blocks.as_slice() {
+        return text.clone();
+    }
+    let mut html = String::new();
+    // Tags of the open lists, outermost first; each has an open `<li>`.
+    let mut lists: Vec<&str> = Vec::new();
+    let close = |html: &mut String, lists: &mut Vec<&str>, depth: usize| {
+        while lists.len() > depth {
+            let tag = lists.pop().unwrap_or("ul");
+            html.push_str(&format!("</li></{}>", tag));
+        }
+    };
+    for block in blocks {
+        match block {
+            CellBlock::ListItem {
+                level,
+                ordered,
+                start,
+                text,
+            } => {
+                let tag = if ordered { "ol" } else { "ul" };
+                close(&mut html, &mut lists, level + 1);
+                if lists.len() == level + 1 {
+                    if lists[level] == tag {
+                        html.push_str("</li>");
+                    } else {
+                        close(&mut html, &mut lists, level);
+                    }
+                }
+                while lists.len() <= level {
+                    match start {
+                        Some(start) => html.push_str(&format!("<ol start=\"{}\">", start)),
+                        None => html.push_str(&format!("<{}>", tag)),
+                    }
+                    lists.push(tag);
+                }
+                html.push_str("<li>");
+                html.push_str(&text);
+            }
+            CellBlock::Paragraph(text) => {
+                close(&mut html, &mut lists, 0);
+                html.push_str(&format!("<p>{}</p>", text));
+            }
+            CellBlock::Table(table) => {
+                close(&mut html, &mut lists, 0);
+                html.push_str(&table);
+            }
+        }
+    }
+    close(&mut html, &mut lists, 0);
+    html
+}
+
+/// Converts twips (1/1440 inch) to CSS pixels (1/96 inch), e.g. `7.2px`.
+fn twips_to_px(twips: u32) -> String {
+    if twips == 0 {
+        return "0".to_string();
+    }
+    let px = format!("{:.2}", twips as f64 / 15.0);
+    format!("{}px", px.trim_end_matches('0').trim_end_matches('.'))
 }
 
 #[cfg(test)]
@@ -126,4 +391,515 @@ mod tests {
         let html = TableConverter::convert(&table, &mut context).expect("table conversion failed");
         assert!(html.contains("<td>SDT-CELL</td>"));
     }
+
+    #[test]
+    fn test_table_caption_and_description_are_rendered() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tblPr><w:tblCaption w:val="Q1 &lt;draft&gt;"/><w:tblDescription w:val="Revenue by region"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let BodyContent::Table(table) = &document.body.content[0] else {
+            panic!("expected a table");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let mut context = super::super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+        context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+            xml,
+            &document.body.content,
+        ));
+
+        let html = TableConverter::convert(table, &mut context).expect("table conversion failed");
+        assert!(html.starts_with(
+            "<table summary=\"Revenue by region\">\n  <caption>Q1 &lt;draft&gt;</caption>\n  <tr>"
+        ));
+    }
+
+    #[test]
+    fn test_no_html_table_caption_is_an_italic_line() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tblPr><w:tblCaption w:val="Q1 *draft* totals_2024 &gt; Q4 [est] &lt;b&gt;"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let BodyContent::Table(table) = &document.body.content[0] else {
+            panic!("expected a table");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            no_html: true,
+            ..Default::default()
+        };
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let mut context = super::super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+        context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+            xml,
+            &document.body.content,
+        ));
+
+        let markdown =
+            TableConverter::convert(table, &mut context).expect("table conversion failed");
+        assert_eq!(
+            markdown,
+            "*Q1 \\*draft\\* totals\\_2024 > Q4 [est] \\<b>*\n\n| A |\n| --- |"
+        );
+    }
+
+    #[test]
+    fn test_first_column_emphasis_renders_row_headers() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tblPr><w:tblLook w:val="04A0" w:firstRow="1" w:lastRow="0" w:firstColumn="1" w:lastColumn="0"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>North</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>4</w:t></w:r></w:p></w:tc></w:tr>
+                <w:tr><w:tc><w:p><w:r><w:t>South</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>2</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+            <w:tbl>
+                <w:tblPr><w:tblLook w:val="0080"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>East</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let (BodyContent::Table(table), BodyContent::Table(legacy)) =
+            (&document.body.content[0], &document.body.content[1])
+        else {
+            panic!("expected two tables");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        for table_row_headers in [true, false] {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                table_row_headers,
+                ..Default::default()
+            };
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+
+            let html =
+                TableConverter::convert(table, &mut context).expect("table conversion failed");
+            let legacy_html =
+                TableConverter::convert(legacy, &mut context).expect("table conversion failed");
+            if table_row_headers {
+                assert_eq!(
+                    html,
+                    "<table>\n\
+                     \x20 <tr>\n    <th scope=\"row\">North</th>\n    <td>4</td>\n  </tr>\n\
+                     \x20 <tr>\n    <th scope=\"row\">South</th>\n    <td>2</td>\n  </tr>\n\
+                     </table>"
+                );
+                assert!(legacy_html.contains("<th scope=\"row\">East</th>"));
+            } else {
+                assert!(!html.contains("<th") && !legacy_html.contains("<th"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_column_emphasis_bolds_pipe_table_cells() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tblPr><w:tblLook w:val="04A0" w:firstRow="1" w:lastRow="0" w:firstColumn="1" w:lastColumn="0"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>North</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>4</w:t></w:r></w:p></w:tc></w:tr>
+                <w:tr><w:tc><w:p/></w:tc><w:tc><w:p><w:r><w:t>2</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+            <w:tbl>
+                <w:tblPr><w:tblLook w:val="04A0" w:firstRow="1" w:lastRow="0" w:firstColumn="0" w:lastColumn="0"/></w:tblPr><w:tblGrid/>
+                <w:tr><w:tc><w:p><w:r><w:t>East</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let (BodyContent::Table(table), BodyContent::Table(plain)) =
+            (&document.body.content[0], &document.body.content[1])
+        else {
+            panic!("expected two tables");
+        };
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+
+        for pipe_table_row_headers in [true, false] {
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                no_html: true,
+                pipe_table_row_headers,
+                ..Default::default()
+            };
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+
+            let markdown =
+                TableConverter::convert(table, &mut context).expect("table conversion failed");
+            let plain_markdown =
+                TableConverter::convert(plain, &mut context).expect("table conversion failed");
+            if pipe_table_row_headers {
+                assert_eq!(markdown, "| **North** | 4 |\n| --- | --- |\n|  | 2 |");
+            } else {
+                assert_eq!(markdown, "| North | 4 |\n| --- | --- |\n|  | 2 |");
+            }
+            assert_eq!(plain_markdown, "| East |\n| --- |");
+        }
+    }
+
+    #[test]
+    fn test_cell_margins_render_as_padding() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tblPr><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr><w:tblGrid/>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Default</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:tcPr><w:tcMar><w:top w:w="150" w:type="dxa"/><w:start w:w="0" w:type="nil"/><w:bottom w:w="150" w:type="dxa"/></w:tcMar></w:tcPr><w:p><w:r><w:t>Own</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let BodyContent::Table(table) = &document.body.content[0] else {
+            panic!("expected a table");
+        };
+
+        for table_cell_padding in [true, false] {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                table_cell_padding,
+                ..Default::default()
+            };
+            let style_resolver = super::super::StyleResolver::new(&docx.styles);
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            context.set_raw_index(super::super::raw_xml::RawDocumentIndex::build(
+                xml,
+                &document.body.content,
+            ));
+
+            let html =
+                TableConverter::convert(table, &mut context).expect("table conversion failed");
+            if table_cell_padding {
+                assert!(
+                    html.contains(
+                        "<td style=\"padding-right:7.2px;padding-left:7.2px\">Default</td>"
+                    ),
+                    "{html}"
+                );
+                assert!(
+                    html.contains("<td style=\"padding:10px 7.2px 10px 0\">Own</td>"),
+                    "{html}"
+                );
+            } else {
+                assert!(!html.contains("style="), "{html}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_html_renders_pipe_table() {
+        let top_master = TableCell::paragraph(Paragraph::default().push_text("TOP"))
+            .property(TableCellProperty::default().grid_span(GridSpan { val: 2 }));
+        let mut two_lines = TableCell::paragraph(Paragraph::default().push_text("a|b"));
+        two_lines.content.push(TableCellContent::Paragraph(
+            Paragraph::default().push_text("c"),
+        ));
+        let table = Table::default()
+            .push_row(TableRow::default().push_cell(top_master))
+            .push_row(
+                TableRow::default()
+                    .push_cell(two_lines)
+                    .push_cell(TableCell::paragraph(Paragraph::default())),
+            );
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = ConvertOptions {
+            no_html: true,
+            ..Default::default()
+        };
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let mut context = super::super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let markdown =
+            TableConverter::convert(&table, &mut context).expect("table conversion failed");
+        assert_eq!(markdown, "| TOP |  |\n| --- | --- |\n| a\\|b c |  |");
+    }
+
+    #[test]
+    fn test_grid_column_widths_render_as_percentages() {
+        let mut table = Table::default().push_row(
+            TableRow::default()
+                .push_cell(TableCell::paragraph(Paragraph::default().push_text("A")))
+                .push_cell(TableCell::paragraph(Paragraph::default().push_text("B")))
+                .push_cell(TableCell::paragraph(Paragraph::default().push_text("C"))),
+        );
+        table.grids = vec![4500, 2250, 2250].into();
+
+        let convert = |table: &Table, table_column_widths| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                table_column_widths,
+                ..Default::default()
+            };
+            let style_resolver = super::super::StyleResolver::new(&docx.styles);
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            TableConverter::convert(table, &mut context).expect("table conversion failed")
+        };
+
+        assert!(convert(&table, true).starts_with(
+            "<table>\n  <colgroup>\n\
+             \x20   <col style=\"width:50%\">\n\
+             \x20   <col style=\"width:25%\">\n\
+             \x20   <col style=\"width:25%\">\n\
+             \x20 </colgroup>\n  <tr>"
+        ));
+        assert!(!convert(&table, false).contains("<col"));
+
+        table.grids = vec![1000, 2000].into();
+        assert!(convert(&table, true).contains("<col style=\"width:33.33%\">"));
+        table.grids = Default::default();
+        assert!(!convert(&table, true).contains("<colgroup>"));
+    }
+
+    #[test]
+    fn test_cell_text_is_html_escaped() {
+        let table = Table::default().push_row(
+            TableRow::default()
+                .push_cell(TableCell::paragraph(
+                    Paragraph::default().push_text("<script>alert(1)</script>"),
+                ))
+                .push_cell(TableCell::paragraph(
+                    Paragraph::default().push_text("a < b & c"),
+                )),
+        );
+
+        let docx = rs_docx::Docx::default();
+        let rels = HashMap::new();
+        let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+        let mut image_extractor = super::super::ImageExtractor::new_skip();
+        let options = ConvertOptions::default();
+        let style_resolver = super::super::StyleResolver::new(&docx.styles);
+        let mut context = super::super::ConversionContext::new(
+            &rels,
+            &mut numbering_resolver,
+            &mut image_extractor,
+            &options,
+            None,
+            None,
+            None,
+            &style_resolver,
+        );
+
+        let html = TableConverter::convert(&table, &mut context).expect("table conversion failed");
+        assert!(html.contains("<td>&lt;script&gt;alert(1)&lt;/script&gt;</td>"));
+        assert!(html.contains("<td>a &lt; b &amp; c</td>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_cell_text_is_trimmed() {
+        let mut second = TableCell::paragraph(Paragraph::default().push_text("  first"));
+        second.content.push(TableCellContent::Paragraph(
+            Paragraph::default().push_text("second\t "),
+        ));
+        let table = Table::default().push_row(
+            TableRow::default()
+                .push_cell(TableCell::paragraph(
+                    Paragraph::default().push_text("   padded  "),
+                ))
+                .push_cell(second),
+        );
+
+        let convert = |trim_table_cells| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            // Without preserve_whitespace, paragraph text is trimmed anyway.
+            let options = ConvertOptions {
+                preserve_whitespace: true,
+                trim_table_cells,
+                ..Default::default()
+            };
+            let style_resolver = super::super::StyleResolver::new(&docx.styles);
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            TableConverter::convert(&table, &mut context).expect("table conversion failed")
+        };
+
+        let html = convert(true);
+        assert!(html.contains("<td>padded</td>"), "{html}");
+        assert!(html.contains("<td>first<br/>second</td>"), "{html}");
+        let html = convert(false);
+        assert!(html.contains("<td>   padded  </td>"), "{html}");
+    }
+
+    #[test]
+    fn test_pretty_html_indents_nested_tables() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid/><w:tr>
+                <w:tc>
+                    <w:p><w:r><w:t>Before</w:t></w:r></w:p>
+                    <w:tbl><w:tblGrid/><w:tr>
+                        <w:tc><w:p><w:r><w:t>x &amp; y</w:t></w:r></w:p></w:tc>
+                        <w:tc><w:p><w:r><w:t>z</w:t></w:r></w:p></w:tc>
+                    </w:tr></w:tbl>
+                    <w:p><w:r><w:t>After</w:t></w:r></w:p>
+                </w:tc>
+                <w:tc><w:p><w:r><w:t>Plain</w:t></w:r></w:p></w:tc>
+            </w:tr></w:tbl>
+        </w:body></w:document>"#;
+        let document = <rs_docx::document::Document as hard_xml::XmlRead>::from_str(xml)
+            .expect("document should parse");
+        let BodyContent::Table(table) = &document.body.content[0] else {
+            panic!("expected a table");
+        };
+
+        let convert = |pretty_html| {
+            let docx = rs_docx::Docx::default();
+            let rels = HashMap::new();
+            let mut numbering_resolver = super::super::NumberingResolver::new(&docx);
+            let mut image_extractor = super::super::ImageExtractor::new_skip();
+            let options = ConvertOptions {
+                pretty_html,
+                ..Default::default()
+            };
+            let style_resolver = super::super::StyleResolver::new(&docx.styles);
+            let mut context = super::super::ConversionContext::new(
+                &rels,
+                &mut numbering_resolver,
+                &mut image_extractor,
+                &options,
+                None,
+                None,
+                None,
+                &style_resolver,
+            );
+            TableConverter::convert(table, &mut context).expect("table conversion failed")
+        };
+
+        assert_eq!(
+            convert(true),
+            "<table>\n\
+             \x20 <tr>\n\
+             \x20   <td>\n\
+             \x20     Before\n\
+             \x20     <table>\n\
+             \x20       <tr>\n\
+             \x20         <td>x &amp; y</td>\n\
+             \x20         <td>z</td>\n\
+             \x20       </tr>\n\
+             \x20     </table>\n\
+             \x20     <br/>After\n\
+             \x20   </td>\n\
+             \x20   <td>Plain</td>\n\
+             \x20 </tr>\n\
+             </table>"
+        );
+        // Without it, the nested table stays inside the cell's line.
+        assert!(convert(false).contains("    <td>Before<table>\n  <tr>\n    <td>x &amp; y</td>"));
+    }
 }
@@ -1,10 +1,13 @@
+use crate::render::escape_html_attr;
 use crate::Result;
-use rs_docx::document::{BodyContent, Table, TableCell};
+use rs_docx::document::{BodyContent, Table, TableCell, TableGrid};
 
 #[derive(Clone, Debug)]
 pub(crate) enum CellStatus {
     Occupied {
         content: String,
+        /// Inline CSS for the cell's `style` attribute (HTML tables only).
+        style: Option<String>,
         rowspan: usize,
         colspan: usize,
     },
@@ -13,12 +16,16 @@ pub(crate) enum CellStatus {
     Empty,
 }
 
+/// Places the cells of `table` on a grid, resolving spans and merges.
+///
+/// `convert_cell` returns the content of a cell and its inline style; it is
+/// not called for cells that continue a vertical merge.
 pub(crate) fn build_grid<'a, F>(
     table: &Table<'a>,
     mut convert_cell: F,
 ) -> Result<Vec<Vec<CellStatus>>>
 where
-    F: FnMut(&TableCell<'a>) -> Result<String>,
+    F: FnMut(&TableCell<'a>) -> Result<(String, Option<String>)>,
 {
     let mut grid: Vec<Vec<CellStatus>> = Vec::new();
 
@@ -63,7 +70,7 @@ fn place_cell<'a, F>(
     convert_cell: &mut F,
 ) -> Result<()>
 where
-    F: FnMut(&TableCell<'a>) -> Result<String>,
+    F: FnMut(&TableCell<'a>) -> Result<(String, Option<String>)>,
 {
     while *col_idx < grid[row_idx].len() && !matches!(grid[row_idx][*col_idx], CellStatus::Empty) {
         *col_idx += 1;
@@ -84,8 +91,8 @@ where
     let is_v_merge_restart = matches!(v_merge_val, Some(rs_docx::formatting::VMergeType::Restart));
     let is_v_merge_continue = cell.property.v_merge.is_some() && !is_v_merge_restart;
 
-    let content = if is_v_merge_continue {
-        String::new()
+    let (content, style) = if is_v_merge_continue {
+        (String::new(), None)
     } else {
         convert_cell(cell)?
     };
@@ -102,6 +109,7 @@ where
             *col_idx,
             CellStatus::Occupied {
                 content,
+                style,
                 rowspan: 1,
                 colspan: grid_span,
             },
@@ -115,14 +123,53 @@ where
     Ok(())
 }
 
-pub(crate) fn render_grid(grid: Vec<Vec<CellStatus>>) -> String {
-    let mut html = String::from("<table>\n");
+/// Renders the grid as an HTML table; blank cells get `empty_cell` as content.
+///
+/// With `row_headers`, cells starting in the first column become `<th scope="row">`.
+/// Non-empty `column_widths` (percentages) are written as a `<colgroup>`.
+/// With `right_to_left`, columns are written in visual order for a
+/// right-to-left table: the first column last.
+pub(crate) fn render_grid(
+    mut grid: Vec<Vec<CellStatus>>,
+    caption: Option<&str>,
+    description: Option<&str>,
+    column_widths: &[f64],
+    empty_cell: &str,
+    row_headers: bool,
+    right_to_left: bool,
+) -> String {
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let mut column_widths = column_widths.to_vec();
+    if right_to_left {
+        reverse_columns(&mut grid);
+        column_widths.reverse();
+    }
+    let mut html = match description {
+        Some(description) => format!("<table summary=\"{}\">\n", escape_html_attr(description)),
+        None => String::from("<table>\n"),
+    };
+    if let Some(caption) = caption {
+        html.push_str(&format!(
+            "  <caption>{}</caption>\n",
+            escape_html_attr(caption)
+        ));
+    }
+    if !column_widths.is_empty() {
+        html.push_str("  <colgroup>\n");
+        for width in &column_widths {
+            let width = format!("{:.2}", width);
+            let width = width.trim_end_matches('0').trim_end_matches('.');
+            html.push_str(&format!("    <col style=\"width:{}%\">\n", width));
+        }
+        html.push_str("  </colgroup>\n");
+    }
     for row in grid {
         html.push_str("  <tr>\n");
-        for cell in row {
+        for (col, cell) in row.into_iter().enumerate() {
             match cell {
                 CellStatus::Occupied {
                     content,
+                    style,
                     rowspan,
                     colspan,
                 } => {
@@ -133,11 +180,31 @@ pub(crate) fn render_grid(grid: Vec<Vec<CellStatus>>) -> String {
                     if colspan > 1 {
                         attrs.push_str(&format!(" colspan=\"{}\"", colspan));
                     }
-                    html.push_str(&format!("    <td{}>{}</td>\n", attrs, content));
+                    if let Some(style) = style {
+                        attrs.push_str(&format!(" style=\"{}\"", style));
+                    }
+                    let content = if content.trim().is_empty() {
+                        empty_cell
+                    } else {
+                        &content
+                    };
+                    let first_column = if right_to_left {
+                        col + colspan == columns
+                    } else {
+                        col == 0
+                    };
+                    if row_headers && first_column {
+                        html.push_str(&format!(
+                            "    <th scope=\"row\"{}>{}</th>\n",
+                            attrs, content
+                        ));
+                    } else {
+                        html.push_str(&format!("    <td{}>{}</td>\n", attrs, content));
+                    }
                 }
                 CellStatus::MergedLeft | CellStatus::MergedUp => {}
                 CellStatus::Empty => {
-                    html.push_str("    <td></td>\n");
+                    html.push_str(&format!("    <td>{}</td>\n", empty_cell));
                 }
             }
         }
@@ -147,6 +214,166 @@ pub(crate) fn render_grid(grid: Vec<Vec<CellStatus>>) -> String {
     html
 }
 
+/// Re-indents an HTML table from [`render_grid`] so the tables nested in its
+/// cells are indented like it, two spaces per level.
+///
+/// A cell holding a table is split over several lines: the table, and each
+/// piece of content before or after it, on lines of their own one level
+/// deeper than the cell. Other cells stay on one line, and no whitespace is
+/// added inside the content of a cell.
+pub(crate) fn indent_nested_tables(table: &str) -> String {
+    let mut html = String::new();
+    write_indented_table(table, 0, &mut html);
+    html.truncate(html.trim_end().len());
+    html
+}
+
+fn write_indented_table(table: &str, level: usize, html: &mut String) {
+    let mut rest = table;
+    while let Some(start) = rest.find('<') {
+        let tag_end = rest[start..]
+            .find('>')
+            .map_or(rest.len(), |end| start + end + 1);
+        let tag = &rest[start..tag_end];
+        let name = tag_name(tag);
+        if !matches!(name, "td" | "th" | "caption") || tag.starts_with("</") {
+            let depth = match name {
+                "table" => 0,
+                "col" => 2,
+                _ => 1,
+            };
+            push_line(html, level + depth, tag);
+            rest = &rest[tag_end..];
+            continue;
+        }
+
+        let close = tag_end + find_close(&rest[tag_end..], name);
+        let close_end = (close + name.len() + 3).min(rest.len());
+        let mut content = &rest[tag_end..close];
+        let depth = if name == "caption" { 1 } else { 2 };
+        if find_open(content, "table").is_none() {
+            push_line(html, level + depth, &rest[start..close_end]);
+            rest = &rest[close_end..];
+            continue;
+        }
+        push_line(html, level + depth, tag);
+        while let Some(table_start) = find_open(content, "table") {
+            if table_start > 0 {
+                push_line(html, level + depth + 1, &content[..table_start]);
+            }
+            let after_tag = table_start + 1;
+            let table_end =
+                (after_tag + find_close(&content[after_tag..], "table") + 8).min(content.len());
+            write_indented_table(&content[table_start..table_end], level + depth + 1, html);
+            content = &content[table_end..];
+        }
+        if !content.is_empty() {
+            push_line(html, level + depth + 1, content);
+        }
+        push_line(html, level + depth, &rest[close..close_end]);
+        rest = &rest[close_end..];
+    }
+}
+
+fn push_line(html: &mut String, level: usize, text: &str) {
+    html.push_str(&"  ".repeat(level));
+    html.push_str(text);
+    html.push('\n');
+}
+
+/// Returns the element name of a start or end tag, e.g. `td` for
+/// `<td colspan="2">` and `</td>`.
+fn tag_name(tag: &str) -> &str {
+    let name = tag.trim_start_matches('<').trim_start_matches('/');
+    let end = name
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(name.len());
+    &name[..end]
+}
+
+/// Returns the offset of the first `name` start tag in `html`.
+fn find_open(html: &str, name: &str) -> Option<usize> {
+    let open = format!("<{}", name);
+    html.match_indices(&open)
+        .map(|(idx, _)| idx)
+        .find(|&idx| tag_name(&html[idx..]) == name)
+}
+
+/// Returns the offset of the end tag closing a `name` element whose content
+/// starts `html`, skipping elements of the same name nested in it, or the
+/// length of `html` when it is not closed.
+fn find_close(html: &str, name: &str) -> usize {
+    let mut depth = 0;
+    let mut offset = 0;
+    while let Some(idx) = html[offset..].find('<') {
+        let idx = offset + idx;
+        let tag = &html[idx..];
+        if tag_name(tag) == name {
+            if tag.starts_with("</") {
+                if depth == 0 {
+                    return idx;
+                }
+                depth -= 1;
+            } else {
+                depth += 1;
+            }
+        }
+        offset = idx + 1;
+    }
+    html.len()
+}
+
+/// Makes the non-blank cells of the first column bold, as Markdown.
+pub(crate) fn bold_first_column(grid: &mut [Vec<CellStatus>]) {
+    for row in grid.iter_mut() {
+        if let Some(CellStatus::Occupied { content, .. }) = row.first_mut() {
+            if !content.trim().is_empty() {
+                *content = format!("**{}**", content.trim());
+            }
+        }
+    }
+}
+
+/// Reverses the column order of every row, padding short rows with empty
+/// cells. A merged cell moves to the left end of the positions it spans.
+pub(crate) fn reverse_columns(grid: &mut [Vec<CellStatus>]) {
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    for row in grid.iter_mut() {
+        let mut reversed = vec![CellStatus::Empty; columns];
+        for (col, cell) in std::mem::take(row).into_iter().enumerate() {
+            match cell {
+                CellStatus::Occupied { colspan, .. } => {
+                    let start = columns.saturating_sub(col + colspan.max(1));
+                    for merged in reversed.iter_mut().skip(start + 1).take(colspan.max(1) - 1) {
+                        *merged = CellStatus::MergedLeft;
+                    }
+                    reversed[start] = cell;
+                }
+                // Positions of a span are rewritten with its master cell.
+                CellStatus::MergedLeft => {}
+                CellStatus::MergedUp | CellStatus::Empty => reversed[columns - 1 - col] = cell,
+            }
+        }
+        *row = reversed;
+    }
+}
+
+/// Converts `w:gridCol` widths (twips) to percentages of the table width.
+///
+/// Returns an empty list when the grid has no columns or no positive total.
+pub(crate) fn column_percentages(grid: &TableGrid) -> Vec<f64> {
+    let widths: Vec<f64> = grid
+        .columns
+        .iter()
+        .map(|column| column.width.max(0) as f64)
+        .collect();
+    let total: f64 = widths.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    widths.iter().map(|width| width * 100.0 / total).collect()
+}
+
 fn set_grid_cell(grid: &mut Vec<Vec<CellStatus>>, row: usize, col: usize, status: CellStatus) {
     if grid.len() <= row {
         grid.resize(row + 1, Vec::new());
@@ -211,3 +438,91 @@ fn increment_rowspan(grid: &mut [Vec<CellStatus>], current_row: usize, col: usiz
         }
     }
 }
+
+/// Renders the grid as a Markdown pipe table, for output without HTML.
+///
+/// The first row becomes the header row. Pipe tables cannot merge cells, so
+/// merged-over positions stay blank; the caption, escaped like cell text,
+/// is written as an italic line above the table.
+pub(crate) fn render_pipe_table(grid: Vec<Vec<CellStatus>>, caption: Option<&str>) -> String {
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines = Vec::with_capacity(grid.len() + 3);
+    if let Some(caption) = caption.map(italic_caption) {
+        if !caption.is_empty() {
+            lines.push(format!("*{}*", caption));
+            lines.push(String::new());
+        }
+    }
+    if columns == 0 {
+        return lines.join("\n").trim_end().to_string();
+    }
+
+    for (idx, row) in grid.into_iter().enumerate() {
+        let mut cells: Vec<String> = row
+            .into_iter()
+            .map(|cell| match cell {
+                CellStatus::Occupied { content, .. } => pipe_cell(&content),
+                CellStatus::MergedLeft | CellStatus::MergedUp | CellStatus::Empty => String::new(),
+            })
+            .collect();
+        cells.resize(columns, String::new());
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if idx == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Renders the grid as plain text, one line per row with the cells separated
+/// by tabs, and the caption on a line of its own above the table. Cells that
+/// a merged cell spans are left out; merged-over rows get an empty cell.
+pub(crate) fn render_text_table(grid: Vec<Vec<CellStatus>>, caption: Option<&str>) -> String {
+    let mut lines: Vec<String> = caption
+        .map(str::trim)
+        .filter(|caption| !caption.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    for row in grid {
+        let cells: Vec<String> = row
+            .into_iter()
+            .filter_map(|cell| match cell {
+                CellStatus::Occupied { content, .. } => {
+                    Some(content.split_whitespace().collect::<Vec<_>>().join(" "))
+                }
+                CellStatus::MergedUp | CellStatus::Empty => Some(String::new()),
+                CellStatus::MergedLeft => None,
+            })
+            .collect();
+        lines.push(cells.join("\t"));
+    }
+    lines.join("\n")
+}
+
+/// Puts a caption on one line and escapes the characters that would end its
+/// italics early.
+fn italic_caption(caption: &str) -> String {
+    let mut escaped = String::with_capacity(caption.len());
+    for word in caption.split_whitespace() {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for ch in word.chars() {
+            if matches!(ch, '*' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+/// Puts cell content on one line and escapes the column separator.
+fn pipe_cell(content: &str) -> String {
+    content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
@@ -0,0 +1,139 @@
+//! Text-only documents - a lighter conversion path for plain styled prose.
+//!
+//! Most documents are nothing but styled paragraphs. `DocxFile` reads and
+//! parses every part of the package, headers, footers, themes, settings and
+//! media included, although the conversion never looks at them, and the raw
+//! XML would be read a second time for elements rs_docx drops. When the main
+//! document has no tables, images, fields, notes, comments or dropped
+//! elements, only the parts its paragraphs can refer to are parsed: styles,
+//! numbering and relationships (for hyperlinks).
+
+use super::raw_xml;
+use crate::{Error, Result};
+use hard_xml::XmlRead;
+use rs_docx::document::{Document, Numbering};
+use rs_docx::rels::Relationships;
+use rs_docx::styles::Styles;
+use rs_docx::Docx;
+use std::io::{Read, Seek};
+
+const DOCUMENT_RELS: &str = "word/_rels/document.xml.rels";
+const STYLES: &str = "word/styles.xml";
+const NUMBERING: &str = "word/numbering.xml";
+
+/// Markup that needs more of the package than the text-only path reads:
+/// tables, images and shapes, fields, note and comment references, and
+/// content wrapped in alternatives or other parts.
+const GENERAL_MARKERS: &[&str] = &[
+    "<w:tbl",
+    "<w:drawing",
+    "<w:pict",
+    "<w:object",
+    "<w:fldSimple",
+    "<w:fldChar",
+    "<w:instrText",
+    "<w:footnoteReference",
+    "<w:endnoteReference",
+    "<w:commentRangeStart",
+    "<w:commentReference",
+    "<mc:AlternateContent",
+    "<w:altChunk",
+];
+
+/// Returns whether the main document XML can be converted through the
+/// text-only path.
+pub(crate) fn is_text_only(document_xml: &str) -> bool {
+    !GENERAL_MARKERS
+        .iter()
+        .any(|marker| document_xml.contains(marker))
+        && !raw_xml::has_interesting_tags(document_xml)
+}
+
+/// The XML of the parts a text-only document is converted with.
+#[derive(Debug, Default)]
+pub(crate) struct TextOnlyParts {
+    document: String,
+    relationships: Option<String>,
+    styles: Option<String>,
+    numbering: Option<String>,
+}
+
+impl TextOnlyParts {
+    /// Reads the parts besides the main document, given as `document`, from
+    /// a DOCX archive. Missing parts are left out.
+    pub fn read<R: Read + Seek>(
+        archive: &mut zip::ZipArchive<R>,
+        document: String,
+    ) -> Result<Self> {
+        let mut read = |name| -> Result<Option<String>> {
+            let Ok(mut part) = archive.by_name(name) else {
+                return Ok(None);
+            };
+            let mut xml = String::new();
+            part.read_to_string(&mut xml)?;
+            Ok(Some(xml))
+        };
+        Ok(Self {
+            relationships: read(DOCUMENT_RELS)?,
+            styles: read(STYLES)?,
+            numbering: read(NUMBERING)?,
+            document,
+        })
+    }
+
+    /// Builds a `Docx` holding the main document, styles, numbering and
+    /// relationships; every other part is empty.
+    pub fn parse(&self) -> Result<Docx<'_>> {
+        Ok(Docx {
+            document: parse_part::<Document>(&self.document)?,
+            document_rels: self
+                .relationships
+                .as_deref()
+                .map(parse_part::<Relationships>)
+                .transpose()?,
+            styles: self
+                .styles
+                .as_deref()
+                .map(parse_part::<Styles>)
+                .transpose()?
+                .unwrap_or_default(),
+            numbering: self
+                .numbering
+                .as_deref()
+                .map(parse_part::<Numbering>)
+                .transpose()?,
+            ..Default::default()
+        })
+    }
+}
+
+fn parse_part<'a, T: XmlRead<'a>>(xml: &'a str) -> Result<T> {
+    T::from_str(xml).map_err(|e| Error::DocxParse(format!("{:?}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_plain_paragraphs_are_text_only() {
+        let document = |body: &str| {
+            format!(
+                r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}</w:body></w:document>"#,
+                body
+            )
+        };
+        assert!(is_text_only(&document(
+            r#"<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t>Title</w:t></w:r></w:p>"#
+        )));
+        assert!(!is_text_only(&document(
+            r#"<w:tbl><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>"#
+        )));
+        assert!(!is_text_only(&document(
+            r#"<w:p><w:r><w:footnoteReference w:id="1"/></w:r></w:p>"#
+        )));
+        assert!(!is_text_only(&document(
+            r#"<w:p><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath></w:p>"#
+        )));
+    }
+}
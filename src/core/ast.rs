@@ -1,3 +1,5 @@
+use crate::NoteMarkers;
+
 #[derive(Debug, Clone, Default)]
 pub struct DocumentAst {
     pub blocks: Vec<BlockNode>,
@@ -9,6 +11,9 @@ pub enum BlockNode {
     Paragraph(String),
     TableHtml(String),
     RawHtml(String),
+    /// Approximate page boundary from a `w:lastRenderedPageBreak` hint,
+    /// emitted with `ConvertOptions::page_boundaries`.
+    PageBreak,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -16,4 +21,33 @@ pub struct ReferenceDefinitions {
     pub footnotes: Vec<String>,
     pub endnotes: Vec<String>,
     pub comments: Vec<(String, String)>,
+    /// Templates the note markers in the text were written with.
+    pub markers: NoteMarkers,
+    /// Number in the marker of each footnote; footnotes past its end are
+    /// numbered by position from 1.
+    pub footnote_numbers: Vec<usize>,
+    /// Number in the marker of each endnote, like `footnote_numbers`.
+    pub endnote_numbers: Vec<usize>,
+}
+
+impl ReferenceDefinitions {
+    /// Returns the footnotes with the numbers of their markers.
+    pub fn numbered_footnotes(&self) -> impl Iterator<Item = (usize, &str)> {
+        numbered(&self.footnotes, &self.footnote_numbers)
+    }
+
+    /// Returns the endnotes with the numbers of their markers.
+    pub fn numbered_endnotes(&self) -> impl Iterator<Item = (usize, &str)> {
+        numbered(&self.endnotes, &self.endnote_numbers)
+    }
+}
+
+fn numbered<'a>(
+    notes: &'a [String],
+    numbers: &'a [usize],
+) -> impl Iterator<Item = (usize, &'a str)> {
+    notes
+        .iter()
+        .enumerate()
+        .map(|(i, note)| (numbers.get(i).copied().unwrap_or(i + 1), note.as_str()))
 }
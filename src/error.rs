@@ -34,4 +34,16 @@ pub enum Error {
     /// Media file not found in DOCX archive.
     #[error("Media not found: {0}")]
     MediaNotFound(String),
+
+    /// A conversion option has an invalid value.
+    #[error("Invalid option: {0}")]
+    InvalidOption(String),
+
+    /// Tables or content controls nest deeper than `max_nesting_depth`.
+    #[error("Nesting too deep: {0}")]
+    NestingTooDeep(String),
+
+    /// Media parts that could not be read, with `strict_media`.
+    #[error("Corrupt media: {0}")]
+    CorruptMedia(String),
 }
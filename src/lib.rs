@@ -71,11 +71,17 @@ pub mod error;
 pub mod localization;
 pub mod render;
 
-pub use converter::DocxToMarkdown;
+pub use converter::{
+    BlockIter, ConversionManifest, ConversionWarning, DocumentMetadata, DocxToMarkdown,
+    ExtractedImage, FragmentContext, ManifestImage, MarkdownWithManifest, MarkdownWithNotes,
+};
 pub use error::{Error, Result};
-pub use localization::parse_heading_style;
+pub use localization::{parse_heading_style, KoreanLocalization, LocalizationStrategy};
 
+use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Options for DOCX to Markdown conversion.
 #[derive(Debug, Clone)]
@@ -84,22 +90,363 @@ pub struct ConvertOptions {
     pub image_handling: ImageHandling,
     /// Whether to preserve exact whitespace.
     pub preserve_whitespace: bool,
-    /// Whether to use HTML for underlined text.
+    /// How tabs at the start of a paragraph, often indenting its first line,
+    /// are written. They are never kept as tabs, which Markdown would take
+    /// for an indented code block.
+    pub leading_tab: LeadingTab,
+    /// Whether to use HTML for underlined text. Only applies while
+    /// `underline_handling` is `None`: `true` then means
+    /// `UnderlineHandling::Html` and `false` `UnderlineHandling::Ignore`.
+    #[deprecated(note = "use `underline_handling` instead")]
     pub html_underline: bool,
+    /// How underlined text (`w:u`) is written. Link text is never underlined.
+    /// `None` follows the deprecated `html_underline`, which by default
+    /// ignores underlines.
+    pub underline_handling: Option<UnderlineHandling>,
     /// Whether to use HTML for strikethrough text.
     pub html_strikethrough: bool,
+    /// How tracked insertions (`w:ins`) and deletions (`w:del`) are written.
+    pub tracked_changes: TrackedChanges,
     /// Whether to fail conversion when a referenced note/comment cannot be resolved.
     pub strict_reference_validation: bool,
+    /// Whether to fail conversion when an image's media part cannot be read;
+    /// by default it becomes an `[image]` placeholder and a warning.
+    pub strict_media: bool,
+    /// How deeply tables and content controls may nest before conversion
+    /// fails with `Error::NestingTooDeep`, instead of overflowing the stack on
+    /// hostile input. Parsing by rs_docx is not covered by this limit.
+    pub max_nesting_depth: usize,
+    /// Whether to wrap runs whose language differs from the document default in `<span lang>`.
+    pub preserve_lang: bool,
+    /// How to render hidden text (`w:vanish`) and index entry (`XE`) fields.
+    pub hidden_text: HiddenText,
+    /// How to render equations that cannot be converted.
+    pub math_fallback: MathFallback,
+    /// Whether to report the Office Math structures (fractions, scripts,
+    /// radicals, ...) that equations lose in conversion, as
+    /// `ConversionWarning::UnsupportedMath` in the manifest.
+    pub warn_unsupported_math: bool,
+    /// Delimiter placed after ordered list numbers.
+    pub ordered_list_delimiter: OrderedListDelimiter,
+    /// How non-decimal list labels (letters, roman numerals, ...) are emitted.
+    pub list_labels: ListLabels,
+    /// Localizes the numbers in literal list labels, such as Korean letters
+    /// for `lowerLetter` lists, and may space CJK and Latin text apart;
+    /// `None` (the default) uses Word's formats and leaves text as is.
+    pub localization: Option<Arc<dyn LocalizationStrategy>>,
+    /// How footnote references are rendered.
+    pub footnote_style: FootnoteStyle,
+    /// Templates of the footnote, endnote and comment markers, used for the
+    /// references in the text and for the note definitions alike.
+    pub note_markers: NoteMarkers,
+    /// Whether endnotes join the footnotes in one sequence numbered in
+    /// document order, written with the footnote marker as one section.
+    pub unify_notes: bool,
+    /// Whether footnote and endnote markers keep the note's id in the
+    /// document (`w:footnoteReference w:id`) instead of being numbered from 1
+    /// in order of first reference, so they stay stable across conversions.
+    /// Cannot be combined with `unify_notes`.
+    pub preserve_note_ids: bool,
+    /// Whether footnotes with identical text share one definition: later
+    /// references to the same text get the marker of the first. Notes are
+    /// compared by their plain text, so formatting differences are ignored.
+    pub merge_identical_footnotes: bool,
+    /// How comments marked resolved in Word are rendered.
+    pub resolved_comments: ResolvedComments,
+    /// How embedded charts are rendered.
+    pub chart_handling: ChartHandling,
+    /// Whether blank table cells stay empty instead of holding `&nbsp;`.
+    pub preserve_empty_table_cells: bool,
+    /// Whether to mark multi-column sections with `<!-- columns: N -->` comments.
+    pub annotate_columns: bool,
+    /// Whether the page breaks Word recorded at its last layout
+    /// (`w:lastRenderedPageBreak`) become
+    /// [`BlockNode::PageBreak`](crate::core::ast::BlockNode::PageBreak)
+    /// blocks before the paragraphs they occur in, instead of `---` rules in
+    /// the text. These are hints: they reflect the pagination of whichever
+    /// application last saved the document and may be stale or missing.
+    pub page_boundaries: bool,
+    /// Whether first-column cells of tables whose `w:tblLook` emphasizes the
+    /// first column render as `<th scope="row">` row headers.
+    pub table_row_headers: bool,
+    /// Whether first-column cells of pipe tables (with `no_html`) whose
+    /// `w:tblLook` emphasizes the first column are bold, the pipe-table
+    /// counterpart of `table_row_headers`.
+    pub pipe_table_row_headers: bool,
+    /// Whether HTML tables get a `<colgroup>` of `<col style="width:X%">`
+    /// elements, sized from the column widths of their `w:tblGrid`.
+    pub table_column_widths: bool,
+    /// Whether HTML table cells get an inline `padding` style from their
+    /// margins (`w:tcMar`, falling back to the table's `w:tblCellMar`),
+    /// converted from twips to pixels at 96 DPI. Margins set through table
+    /// styles are not applied.
+    pub table_cell_padding: bool,
+    /// Whether tables nested in HTML table cells are indented one level
+    /// deeper than the cell, with the cell split over several lines, like the
+    /// table around them. Text inside cells is left on one line, so the
+    /// rendered content does not change.
+    pub pretty_html: bool,
+    /// Column order of right-to-left tables (`w:bidiVisual`).
+    pub rtl_table_order: RtlTableOrder,
+    /// Number of levels every heading is shifted down (H1 becomes H1+N), clamped to 1..=6.
+    pub heading_offset: u8,
+    /// Whether a paragraph with an outline level (`w:outlineLvl` N, set on
+    /// the paragraph or its style) but no heading style becomes a heading of
+    /// level N+1. Outline level 9 is body text.
+    pub outline_headings: bool,
+    /// Whether leading and trailing whitespace is trimmed from the text of
+    /// each paragraph in a table cell, even with `preserve_whitespace`.
+    pub trim_table_cells: bool,
+    /// Whether a table cell holding several paragraphs or a list keeps its
+    /// blocks in the HTML output: each paragraph in a `<p>` and list items
+    /// in `<ul>`/`<ol>`, instead of paragraphs joined with `<br/>`. Ignored
+    /// with `no_html`.
+    pub table_cell_blocks: bool,
+    /// Detection of tab-aligned paragraphs as tables; `None` (the default) disables it.
+    pub tab_tables: Option<TabTables>,
+    /// Fenced code blocks for runs of paragraphs set entirely in a monospace
+    /// font; `None` (the default) writes them as ordinary paragraphs.
+    pub code_blocks: Option<CodeBlocks>,
+    /// Names or IDs of paragraph styles whose paragraphs are written
+    /// verbatim in a fenced block: consecutive ones form one block, and their
+    /// text is neither escaped, trimmed nor reflowed.
+    pub verbatim_styles: Vec<String>,
+    /// Names or IDs of character styles whose runs are written as `<kbd>`
+    /// elements, such as a style used for key names in shortcuts.
+    pub kbd_styles: Vec<String>,
+    /// How line breaks within a paragraph (`w:br`, `w:cr`) are written.
+    pub line_break: LineBreak,
+    /// Whether line breaks within list items are written as spaces, keeping
+    /// each item on one line whatever `line_break` is.
+    pub collapse_list_item_breaks: bool,
+    /// Directory the Markdown will be written to. Links to images saved by
+    /// `ImageHandling::SaveToDir` or `ImageHandling::InlineUpTo` are made
+    /// relative to it (e.g. `./assets/image_1.png`).
+    pub markdown_output_dir: Option<PathBuf>,
+    /// Whether to keep raw HTML out of the output entirely.
+    ///
+    /// Formatting falls back to Markdown (`**`, `*`, `~~`) or is dropped
+    /// (underline, language spans, alignment), tables become pipe tables,
+    /// inline images use `![image](data:...)`, and bookmark anchors and HTML
+    /// comments are omitted. `<` in document text is escaped. This overrides
+    /// `UnderlineHandling::Html`, `html_strikethrough`, `preserve_lang`,
+    /// `HiddenText::AsComment`, `annotate_columns` and `LineBreak::Html`
+    /// (which becomes `LineBreak::Backslash`).
+    pub no_html: bool,
+    /// Which characters of extended Markdown syntax are backslash-escaped
+    /// in document text, so the target flavor reads them literally.
+    /// Formatting written by the converter, such as `~~` for strikethrough,
+    /// is never escaped.
+    pub escape_extended_syntax: ExtendedSyntaxEscaping,
+    /// Names heading anchors. When set, every heading gets an anchor with its
+    /// slug (in place of its bookmark anchors), and internal links to those
+    /// bookmarks, such as table of contents entries, point at the slug.
+    pub slug_fn: Option<SlugFn>,
+    /// How heading anchors are written. `HeadingIdStyle::Attribute` names
+    /// headings with `slug_fn`, or GitHub slugs when it is `None`.
+    pub heading_id_style: HeadingIdStyle,
+    /// What happens to a heading directly followed, with no other heading in
+    /// between, by headings of the same level and text.
+    pub repeated_headings: RepeatedHeadings,
+    /// Whether hyperlink tooltips (`w:tooltip`) become link titles, as in
+    /// `[text](url "tooltip")`.
+    pub link_titles: bool,
+    /// Paragraph styles rendered as blockquotes, such as GitHub alerts
+    /// (`> [!NOTE]`) for "Note" or "Warning" boxes; `None` (the default)
+    /// disables it.
+    pub callout_styles: Option<CalloutStyles>,
+    /// Whether an image-only paragraph directly followed by a `Caption`
+    /// paragraph becomes a `<figure>` with the caption as `<figcaption>`.
+    /// Ignored with `no_html`.
+    pub figure_captions: bool,
+    /// How the figures found by `figure_captions` are written.
+    /// `FigureSyntax::Pandoc` also applies with `no_html`.
+    pub figure_syntax: FigureSyntax,
+    /// Whether text in capitals (`w:caps`, `w:smallCaps`) is uppercased.
+    pub text_transform: TextTransform,
+    /// How phonetic guides (`w:ruby`, e.g. furigana) are written.
+    /// `no_html` implies `RubyText::Parentheses`.
+    pub ruby: RubyText,
+    /// How text recognized by smart tags (`w:smartTag`: dates, names,
+    /// places, ...) is written. `no_html` implies `SmartTags::Unwrap`.
+    pub smart_tags: SmartTags,
+    /// Replacements for glyphs of symbol fonts, keyed by font name and
+    /// character code, such as `("Corporate Icons".to_string(), 0x41)`.
+    /// They are consulted before the built-in Symbol and Wingdings tables,
+    /// for `w:sym` characters and for text in the font alike, and written
+    /// as they are. Font names are compared ignoring ASCII case, and a code
+    /// `0xXX` also matches its `0xF0XX` private-use alias.
+    pub symbol_overrides: HashMap<(String, u32), String>,
+    /// How positional tabs (`w:ptab`), which push text such as a letterhead
+    /// date to the margin, are written.
+    pub positional_tabs: PositionalTabs,
+    /// Whether the Markdown output starts with a UTF-8 byte order mark, for
+    /// Windows tools that expect one.
+    pub emit_bom: bool,
+    /// Whether embedded SVG images are written as `<svg>` markup instead of a
+    /// data URI with `ImageHandling::Inline`. Ignored with `no_html`.
+    pub inline_svg: bool,
+    /// Line ending of the output, used for every line including notes.
+    pub line_ending: LineEnding,
+    /// Line breaks written between blocks (paragraphs, headings, tables)
+    /// of the Markdown output, `"\n\n"` by default. Only `\n` and `\r`
+    /// are allowed; they are normalized and written with `line_ending` like
+    /// every other line break. A single `"\n"` suits renderers that start a
+    /// paragraph at every line.
+    pub paragraph_separator: String,
+    /// How table of contents entries are written.
+    pub toc: TocHandling,
+    /// How a generated alphabetical index (`INDEX` field) is written.
+    pub index: IndexHandling,
+    /// Whether the sources of a bibliography (`BIBLIOGRAPHY` field) are
+    /// written as a Markdown list, one item per source. Citations
+    /// (`CITATION` fields) and the sources themselves always keep the text
+    /// Word formatted them with, whatever the citation style.
+    pub bibliography_list: bool,
+    /// How tables with a single cell are written.
+    pub single_cell_tables: SingleCellTables,
+    /// How paragraphs in text frames (`w:framePr`), such as pull quotes and
+    /// sidebars, are written. Drop-cap frames are always merged into the
+    /// paragraph they start.
+    pub text_frames: TextFrames,
+    /// Where text frames are written when `text_frames` is not `Inline`.
+    pub text_frame_position: TextFramePosition,
 }
 
+#[allow(deprecated)]
 impl Default for ConvertOptions {
     fn default() -> Self {
         Self {
             image_handling: ImageHandling::Inline,
             preserve_whitespace: false,
-            html_underline: true,
+            leading_tab: LeadingTab::Strip,
+            html_underline: false,
+            underline_handling: None,
             html_strikethrough: false,
+            tracked_changes: TrackedChanges::Markup,
             strict_reference_validation: false,
+            strict_media: false,
+            max_nesting_depth: 100,
+            preserve_lang: false,
+            hidden_text: HiddenText::Drop,
+            math_fallback: MathFallback::Image,
+            warn_unsupported_math: false,
+            ordered_list_delimiter: OrderedListDelimiter::Period,
+            list_labels: ListLabels::Literal,
+            localization: None,
+            footnote_style: FootnoteStyle::Reference,
+            note_markers: NoteMarkers::default(),
+            unify_notes: false,
+            merge_identical_footnotes: false,
+            preserve_note_ids: false,
+            resolved_comments: ResolvedComments::Annotate,
+            chart_handling: ChartHandling::Table,
+            preserve_empty_table_cells: false,
+            annotate_columns: false,
+            page_boundaries: false,
+            table_row_headers: true,
+            pipe_table_row_headers: false,
+            table_column_widths: false,
+            table_cell_padding: false,
+            pretty_html: false,
+            rtl_table_order: RtlTableOrder::Logical,
+            heading_offset: 0,
+            outline_headings: true,
+            trim_table_cells: true,
+            table_cell_blocks: false,
+            tab_tables: None,
+            code_blocks: None,
+            verbatim_styles: Vec::new(),
+            kbd_styles: Vec::new(),
+            line_break: LineBreak::Newline,
+            collapse_list_item_breaks: false,
+            markdown_output_dir: None,
+            no_html: false,
+            escape_extended_syntax: ExtendedSyntaxEscaping::None,
+            slug_fn: None,
+            heading_id_style: HeadingIdStyle::HtmlAnchor,
+            repeated_headings: RepeatedHeadings::Keep,
+            link_titles: true,
+            callout_styles: None,
+            figure_captions: false,
+            figure_syntax: FigureSyntax::Html,
+            text_transform: TextTransform::Preserve,
+            ruby: RubyText::Html,
+            smart_tags: SmartTags::Unwrap,
+            symbol_overrides: HashMap::new(),
+            positional_tabs: PositionalTabs::Separator(" ".to_string()),
+            emit_bom: false,
+            inline_svg: false,
+            line_ending: LineEnding::Lf,
+            paragraph_separator: "\n\n".to_string(),
+            toc: TocHandling::Links,
+            index: IndexHandling::Drop,
+            bibliography_list: false,
+            single_cell_tables: SingleCellTables::Table,
+            text_frames: TextFrames::Inline,
+            text_frame_position: TextFramePosition::InPlace,
+        }
+    }
+}
+
+impl ConvertOptions {
+    /// Preset for GitHub Flavored Markdown: `~~strikethrough~~` with `~`
+    /// in text escaped, GitHub alerts for callout styles
+    /// (`CalloutStyles::default()`), `<a id>` heading anchors and backslash
+    /// hard line breaks. Everything else is the default.
+    ///
+    /// ```
+    /// use dm2xcod::{ConvertOptions, ImageHandling};
+    ///
+    /// let options = ConvertOptions {
+    ///     image_handling: ImageHandling::SaveToDir("./images".into()),
+    ///     ..ConvertOptions::github()
+    /// };
+    /// assert!(options.callout_styles.is_some());
+    /// ```
+    pub fn github() -> Self {
+        Self {
+            html_strikethrough: false,
+            escape_extended_syntax: ExtendedSyntaxEscaping::Tilde,
+            callout_styles: Some(CalloutStyles::default()),
+            heading_id_style: HeadingIdStyle::HtmlAnchor,
+            line_break: LineBreak::Backslash,
+            ..Self::default()
+        }
+    }
+
+    /// Preset for Pandoc Markdown: `[^n]` footnotes, `{#slug}` heading
+    /// attributes, `~~strikethrough~~` with `~`, `^` and `==` in text
+    /// escaped, backslash hard line breaks, equations as their raw text and
+    /// captioned images as Pandoc figures. Everything else is the default.
+    pub fn pandoc() -> Self {
+        Self {
+            html_strikethrough: false,
+            escape_extended_syntax: ExtendedSyntaxEscaping::All,
+            footnote_style: FootnoteStyle::Reference,
+            heading_id_style: HeadingIdStyle::Attribute,
+            line_break: LineBreak::Backslash,
+            math_fallback: MathFallback::RawText,
+            figure_captions: true,
+            figure_syntax: FigureSyntax::Pandoc,
+            ..Self::default()
+        }
+    }
+
+    /// Preset for Markdown without HTML and little formatting: `no_html`,
+    /// no images, footnotes inline in parentheses, equations as their raw
+    /// text and phonetic guides in parentheses. Everything else is the
+    /// default.
+    pub fn plain() -> Self {
+        Self {
+            no_html: true,
+            image_handling: ImageHandling::Skip,
+            underline_handling: Some(UnderlineHandling::Ignore),
+            html_strikethrough: false,
+            footnote_style: FootnoteStyle::Inline,
+            math_fallback: MathFallback::RawText,
+            ruby: RubyText::Parentheses,
+            ..Self::default()
         }
     }
 }
@@ -111,10 +458,650 @@ pub enum ImageHandling {
     SaveToDir(PathBuf),
     /// Embed images as base64 data URIs.
     Inline,
+    /// Embed images of at most `max_bytes` as base64 data URIs and save
+    /// larger ones to `dir`, like `SaveToDir`.
+    InlineUpTo { max_bytes: usize, dir: PathBuf },
     /// Skip images entirely.
     Skip,
 }
 
+/// Specifies how hidden text (`w:vanish`, index entry fields) is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HiddenText {
+    /// Omit hidden text.
+    Drop,
+    /// Render hidden text like visible text.
+    Include,
+    /// Render hidden text as an HTML comment (`<!-- ... -->`).
+    AsComment,
+}
+
+/// Specifies how equations that cannot be converted are rendered.
+///
+/// This covers Office Math (OMML) and legacy equation objects, such as
+/// MathType or Equation Editor 3.0, which only have a preview image and no
+/// raw text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathFallback {
+    /// Emit an `[equation]` placeholder, or `[equation: description]` when
+    /// the equation's preview image has alt text.
+    Placeholder,
+    /// Emit the equation's raw text.
+    RawText,
+    /// Emit the equation's rendered preview image (honoring `image_handling`)
+    /// with its alt text, falling back to raw text when there is none.
+    Image,
+}
+
+/// Specifies the delimiter that follows an ordered list number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderedListDelimiter {
+    /// `1.`
+    Period,
+    /// `1)`
+    Paren,
+}
+
+impl OrderedListDelimiter {
+    /// Returns the delimiter character.
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Period => '.',
+            Self::Paren => ')',
+        }
+    }
+}
+
+/// Specifies how list labels that Markdown cannot number natively are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListLabels {
+    /// Emit the label as Word displays it (e.g., `a)`, `iv.`, `1.2.`).
+    Literal,
+    /// Map every numbered label to a Markdown ordered-list number (e.g., `a)` becomes `1.`).
+    Markdown,
+}
+
+/// Specifies how underlined text (`w:u`) is written, as Markdown has no
+/// underline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnderlineHandling {
+    /// Drop the underline and keep the text.
+    Ignore,
+    /// An HTML `<u>` element; ignored with `no_html`.
+    Html,
+    /// Italics, as some style guides underline for emphasis.
+    Emphasis,
+}
+
+/// Specifies how footnotes are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FootnoteStyle {
+    /// Emit a `[^n]` marker and collect the note text at the end of the document.
+    Reference,
+    /// Replace the marker with the note text in parentheses (` (note text)`).
+    Inline,
+}
+
+/// Specifies how comments marked resolved (done) in Word are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolvedComments {
+    /// Label the comment text `(resolved)`; open comments are unlabelled.
+    Annotate,
+    /// Render resolved comments like open ones.
+    Include,
+    /// Leave resolved comments out, with their markers in the text, so only
+    /// open threads remain.
+    Skip,
+}
+
+/// Specifies how embedded charts are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartHandling {
+    /// Emit the chart's cached series data as a table.
+    Table,
+    /// Emit the chart's preview image (honoring `image_handling`),
+    /// falling back to the data table when there is none.
+    Image,
+}
+
+/// Settings for rendering runs of tab-aligned paragraphs as tables.
+///
+/// Consecutive paragraphs with the same number of tab-separated columns form a
+/// region. Its confidence is the share of rows using the most common tab layout
+/// times the share of non-blank cells, and is emitted before the table as
+/// `<!-- tab table: confidence N -->`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TabTables {
+    /// Minimum number of consecutive rows in a region.
+    pub min_rows: usize,
+    /// Minimum confidence (`0.0..=1.0`) a region needs to become a table.
+    pub min_confidence: f32,
+}
+
+impl Default for TabTables {
+    fn default() -> Self {
+        Self {
+            min_rows: 3,
+            min_confidence: 0.8,
+        }
+    }
+}
+
+/// Settings for writing monospace paragraphs as fenced code blocks.
+///
+/// A paragraph is code when every run with text resolves to a monospace font,
+/// directly or through its styles. Consecutive code paragraphs, including
+/// empty lines between them, form one block; their text is written verbatim.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeBlocks {
+    /// Language written in the info string of the opening fence, e.g. `rust`.
+    pub language: Option<String>,
+    /// How the lines of a block are numbered.
+    pub line_numbers: CodeLineNumbers,
+}
+
+/// Specifies how the lines of a fenced code block are numbered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CodeLineNumbers {
+    /// No line numbers.
+    #[default]
+    None,
+    /// A `{.numberLines}` attribute in the info string, for renderers such as
+    /// Pandoc that number the lines themselves.
+    InfoString,
+    /// Literal right-aligned numbers before each line (` 9  `, `10  `).
+    Prefix,
+}
+
+/// Templates of note markers, such as `[^fn-{n}]` for footnotes.
+///
+/// `{n}` is replaced by the 1-based footnote or endnote number and `{id}` by
+/// the comment id. The definition of a note is its marker followed by `: `,
+/// so every template must be a Markdown footnote label (`[^...]`).
+///
+/// ```
+/// use dm2xcod::NoteMarkers;
+///
+/// let markers = NoteMarkers {
+///     footnote: "[^fn-{n}]".to_string(),
+///     ..Default::default()
+/// };
+/// assert!(markers.validate().is_ok());
+/// assert_eq!(markers.footnote(2), "[^fn-2]");
+/// assert_eq!(markers.comment("7"), "[^c7]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteMarkers {
+    /// Footnote marker, with `{n}`.
+    pub footnote: String,
+    /// Endnote marker, with `{n}`.
+    pub endnote: String,
+    /// Comment marker, with `{id}`.
+    pub comment: String,
+}
+
+impl NoteMarkers {
+    /// Returns the marker of the `n`th footnote.
+    pub fn footnote(&self, n: usize) -> String {
+        self.footnote.replace("{n}", &n.to_string())
+    }
+
+    /// Returns the marker of the `n`th endnote.
+    pub fn endnote(&self, n: usize) -> String {
+        self.endnote.replace("{n}", &n.to_string())
+    }
+
+    /// Returns the marker of the comment with id `id`.
+    pub fn comment(&self, id: &str) -> String {
+        self.comment.replace("{id}", id)
+    }
+
+    /// Checks that every template is a footnote label (`[^...]` without
+    /// spaces or brackets inside) holding its placeholder, and that the
+    /// templates differ, so each marker resolves to exactly one definition.
+    pub fn validate(&self) -> Result<()> {
+        let templates = [
+            ("footnote", &self.footnote, "{n}"),
+            ("endnote", &self.endnote, "{n}"),
+            ("comment", &self.comment, "{id}"),
+        ];
+        for (kind, template, placeholder) in templates {
+            let label = template
+                .strip_prefix("[^")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .filter(|label| !label.contains(|c: char| c.is_whitespace() || "[]^".contains(c)));
+            if !label.is_some_and(|label| label.contains(placeholder)) {
+                return Err(Error::InvalidOption(format!(
+                    "{} marker {:?} must look like [^...{}]",
+                    kind, template, placeholder
+                )));
+            }
+        }
+        if self.footnote == self.endnote
+            || self.footnote.replace("{n}", "{id}") == self.comment
+            || self.endnote.replace("{n}", "{id}") == self.comment
+        {
+            return Err(Error::InvalidOption(
+                "footnote, endnote and comment markers must differ".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for NoteMarkers {
+    fn default() -> Self {
+        Self {
+            footnote: "[^{n}]".to_string(),
+            endnote: "[^en{n}]".to_string(),
+            comment: "[^c{id}]".to_string(),
+        }
+    }
+}
+
+/// Specifies how text formatted as all caps (`w:caps`) or small caps
+/// (`w:smallCaps`) is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextTransform {
+    /// Keep the stored casing.
+    Preserve,
+    /// Uppercase the text, as Word displays it. Small caps are uppercased too,
+    /// since Markdown has no smaller capitals. This is lossy: the stored
+    /// casing cannot be recovered from the output.
+    Apply,
+}
+
+/// Specifies how phonetic guides (`w:ruby`) such as furigana or pinyin are
+/// written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RubyText {
+    /// `<ruby>漢字<rt>かんじ</rt></ruby>`.
+    Html,
+    /// The annotation in parentheses after the base text: `漢字(かんじ)`.
+    Parentheses,
+}
+
+/// Specifies how the tabs a paragraph starts with (`w:tab`) are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeadingTab {
+    /// Leave them out.
+    Strip,
+    /// Write this prefix for each tab, e.g. `&emsp;`. It must not start with
+    /// a space or a tab.
+    Prefix(String),
+}
+
+impl LeadingTab {
+    /// Checks that a prefix cannot indent the paragraph into a code block.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Prefix(prefix) if prefix.starts_with([' ', '\t']) => {
+                Err(Error::InvalidOption(format!(
+                    "leading tab prefix {:?} must not start with a space or tab",
+                    prefix
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Specifies how the text of a smart tag (`w:smartTag`) is written. Its runs
+/// are converted like any others either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmartTags {
+    /// Write the text alone.
+    Unwrap,
+    /// Wrap the text in a `<span>` naming the recognized type:
+    /// `<span data-smart-tag="date">March 14, 2024</span>`.
+    Annotate,
+}
+
+/// Specifies how positional tabs (`w:ptab`) are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionalTabs {
+    /// Write the given text in place of each tab, e.g. `" "` or `" | "`.
+    Separator(String),
+    /// Write a paragraph split by positional tabs, the common "left text ...
+    /// right text" line, as a one-row HTML table with a cell per part, each
+    /// aligned like the tab before it. Headings, list items and `no_html`
+    /// output get a space instead.
+    Columns,
+}
+
+/// Specifies how a table with a single row holding a single cell is written.
+///
+/// Such tables are often only a box drawn around some text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingleCellTables {
+    /// Write them as tables, like any other.
+    Table,
+    /// Write the cell's content as regular blocks: paragraphs, list items
+    /// and nested tables.
+    Unwrap,
+    /// Write the cell's content as regular blocks inside a blockquote.
+    Quote,
+}
+
+/// Specifies how the paragraphs of a text frame are written. Consecutive
+/// framed paragraphs form one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFrames {
+    /// Write them as regular paragraphs, where they are in the document.
+    Inline,
+    /// Write each frame as a blockquote.
+    Quote,
+    /// Write each frame as an `<aside>` element; a blockquote with `no_html`.
+    Aside,
+}
+
+/// Specifies where a text frame is written. Word anchors a frame to the
+/// paragraph following it, which the frame floats beside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFramePosition {
+    /// Where the frame is in the document, before its anchoring paragraph.
+    InPlace,
+    /// After the anchoring paragraph (or table), so the text it floats
+    /// beside is not interrupted before it starts.
+    AfterAnchor,
+}
+
+/// Specifies how the anchor of a heading is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingIdStyle {
+    /// An `<a id="slug"></a>` line before the heading, only when `slug_fn`
+    /// is set.
+    HtmlAnchor,
+    /// A Pandoc/kramdown attribute after the heading text
+    /// (`## Heading {#slug}`), also written with `no_html`.
+    Attribute,
+}
+
+/// Specifies which extended syntax characters in document text are escaped
+/// (see `escape_extended_syntax`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedSyntaxEscaping {
+    /// Nothing beyond what CommonMark needs: `x^2` and `a ~ b` are written
+    /// as they are.
+    None,
+    /// `~`, which GitHub Flavored Markdown reads as strikethrough (`~a~`).
+    Tilde,
+    /// `~`, `^` and runs of `=`, which Pandoc and similar flavors read as
+    /// strikethrough or subscript, superscript (`x^2^`) and highlight
+    /// (`==a==`).
+    All,
+}
+
+/// Specifies how a captioned figure (see `figure_captions`) is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FigureSyntax {
+    /// A `<figure>` holding the images and a `<figcaption>`.
+    Html,
+    /// A Pandoc implicit figure: the image alone in a paragraph with the
+    /// caption as its description and a `pandoc-crossref` id, as in
+    /// `![Caption](media/image1.png){#fig:1}`. Figures with several images,
+    /// or with inline SVG markup, fall back to `Html`.
+    Pandoc,
+}
+
+/// Specifies what happens to repeated headings: a heading directly followed,
+/// with no other heading in between, by headings of the same level and text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatedHeadings {
+    /// Write them all.
+    Keep,
+    /// Write them all, and report each run as
+    /// `ConversionWarning::RepeatedHeading` in the manifest.
+    Warn,
+    /// Write only the first heading of a run, so the sections of the others
+    /// join its section, and report the run like `Warn`. With heading slugs,
+    /// links to a dropped heading's bookmarks point at the first heading.
+    Merge,
+}
+
+/// Specifies how table of contents entries (paragraphs in the `TOC 1` to
+/// `TOC 9` styles) are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TocHandling {
+    /// Keep the entries as Word wrote them, with the tab before the page
+    /// number and the page number itself.
+    Keep,
+    /// Rewrite each entry as a list item linking to its heading, nested by
+    /// TOC level, without leader tabs or page numbers.
+    Links,
+}
+
+/// Specifies how a generated alphabetical index is written: the paragraphs
+/// in the `index 1`..`index 9` and `index heading` styles that Word fills
+/// from the index entry (`XE`) fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexHandling {
+    /// Leave the index out; it is derived from the entries.
+    Drop,
+    /// Keep the index as Word wrote it, with page numbers.
+    Keep,
+    /// Regenerate the index as a nested list of the entries, sorted by term,
+    /// linking to anchors written where the entries occur. With `no_html`
+    /// no anchors are written and only the terms are listed.
+    Links,
+}
+
+/// Specifies the column order of right-to-left tables (`w:bidiVisual`),
+/// which Word displays with the first column on the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtlTableOrder {
+    /// Document order: the first column is written first, so each header
+    /// stays above its data whatever the reading direction.
+    Logical,
+    /// Display order: the columns are reversed, the first column written
+    /// last, as the table appears in Word.
+    Visual,
+}
+
+/// Specifies how tracked changes are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackedChanges {
+    /// Both sides of each change, marked up: insertions as `<ins>` and
+    /// deletions as `<del>` (or `~~deleted~~` without revision metadata or
+    /// with `no_html`).
+    Markup,
+    /// The document with all changes accepted: inserted text as plain text,
+    /// deleted text (`w:delText`) left out.
+    AcceptAll,
+    /// The document with all changes rejected: deleted text as plain text,
+    /// inserted text left out.
+    RejectAll,
+}
+
+/// Specifies the line ending of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`.
+    Lf,
+    /// `\r\n`, for Windows tools.
+    CrLf,
+}
+
+/// Specifies how line breaks within a paragraph (`w:br`, `w:cr`) are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineBreak {
+    /// A bare newline, which Markdown renders as a soft break (a space).
+    Newline,
+    /// Two trailing spaces before the newline (`  \n`).
+    Spaces,
+    /// A backslash at the end of the line (`\`).
+    Backslash,
+    /// An HTML `<br/>` tag.
+    Html,
+}
+
+impl LineBreak {
+    /// Returns the text emitted for one line break.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Newline => "\n",
+            Self::Spaces => "  \n",
+            Self::Backslash => "\\\n",
+            Self::Html => "<br/>\n",
+        }
+    }
+}
+
+/// GitHub alert types, written as `> [!NOTE]`, `> [!TIP]`, ...
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// `> [!NOTE]`
+    Note,
+    /// `> [!TIP]`
+    Tip,
+    /// `> [!IMPORTANT]`
+    Important,
+    /// `> [!WARNING]`
+    Warning,
+    /// `> [!CAUTION]`
+    Caution,
+}
+
+impl AlertKind {
+    /// Returns the alert type as written in the marker (e.g., `NOTE`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Note => "NOTE",
+            Self::Tip => "TIP",
+            Self::Important => "IMPORTANT",
+            Self::Warning => "WARNING",
+            Self::Caution => "CAUTION",
+        }
+    }
+}
+
+/// Specifies how paragraphs with a callout style are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Callout {
+    /// A GitHub alert blockquote (`> [!NOTE]`).
+    Alert(AlertKind),
+    /// A plain blockquote.
+    Quote,
+}
+
+/// Maps paragraph style names to callouts.
+///
+/// A paragraph matches when its style, or a style it is based on, has a
+/// listed name or ID; names are compared case-insensitively, ignoring
+/// whitespace. Consecutive paragraphs with the same callout share one
+/// blockquote.
+///
+/// The default maps common English names ("Note", "Tip", "Important",
+/// "Warning", "Caution" to alerts; "Quote", "Intense Quote", "Block Text",
+/// "Callout" to plain blockquotes). Styles not listed are then looked up in
+/// the localized names of
+/// [`LocalizationStrategy::callout_style_names`], which default to
+/// [`localization::CALLOUT_STYLE_NAMES`] with or without a `localization`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalloutStyles {
+    /// Style names and their callouts; the first match wins.
+    pub styles: Vec<(String, Callout)>,
+}
+
+impl CalloutStyles {
+    /// Returns the callout for a style name or ID.
+    pub fn get(&self, name: &str) -> Option<Callout> {
+        find_callout(
+            self.styles
+                .iter()
+                .map(|(style, callout)| (style.as_str(), *callout)),
+            name,
+        )
+    }
+}
+
+/// Returns the callout of the first of `styles` named `name`, compared like
+/// [`CalloutStyles::get`] does.
+pub(crate) fn find_callout<'s>(
+    styles: impl IntoIterator<Item = (&'s str, Callout)>,
+    name: &str,
+) -> Option<Callout> {
+    let name = normalize_style_name(name);
+    styles
+        .into_iter()
+        .find(|(style, _)| normalize_style_name(style) == name)
+        .map(|(_, callout)| callout)
+}
+
+impl Default for CalloutStyles {
+    fn default() -> Self {
+        const ENGLISH: &[(&str, Callout)] = &[
+            ("Note", Callout::Alert(AlertKind::Note)),
+            ("Tip", Callout::Alert(AlertKind::Tip)),
+            ("Hint", Callout::Alert(AlertKind::Tip)),
+            ("Important", Callout::Alert(AlertKind::Important)),
+            ("Warning", Callout::Alert(AlertKind::Warning)),
+            ("Caution", Callout::Alert(AlertKind::Caution)),
+            ("Quote", Callout::Quote),
+            ("Intense Quote", Callout::Quote),
+            ("Block Text", Callout::Quote),
+            ("Callout", Callout::Quote),
+        ];
+        Self {
+            styles: ENGLISH
+                .iter()
+                .map(|(name, callout)| (name.to_string(), *callout))
+                .collect(),
+        }
+    }
+}
+
+fn normalize_style_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Turns a heading's text and output level (1-6) into its anchor slug.
+///
+/// The default implements GitHub's slugification. Repeated slugs get a `-1`,
+/// `-2`, ... suffix from the converter.
+///
+/// ```
+/// use dm2xcod::SlugFn;
+///
+/// let slug = SlugFn::new(|text, level| format!("h{}-{}", level, text.to_lowercase()));
+/// assert_eq!(slug.slug("Intro", 2), "h2-intro");
+/// assert_eq!(SlugFn::default().slug("Getting Started!", 1), "getting-started");
+/// ```
+#[derive(Clone)]
+pub struct SlugFn(Arc<SlugFnInner>);
+
+type SlugFnInner = dyn Fn(&str, u8) -> String + Send + Sync;
+
+impl SlugFn {
+    /// Wraps a slug function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&str, u8) -> String + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Returns the slug for a heading.
+    pub fn slug(&self, text: &str, level: u8) -> String {
+        (self.0)(text, level)
+    }
+}
+
+impl Default for SlugFn {
+    fn default() -> Self {
+        Self::new(converter::github_slug)
+    }
+}
+
+impl fmt::Debug for SlugFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SlugFn(..)")
+    }
+}
+
 // Python bindings (only when 'python' feature is enabled)
 #[cfg(feature = "python")]
 mod python_bindings {
@@ -1,4 +1,141 @@
-//! Heading style parsing utilities.
+//! Localization - heading style parsing, callout style names, list label
+//! formats and spacing between CJK and Latin text.
+
+use crate::converter::NumberingResolver;
+use crate::{AlertKind, Callout};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Localizes text the converter generates, set with
+/// [`ConvertOptions::localization`](crate::ConvertOptions::localization).
+///
+/// Every method defaults to the built-in output.
+pub trait LocalizationStrategy: fmt::Debug + Send + Sync {
+    /// Formats `value` for a list level numbered in the `w:numFmt` format
+    /// `num_fmt` (e.g. `lowerRoman`, `upperLetter`). The result replaces the
+    /// level's placeholder in a literal label, so `a.` can become `가.`.
+    fn list_number(&self, num_fmt: &str, value: i32) -> Option<String> {
+        let _ = (num_fmt, value);
+        None
+    }
+
+    /// Space inserted where CJK (Hangul, Han, kana) and Latin letters touch
+    /// in run text, such as a thin space (`"\u{2009}"`) for
+    /// style guides that separate `한국어English` as `한국어 English`.
+    fn cjk_latin_space(&self) -> Option<&str> {
+        None
+    }
+
+    /// Localized names of callout paragraph styles, matched when
+    /// [`CalloutStyles`](crate::CalloutStyles) has no entry for a paragraph's
+    /// style. Defaults to [`CALLOUT_STYLE_NAMES`].
+    fn callout_style_names(&self) -> &[(&str, Callout)] {
+        CALLOUT_STYLE_NAMES
+    }
+}
+
+/// Korean list labels: lettered lists count 가, 나, 다, ... like Word's
+/// `ganada` format, up to 하 (14).
+///
+/// ```
+/// use dm2xcod::{KoreanLocalization, LocalizationStrategy};
+///
+/// assert_eq!(KoreanLocalization.list_number("lowerLetter", 2), Some("나".to_string()));
+/// assert_eq!(KoreanLocalization.list_number("upperRoman", 2), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KoreanLocalization;
+
+impl LocalizationStrategy for KoreanLocalization {
+    fn list_number(&self, num_fmt: &str, value: i32) -> Option<String> {
+        match num_fmt {
+            "lowerLetter" | "upperLetter" if (1..=14).contains(&value) => {
+                Some(NumberingResolver::format_ganada(value))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns true for Hangul, Han ideographs, Hiragana and Katakana.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3130}'..='\u{318F}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FFFF}')
+}
+
+/// Returns true for ASCII and accented (Latin-1 and Latin Extended) letters.
+/// Digits are not Latin: `2024년` is written without a space.
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic() || (('\u{00C0}'..='\u{024F}').contains(&c) && c.is_alphabetic())
+}
+
+/// Returns true when `before` followed by `after` is a boundary between CJK
+/// and Latin text.
+pub(crate) fn is_cjk_latin_boundary(before: char, after: char) -> bool {
+    (is_cjk(before) && is_latin(after)) || (is_latin(before) && is_cjk(after))
+}
+
+/// Inserts `space` between touching CJK and Latin characters of `text`.
+pub(crate) fn space_cjk_latin<'t>(text: &'t str, space: &str) -> Cow<'t, str> {
+    let touching = |(a, b): (char, char)| is_cjk_latin_boundary(a, b);
+    if !text.chars().zip(text.chars().skip(1)).any(touching) {
+        return Cow::Borrowed(text);
+    }
+    let mut spaced = String::with_capacity(text.len() + space.len());
+    let mut previous = None;
+    for c in text.chars() {
+        if previous.is_some_and(|previous| is_cjk_latin_boundary(previous, c)) {
+            spaced.push_str(space);
+        }
+        spaced.push(c);
+        previous = Some(c);
+    }
+    Cow::Owned(spaced)
+}
+
+/// Localized names of callout paragraph styles (Korean, Japanese, German,
+/// French), the default of [`LocalizationStrategy::callout_style_names`].
+pub const CALLOUT_STYLE_NAMES: &[(&str, Callout)] = &[
+    // Korean
+    ("참고", Callout::Alert(AlertKind::Note)),
+    ("팁", Callout::Alert(AlertKind::Tip)),
+    ("중요", Callout::Alert(AlertKind::Important)),
+    ("경고", Callout::Alert(AlertKind::Warning)),
+    ("주의", Callout::Alert(AlertKind::Caution)),
+    ("인용", Callout::Quote),
+    ("강한 인용", Callout::Quote),
+    // Japanese
+    ("注記", Callout::Alert(AlertKind::Note)),
+    ("ヒント", Callout::Alert(AlertKind::Tip)),
+    ("重要", Callout::Alert(AlertKind::Important)),
+    ("警告", Callout::Alert(AlertKind::Warning)),
+    ("注意", Callout::Alert(AlertKind::Caution)),
+    ("引用文", Callout::Quote),
+    ("引用文 2", Callout::Quote),
+    // German
+    ("Hinweis", Callout::Alert(AlertKind::Note)),
+    ("Tipp", Callout::Alert(AlertKind::Tip)),
+    ("Wichtig", Callout::Alert(AlertKind::Important)),
+    ("Warnung", Callout::Alert(AlertKind::Warning)),
+    ("Vorsicht", Callout::Alert(AlertKind::Caution)),
+    ("Zitat", Callout::Quote),
+    ("Intensives Zitat", Callout::Quote),
+    // French
+    ("Remarque", Callout::Alert(AlertKind::Note)),
+    ("Astuce", Callout::Alert(AlertKind::Tip)),
+    ("Avertissement", Callout::Alert(AlertKind::Warning)),
+    ("Attention", Callout::Alert(AlertKind::Caution)),
+    ("Citation", Callout::Quote),
+    ("Citation intense", Callout::Quote),
+];
 
 /// Parses a DOCX style name to determine the heading level.
 ///
@@ -21,3 +158,28 @@ pub fn parse_heading_style(style: &str) -> Option<usize> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_space_cjk_latin_separates_scripts() {
+        assert_eq!(
+            space_cjk_latin("한국어English와 日本語Rustで", " "),
+            "한국어 English 와 日本語 Rust で"
+        );
+        // Digits stay next to CJK text.
+        assert!(matches!(
+            space_cjk_latin("2024년 3月", " "),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(space_cjk_latin("2024년v2", " "), "2024년 v2");
+        assert_eq!(space_cjk_latin("Café에서", "\u{2009}"), "Café\u{2009}에서");
+        // Punctuation and existing spaces are left alone.
+        assert!(matches!(
+            space_cjk_latin("한국 (Korea), 서울.", " "),
+            Cow::Borrowed(_)
+        ));
+    }
+}
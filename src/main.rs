@@ -1,8 +1,8 @@
 //! CLI for dm2xcod - DOCX to Markdown converter
 
 use clap::Parser;
-use dm2xcod::{ConvertOptions, DocxToMarkdown, ImageHandling};
-use std::path::PathBuf;
+use dm2xcod::{ConversionManifest, ConvertOptions, DocxToMarkdown, ImageHandling};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -17,9 +17,28 @@ struct Args {
     #[arg(long)]
     images_dir: Option<PathBuf>,
 
+    /// Embed images of at most this many bytes and save only larger ones to
+    /// `--images-dir`
+    #[arg(long, requires = "images_dir")]
+    max_inline_bytes: Option<usize>,
+
     /// Skip extracting images
     #[arg(long)]
     skip_images: bool,
+
+    /// Write footnotes, endnotes and comments to a sibling `<OUTPUT stem>.notes.md`
+    /// file instead of appending them
+    #[arg(long, requires = "output")]
+    separate_notes: bool,
+
+    /// Start the output with a UTF-8 byte order mark
+    #[arg(long)]
+    bom: bool,
+
+    /// Write the conversion manifest (images written and warnings) as JSON
+    /// to this path
+    #[arg(long, value_name = "PATH", conflicts_with = "separate_notes")]
+    manifest: Option<PathBuf>,
 }
 
 fn main() {
@@ -28,25 +47,62 @@ fn main() {
     let image_handling = if args.skip_images {
         ImageHandling::Skip
     } else if let Some(dir) = args.images_dir {
-        ImageHandling::SaveToDir(dir)
+        match args.max_inline_bytes {
+            Some(max_bytes) => ImageHandling::InlineUpTo { max_bytes, dir },
+            None => ImageHandling::SaveToDir(dir),
+        }
     } else {
         ImageHandling::Inline
     };
 
+    // Link saved images relative to where the Markdown file is written.
+    let markdown_output_dir = args
+        .output
+        .as_ref()
+        .map(|output| output.parent().unwrap_or(Path::new("")).to_path_buf());
+
     let options = ConvertOptions {
         image_handling,
+        markdown_output_dir,
+        emit_bom: args.bom,
         ..Default::default()
     };
 
     let converter = DocxToMarkdown::new(options);
 
-    match converter.convert(&args.input) {
+    if args.separate_notes {
+        let output = args.output.expect("--separate-notes requires OUTPUT");
+        match converter.convert_with_notes(&args.input) {
+            Ok(converted) => {
+                write_output(&output, &converted.markdown);
+                if !converted.notes.is_empty() {
+                    let notes_path = output.with_extension("notes.md");
+                    write_output(&notes_path, &converted.notes);
+                    println!("Notes written to {:?}", notes_path);
+                }
+                println!("Successfully converted to {:?}", output);
+            }
+            Err(e) => {
+                eprintln!("Error converting DOCX: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let converted = match &args.manifest {
+        Some(manifest_path) => converter
+            .convert_with_manifest(&args.input)
+            .map(|converted| {
+                write_manifest(manifest_path, &converted.manifest);
+                converted.markdown
+            }),
+        None => converter.convert(&args.input),
+    };
+    match converted {
         Ok(markdown) => {
             if let Some(output) = args.output {
-                if let Err(e) = std::fs::write(&output, &markdown) {
-                    eprintln!("Error writing output: {}", e);
-                    std::process::exit(1);
-                }
+                write_output(&output, &markdown);
                 println!("Successfully converted to {:?}", output);
             } else {
                 println!("{}", markdown);
@@ -58,3 +114,20 @@ fn main() {
         }
     }
 }
+
+fn write_manifest(path: &Path, manifest: &ConversionManifest) {
+    match serde_json::to_string_pretty(manifest) {
+        Ok(json) => write_output(path, &json),
+        Err(e) => {
+            eprintln!("Error serializing manifest: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn write_output(path: &Path, contents: &str) {
+    if let Err(e) = std::fs::write(path, contents) {
+        eprintln!("Error writing output: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -1,12 +1,21 @@
 pub fn escape_html_attr(value: &str) -> String {
+    escape_html(value, true)
+}
+
+/// Escapes text placed inside an HTML element: `&`, `<` and `>`.
+pub fn escape_html_text(value: &str) -> String {
+    escape_html(value, false)
+}
+
+fn escape_html(value: &str, quotes: bool) -> String {
     let mut escaped = String::with_capacity(value.len());
     for ch in value.chars() {
         match ch {
             '&' => escaped.push_str("&amp;"),
             '<' => escaped.push_str("&lt;"),
             '>' => escaped.push_str("&gt;"),
-            '"' => escaped.push_str("&quot;"),
-            '\'' => escaped.push_str("&#39;"),
+            '"' if quotes => escaped.push_str("&quot;"),
+            '\'' if quotes => escaped.push_str("&#39;"),
             _ => escaped.push(ch),
         }
     }
@@ -41,6 +50,66 @@ pub fn escape_markdown_link_destination(value: &str) -> String {
     escaped
 }
 
+/// Escapes a link title written in double quotes: `\` and `"` are escaped
+/// and whitespace (including newlines) collapses to single spaces.
+pub fn escape_markdown_link_title(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for word in value.split_whitespace() {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for ch in word.chars() {
+            if matches!(ch, '\\' | '"') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+/// Escapes text placed inline within a paragraph: whitespace (including
+/// newlines) collapses to single spaces and inline Markdown syntax is escaped.
+pub fn escape_markdown_inline(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for word in value.split_whitespace() {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for ch in word.chars() {
+            if matches!(ch, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters of extended Markdown syntax in document text: `~`,
+/// and with `superscript_and_highlight` also `^` and every `=` next to
+/// another `=`. A lone `=`, as in `a = b`, is left as it is.
+pub fn escape_markdown_extended(value: &str, superscript_and_highlight: bool) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut escaped = String::with_capacity(value.len());
+    for (i, &ch) in chars.iter().enumerate() {
+        let escape = match ch {
+            '~' => true,
+            '^' => superscript_and_highlight,
+            '=' => {
+                superscript_and_highlight
+                    && ((i > 0 && chars[i - 1] == '=') || chars.get(i + 1) == Some(&'='))
+            }
+            _ => false,
+        };
+        if escape {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +122,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_escape_html_text() {
+        assert_eq!(
+            escape_html_text("a < b && \"c\" > 'd'"),
+            "a &lt; b &amp;&amp; \"c\" &gt; 'd'"
+        );
+    }
+
     #[test]
     fn test_escape_markdown_link_text() {
         assert_eq!(escape_markdown_link_text("A[B]"), "A\\[B\\]");
@@ -65,4 +142,33 @@ mod tests {
             "https://x.y/a\\ b\\(c\\)"
         );
     }
+
+    #[test]
+    fn test_escape_markdown_link_title() {
+        assert_eq!(
+            escape_markdown_link_title("Open \"C:\\docs\"\n now"),
+            "Open \\\"C:\\\\docs\\\" now"
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown_inline() {
+        assert_eq!(
+            escape_markdown_inline("See *this*\n\n[^1] <b>"),
+            "See \\*this\\* \\[^1\\] \\<b\\>"
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown_extended() {
+        let text = "x^2 ~ 3, a = b == c";
+        assert_eq!(
+            escape_markdown_extended(text, false),
+            "x^2 \\~ 3, a = b == c"
+        );
+        assert_eq!(
+            escape_markdown_extended(text, true),
+            "x\\^2 \\~ 3, a = b \\=\\= c"
+        );
+    }
 }
@@ -1,12 +1,27 @@
-use crate::core::ast::{BlockNode, DocumentAst};
+use crate::core::ast::{BlockNode, DocumentAst, ReferenceDefinitions};
 use crate::render::Renderer;
-use crate::Result;
+use crate::{ConvertOptions, Result};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct MarkdownRenderer;
 
 impl Renderer for MarkdownRenderer {
     fn render(&self, document: &DocumentAst) -> Result<String> {
+        Ok(Self::render_blocks(document, "\n\n"))
+    }
+
+    /// Separates the blocks with `paragraph_separator`.
+    fn render_with_options(
+        &self,
+        document: &DocumentAst,
+        options: &ConvertOptions,
+    ) -> Result<String> {
+        Ok(Self::render_blocks(document, &options.paragraph_separator))
+    }
+}
+
+impl MarkdownRenderer {
+    fn render_blocks(document: &DocumentAst, separator: &str) -> String {
         let mut out = String::new();
 
         for block in &document.blocks {
@@ -14,32 +29,73 @@ impl Renderer for MarkdownRenderer {
                 BlockNode::Paragraph(text)
                 | BlockNode::TableHtml(text)
                 | BlockNode::RawHtml(text) => text,
+                BlockNode::PageBreak => {
+                    push_rule(&mut out);
+                    "---"
+                }
             };
             if rendered.is_empty() {
                 continue;
             }
             out.push_str(rendered);
-            out.push_str("\n\n");
+            out.push_str(separator);
         }
 
-        let refs = &document.references;
-        if !refs.footnotes.is_empty() || !refs.endnotes.is_empty() || !refs.comments.is_empty() {
-            out.push_str("---\n\n");
-            for (i, note) in refs.footnotes.iter().enumerate() {
-                out.push_str(&format!("[^{}]: {}\n", i + 1, note));
-            }
-            for (i, note) in refs.endnotes.iter().enumerate() {
-                out.push_str(&format!("[^en{}]: {}\n", i + 1, note));
-            }
-            for (id, text) in &refs.comments {
-                out.push_str(&format!("[^c{}]: {}\n", id, text));
-            }
+        let notes = Self::render_notes(&document.references);
+        if !notes.is_empty() {
+            push_rule(&mut out);
+            out.push_str("---");
+            out.push_str(separator);
+            out.push_str(&notes);
         }
 
-        Ok(out)
+        out
+    }
+
+    /// Renders the note definitions (`[^1]: ...`, `[^en1]: ...`, `[^c<id>]: ...`
+    /// with the default markers) that `render` appends after a `---` rule;
+    /// empty when there are no notes.
+    pub fn render_notes(references: &ReferenceDefinitions) -> String {
+        let markers = &references.markers;
+        let mut out = String::new();
+        for (n, note) in references.numbered_footnotes() {
+            out.push_str(&format!("{}: {}\n", markers.footnote(n), indent_note(note)));
+        }
+        for (n, note) in references.numbered_endnotes() {
+            out.push_str(&format!("{}: {}\n", markers.endnote(n), indent_note(note)));
+        }
+        for (id, text) in &references.comments {
+            out.push_str(&format!("{}: {}\n", markers.comment(id), text));
+        }
+        out
     }
 }
 
+/// Ends `out` with a blank line before a `---` rule, which right under a
+/// line of text would make it a heading instead.
+fn push_rule(out: &mut String) {
+    let text = out.trim_end_matches(['\r', '\n']);
+    if !text.is_empty() && out[text.len()..].matches('\n').count() < 2 {
+        out.push('\n');
+    }
+}
+
+/// Indents the lines of a note after the first by four spaces, so blocks
+/// such as tables stay within its definition.
+fn indent_note(note: &str) -> String {
+    note.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("    {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,10 +109,41 @@ mod tests {
                 footnotes: vec!["note".to_string()],
                 endnotes: Vec::new(),
                 comments: Vec::new(),
+                ..Default::default()
             },
         };
         let rendered = MarkdownRenderer.render(&doc).expect("render should work");
         assert!(rendered.contains("A"));
         assert!(rendered.contains("[^1]: note"));
     }
+
+    #[test]
+    fn test_paragraph_separator_keeps_rules_apart_from_text() {
+        let doc = DocumentAst {
+            blocks: vec![
+                BlockNode::Paragraph("# A".to_string()),
+                BlockNode::Paragraph("B".to_string()),
+                BlockNode::PageBreak,
+                BlockNode::Paragraph("C".to_string()),
+            ],
+            references: ReferenceDefinitions {
+                footnotes: vec!["note".to_string()],
+                ..Default::default()
+            },
+        };
+        let options = ConvertOptions {
+            paragraph_separator: "\n".to_string(),
+            ..Default::default()
+        };
+        let rendered = MarkdownRenderer
+            .render_with_options(&doc, &options)
+            .expect("render should work");
+        assert_eq!(rendered, "# A\nB\n\n---\nC\n\n---\n[^1]: note\n");
+        assert_eq!(
+            MarkdownRenderer.render(&doc).unwrap(),
+            MarkdownRenderer
+                .render_with_options(&doc, &ConvertOptions::default())
+                .unwrap()
+        );
+    }
 }
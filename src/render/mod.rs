@@ -1,12 +1,34 @@
 mod escape;
 mod markdown;
+mod plain_text;
 
 use crate::core::ast::DocumentAst;
-use crate::Result;
+use crate::{ConvertOptions, Result};
 
-pub use escape::{escape_html_attr, escape_markdown_link_destination, escape_markdown_link_text};
+pub use escape::{
+    escape_html_attr, escape_html_text, escape_markdown_extended, escape_markdown_inline,
+    escape_markdown_link_destination, escape_markdown_link_text, escape_markdown_link_title,
+};
 pub use markdown::MarkdownRenderer;
+pub use plain_text::PlainTextRenderer;
 
 pub trait Renderer {
     fn render(&self, document: &DocumentAst) -> Result<String>;
+
+    /// Renders the document with the options of the conversion, which is
+    /// how `DocxToMarkdown` calls its renderer. Defaults to `render`.
+    fn render_with_options(
+        &self,
+        document: &DocumentAst,
+        _options: &ConvertOptions,
+    ) -> Result<String> {
+        self.render(document)
+    }
+
+    /// Whether the renderer writes plain text. `DocxToMarkdown` then extracts
+    /// blocks without Markdown or HTML markup for it to join. Defaults to
+    /// `false`.
+    fn plain_text(&self) -> bool {
+        false
+    }
 }
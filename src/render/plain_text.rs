@@ -0,0 +1,108 @@
+use crate::core::ast::{BlockNode, DocumentAst};
+use crate::render::Renderer;
+use crate::Result;
+
+/// Renderer that produces plain text without any Markdown or HTML markup.
+///
+/// Conversions rendered with it extract plain blocks (see
+/// [`Renderer::plain_text`]): formatting is left out, links and images are
+/// reduced to their text/alt text, and tables become tab-separated rows. The
+/// renderer only joins those blocks and lists the notes after them.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlainTextRenderer;
+
+impl Renderer for PlainTextRenderer {
+    fn render(&self, document: &DocumentAst) -> Result<String> {
+        let mut out = String::new();
+
+        for block in &document.blocks {
+            let rendered = match block {
+                BlockNode::Paragraph(text) | BlockNode::TableHtml(text) => text,
+                // Bookmark anchors and column markers have no text.
+                BlockNode::RawHtml(_) => continue,
+                // Pages are separated by a form feed.
+                BlockNode::PageBreak => {
+                    out.push_str("\u{000C}\n\n");
+                    continue;
+                }
+            };
+            let rendered = rendered.trim_matches('\n');
+            if rendered.trim().is_empty() {
+                continue;
+            }
+            out.push_str(rendered);
+            out.push_str("\n\n");
+        }
+
+        // Notes are labelled like their markers in the text, without the `^`.
+        let refs = &document.references;
+        let label = |marker: String| marker.replacen("[^", "[", 1);
+        for (n, note) in refs.numbered_footnotes() {
+            let marker = label(refs.markers.footnote(n));
+            out.push_str(&format!("{} {}\n", marker, note_text(note)));
+        }
+        for (n, note) in refs.numbered_endnotes() {
+            let marker = label(refs.markers.endnote(n));
+            out.push_str(&format!("{} {}\n", marker, note_text(note)));
+        }
+        for (id, text) in &refs.comments {
+            out.push_str(&format!("{} {}\n", label(refs.markers.comment(id)), text));
+        }
+
+        Ok(out)
+    }
+
+    fn plain_text(&self) -> bool {
+        true
+    }
+}
+
+/// Puts the blocks of a note on consecutive lines.
+fn note_text(note: &str) -> String {
+    note.split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ast::ReferenceDefinitions;
+
+    #[test]
+    fn test_plain_text_renderer_writes_blocks_as_they_are() {
+        let doc = DocumentAst {
+            blocks: vec![
+                BlockNode::Paragraph("a*b* <tag> [l](u)".to_string()),
+                BlockNode::Paragraph("# not heading".to_string()),
+                BlockNode::Paragraph("---".to_string()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            PlainTextRenderer.render(&doc).unwrap(),
+            "a*b* <tag> [l](u)\n\n# not heading\n\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_renderer_tables_and_notes() {
+        let doc = DocumentAst {
+            blocks: vec![
+                BlockNode::RawHtml("<a id=\"top\"></a>".to_string()),
+                BlockNode::Paragraph("Intro[1]".to_string()),
+                BlockNode::TableHtml("A\tB C".to_string()),
+            ],
+            references: ReferenceDefinitions {
+                footnotes: vec!["note\n\nsecond".to_string()],
+                endnotes: Vec::new(),
+                comments: Vec::new(),
+                ..Default::default()
+            },
+        };
+
+        let rendered = PlainTextRenderer.render(&doc).expect("render should work");
+        assert_eq!(rendered, "Intro[1]\n\nA\tB C\n\n[1] note\nsecond\n");
+    }
+}
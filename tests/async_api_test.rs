@@ -0,0 +1,33 @@
+#![cfg(feature = "tokio")]
+
+use dm2xcod::{ConvertOptions, DocxToMarkdown};
+
+const SAMPLE: &str = "./tests/aaa/aa.docx";
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("failed to build tokio runtime")
+        .block_on(future)
+}
+
+#[test]
+fn convert_async_matches_sync_conversion() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let expected = converter.convert(SAMPLE).expect("sync conversion failed");
+
+    let from_path = block_on(converter.convert_async(SAMPLE)).expect("async conversion failed");
+    assert_eq!(from_path, expected);
+
+    let bytes = std::fs::read(SAMPLE).expect("failed to read sample docx");
+    let from_bytes =
+        block_on(converter.convert_from_bytes_async(bytes)).expect("async conversion failed");
+    assert_eq!(from_bytes, expected);
+}
+
+#[test]
+fn convert_async_reports_missing_file() {
+    let converter = DocxToMarkdown::with_defaults();
+    let result = block_on(converter.convert_async("./tests/aaa/does-not-exist.docx"));
+    assert!(result.is_err());
+}
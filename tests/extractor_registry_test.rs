@@ -0,0 +1,65 @@
+use dm2xcod::adapters::{DocumentExtractor, ExtractorRegistry};
+use dm2xcod::core::ast::{BlockNode, DocumentAst};
+use dm2xcod::render::{MarkdownRenderer, PlainTextRenderer};
+use dm2xcod::{ConvertOptions, DocxToMarkdown, Error, Result};
+
+const SAMPLE: &str = "./tests/aaa/aa.docx";
+
+/// Treats each blank-line separated chunk of a text file as a paragraph.
+struct TextExtractor;
+
+impl DocumentExtractor for TextExtractor {
+    fn extract(&self, bytes: &[u8]) -> Result<DocumentAst> {
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::Conversion(e.to_string()))?;
+        Ok(DocumentAst {
+            blocks: text
+                .split("\n\n")
+                .map(|chunk| BlockNode::Paragraph(chunk.trim().to_string()))
+                .collect(),
+            references: Default::default(),
+        })
+    }
+}
+
+#[test]
+fn registry_converts_docx_like_the_default_converter() {
+    let registry = ExtractorRegistry::with_docx(ConvertOptions::default());
+    let expected = DocxToMarkdown::new(ConvertOptions::default())
+        .convert(SAMPLE)
+        .expect("conversion failed");
+
+    let markdown = registry
+        .convert(SAMPLE, &MarkdownRenderer)
+        .expect("registry conversion failed");
+    assert_eq!(markdown, expected);
+}
+
+#[test]
+fn registry_dispatches_custom_extractors_by_extension() {
+    let path = std::env::temp_dir().join(format!("dm2xcod-registry-{}.TXT", std::process::id()));
+    std::fs::write(&path, "First paragraph\n\nSecond paragraph\n").expect("write failed");
+
+    let mut registry = ExtractorRegistry::with_docx(ConvertOptions::default());
+    registry.register(".txt", TextExtractor);
+    let markdown = registry.convert(&path, &MarkdownRenderer);
+    let plain = registry.convert(&path, &PlainTextRenderer);
+    std::fs::remove_file(&path).expect("cleanup failed");
+
+    assert_eq!(
+        markdown.expect("conversion failed"),
+        "First paragraph\n\nSecond paragraph\n\n"
+    );
+    assert!(plain
+        .expect("conversion failed")
+        .contains("Second paragraph"));
+    assert!(registry.get("docx").is_some());
+}
+
+#[test]
+fn registry_rejects_unregistered_extensions() {
+    let registry = ExtractorRegistry::default();
+    let err = registry
+        .extract(SAMPLE)
+        .expect_err("docx is not registered");
+    assert!(matches!(err, Error::Conversion(_)), "{err:?}");
+}
@@ -1,10 +1,10 @@
-use dm2xcod::{ConvertOptions, DocxToMarkdown};
+use dm2xcod::{ConvertOptions, DocxToMarkdown, ListLabels};
 use rs_docx::document::{
     AbstractNum, AbstractNumId, BodyContent, Comment, CommentReference, Comments, FootNote,
     FootNotes, FootnoteReference, Level, LevelStart, LevelText, Num, NumFmt, Numbering, Paragraph,
-    Run, RunContent,
+    Run, RunContent, Table, TableCell, TableRow,
 };
-use rs_docx::formatting::{NumberingProperty, ParagraphProperty};
+use rs_docx::formatting::{Bidi, NumberingProperty, ParagraphProperty};
 use rs_docx::Docx;
 use std::borrow::Cow;
 use std::path::PathBuf;
@@ -138,3 +138,115 @@ fn golden_snapshot_duplicate_note_comment_output() {
     let expected = include_str!("golden/notes_comments_expected.md");
     assert_eq!(markdown.trim_end(), expected.trim_end());
 }
+
+#[test]
+fn golden_snapshot_blank_table_cell_output() {
+    let row = |cells: [&'static str; 3]| {
+        cells.into_iter().fold(TableRow::default(), |row, text| {
+            row.push_cell(TableCell::paragraph(Paragraph::default().push_text(text)))
+        })
+    };
+    let mut docx = Docx::default();
+    docx.document.push(
+        Table::default()
+            .push_row(row(["Name", "Role", "Team"]))
+            .push_row(row(["Ada", "", "Core"])),
+    );
+
+    let path = temp_docx_path("blank_table_cell");
+    docx.write_file(&path)
+        .expect("failed to write generated docx");
+
+    let convert = |options| {
+        DocxToMarkdown::new(options)
+            .convert(&path)
+            .expect("failed to convert generated table docx")
+    };
+    let markdown = convert(ConvertOptions::default());
+    let preserved = convert(ConvertOptions {
+        preserve_empty_table_cells: true,
+        ..Default::default()
+    });
+
+    let _ = std::fs::remove_file(&path);
+
+    let expected = include_str!("golden/blank_table_cell_expected.md");
+    assert_eq!(markdown.trim_end(), expected.trim_end());
+    assert_eq!(
+        preserved.trim_end(),
+        expected.trim_end().replace("&nbsp;", "")
+    );
+}
+
+#[test]
+fn golden_snapshot_arabic_indic_rtl_list_output() {
+    let level = |ilvl, start, format, text| Level {
+        i_level: Some(ilvl),
+        start: Some(LevelStart { value: Some(start) }),
+        number_format: Some(NumFmt {
+            value: Cow::Borrowed(format),
+        }),
+        level_text: Some(LevelText {
+            value: Some(Cow::Borrowed(text)),
+        }),
+        ..Default::default()
+    };
+    let mut docx = Docx {
+        numbering: Some(Numbering {
+            abstract_numberings: vec![AbstractNum {
+                abstract_num_id: Some(1),
+                levels: vec![
+                    level(0, 9, "hindiNumbers", "%1."),
+                    level(1, 1, "arabicAbjad", "%2)"),
+                ],
+                ..Default::default()
+            }],
+            numberings: vec![Num {
+                num_id: Some(2),
+                abstract_num_id: Some(AbstractNumId { value: Some(1) }),
+                ..Default::default()
+            }],
+        }),
+        ..Default::default()
+    };
+
+    for (ilvl, text) in [
+        (0isize, "البند التاسع"),
+        (0, "البند العاشر"),
+        (1, "فرع أول"),
+        (1, "فرع ثان"),
+    ] {
+        docx.document.push(
+            Paragraph::default()
+                .property(ParagraphProperty {
+                    numbering: Some(NumberingProperty::from((2isize, ilvl))),
+                    bidi: Some(Bidi { value: Some(true) }),
+                    ..Default::default()
+                })
+                .push_text(text),
+        );
+    }
+
+    let path = temp_docx_path("arabic_list");
+    docx.write_file(&path)
+        .expect("failed to write generated docx");
+
+    let literal = DocxToMarkdown::new(ConvertOptions::default())
+        .convert(&path)
+        .expect("failed to convert generated arabic-list docx");
+    let western = DocxToMarkdown::new(ConvertOptions {
+        list_labels: ListLabels::Markdown,
+        ..Default::default()
+    })
+    .convert(&path)
+    .expect("failed to convert generated arabic-list docx");
+
+    let _ = std::fs::remove_file(&path);
+
+    let expected = include_str!("golden/arabic_list_expected.md");
+    assert_eq!(literal.trim_end(), expected.trim_end());
+    assert_eq!(
+        western.trim_end(),
+        "9. البند التاسع\n10. البند العاشر\n  1. فرع أول\n  2. فرع ثان"
+    );
+}
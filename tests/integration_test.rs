@@ -1,4 +1,11 @@
-use dm2xcod::{ConvertOptions, DocxToMarkdown};
+use dm2xcod::core::ast::{BlockNode, DocumentAst};
+use dm2xcod::render::{MarkdownRenderer, PlainTextRenderer, Renderer};
+use dm2xcod::{
+    CalloutStyles, CodeBlocks, CodeLineNumbers, ConversionWarning, ConvertOptions,
+    DocumentMetadata, DocxToMarkdown, Error, FragmentContext, HiddenText, ImageHandling,
+    IndexHandling, LineBreak, ManifestImage, MathFallback, RepeatedHeadings, ResolvedComments,
+    RtlTableOrder, RubyText, SmartTags, TextFramePosition, TextFrames,
+};
 use std::fs::{create_dir_all, read_dir, File};
 use std::io::Write;
 use std::process::Command;
@@ -55,3 +62,1122 @@ fn verify_markdown_conversion() {
     }
     println!("Successfully verified {} files", count);
 }
+
+#[test]
+fn extract_images_returns_media_with_alt_text() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let images = converter
+        .extract_images("./tests/pandoc/image.docx")
+        .expect("image extraction failed");
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].name, "word/media/rId20.jpg");
+    assert_eq!(images[0].alt_text.as_deref(), Some("testimg"));
+    assert!(
+        images[0].data.starts_with(&[0xFF, 0xD8]),
+        "expected JPEG bytes"
+    );
+    assert_eq!(images[0].path, None);
+
+    let output_dir = "./tests/output/extract_images";
+    let saving = DocxToMarkdown::new(ConvertOptions {
+        image_handling: ImageHandling::SaveToDir(output_dir.into()),
+        ..Default::default()
+    });
+    let saved = saving
+        .extract_images("./tests/pandoc/image.docx")
+        .expect("image extraction failed");
+    let path = saved[0].path.as_ref().expect("image should be saved");
+    assert_eq!(std::fs::read(path).expect("saved image"), images[0].data);
+
+    let skipping = DocxToMarkdown::new(ConvertOptions {
+        image_handling: ImageHandling::Skip,
+        ..Default::default()
+    });
+    assert!(skipping
+        .extract_images("./tests/pandoc/image.docx")
+        .expect("image extraction failed")
+        .is_empty());
+}
+
+#[test]
+fn extract_custom_xml_returns_data_parts() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let parts = converter
+        .extract_custom_xml("./tests/aaa/aa.docx")
+        .expect("custom XML extraction failed");
+
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].0, "customXml/item1.xml");
+    assert!(parts[0].1.contains("<b:Sources"));
+
+    let none = converter
+        .extract_custom_xml("./tests/pandoc/image.docx")
+        .expect("custom XML extraction failed");
+    assert!(none.is_empty());
+}
+
+#[test]
+fn extract_metadata_reads_core_and_app_properties() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let metadata = converter
+        .extract_metadata("./tests/aaa/aa.docx")
+        .expect("metadata extraction failed");
+
+    assert_eq!(
+        metadata,
+        DocumentMetadata {
+            creator: Some("Cai Jack".into()),
+            last_modified_by: Some("Jack Cai".into()),
+            revision: Some(2),
+            created: Some("2023-12-27T12:12:00Z".into()),
+            modified: Some("2023-12-27T12:12:00Z".into()),
+            application: Some("Microsoft Office Word".into()),
+            app_version: Some("16.0000".into()),
+            template: Some("Normal.dotm".into()),
+            total_editing_minutes: Some(0),
+            pages: Some(11),
+            words: Some(26),
+            characters: Some(151),
+            paragraphs: Some(1),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn no_html_output_contains_no_html_tags() {
+    let converter = DocxToMarkdown::new(ConvertOptions {
+        no_html: true,
+        hidden_text: HiddenText::AsComment,
+        preserve_lang: true,
+        annotate_columns: true,
+        line_break: LineBreak::Html,
+        ..Default::default()
+    });
+
+    for folder in ["./tests/aaa", "./tests/pandoc"] {
+        for entry in read_dir(folder).expect("Failed to read directory") {
+            let path = entry.unwrap().path();
+            if !path.extension().is_some_and(|ext| ext == "docx") {
+                continue;
+            }
+            let markdown = converter
+                .convert(&path)
+                .unwrap_or_else(|e| panic!("Failed to convert {:?}: {:?}", path, e));
+            let unescaped = markdown
+                .match_indices('<')
+                .find(|(idx, _)| !markdown[..*idx].ends_with('\\'));
+            if let Some((idx, _)) = unescaped {
+                let end = markdown.len().min(idx + 60);
+                panic!("{:?} contains HTML: {}", path, &markdown[idx..end]);
+            }
+        }
+    }
+}
+
+#[test]
+fn blocks_iter_yields_the_blocks_of_convert() {
+    let converter = DocxToMarkdown::new(ConvertOptions {
+        image_handling: ImageHandling::Skip,
+        callout_styles: Some(CalloutStyles::default()),
+        ..Default::default()
+    });
+
+    for folder in ["./tests/aaa", "./tests/pandoc"] {
+        for entry in read_dir(folder).expect("Failed to read directory") {
+            let path = entry.unwrap().path();
+            if !path.extension().is_some_and(|ext| ext == "docx") {
+                continue;
+            }
+            let mut blocks = converter
+                .blocks_iter(&path)
+                .unwrap_or_else(|e| panic!("Failed to open {:?}: {:?}", path, e));
+            let document = DocumentAst {
+                blocks: blocks
+                    .by_ref()
+                    .collect::<Result<_, _>>()
+                    .unwrap_or_else(|e| panic!("Failed to convert {:?}: {:?}", path, e)),
+                references: blocks
+                    .into_references()
+                    .expect("references after the last block"),
+            };
+            assert_eq!(
+                MarkdownRenderer.render(&document).unwrap(),
+                converter.convert(&path).unwrap(),
+                "{:?}",
+                path
+            );
+        }
+    }
+
+    // Dropping the iterator early stops the conversion.
+    let mut blocks = converter.blocks_iter("./tests/aaa/aa.docx").unwrap();
+    assert!(blocks.next().is_some_and(|block| block.is_ok()));
+    assert!(blocks.references().is_none());
+    drop(blocks);
+}
+
+#[test]
+fn convert_with_notes_splits_off_note_definitions() {
+    let converter = DocxToMarkdown::new(ConvertOptions {
+        image_handling: ImageHandling::Skip,
+        ..Default::default()
+    });
+    let path = "./tests/aaa/aa.docx";
+
+    let converted = converter.convert_with_notes(path).unwrap();
+    assert!(converted.markdown.contains("[^1]"));
+    assert!(!converted.markdown.contains("[^1]:"));
+    assert!(converted.notes.starts_with("[^1]: "));
+    assert!(converted.notes.contains("[^en1]: "));
+    assert_eq!(
+        format!("{}---\n\n{}", converted.markdown, converted.notes),
+        converter.convert(path).unwrap()
+    );
+
+    let dir = std::env::temp_dir().join(format!("dm2xcod_notes_{}", std::process::id()));
+    create_dir_all(&dir).unwrap();
+    let output = dir.join("aa.md");
+    let status = Command::new(env!("CARGO_BIN_EXE_dm2xcod"))
+        .arg(path)
+        .arg(&output)
+        .args(["--skip-images", "--separate-notes"])
+        .status()
+        .expect("Failed to execute command");
+    assert!(status.success());
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        converted.markdown
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("aa.notes.md")).unwrap(),
+        converted.notes
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn convert_with_manifest_matches_convert() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let path = "./tests/pandoc/image.docx";
+
+    let converted = converter.convert_with_manifest(path).unwrap();
+    assert_eq!(converted.markdown, converter.convert(path).unwrap());
+    assert!(converted.manifest.warnings.is_empty());
+
+    let images = &converted.manifest.images;
+    assert_eq!(images.len(), 4);
+    assert_eq!(
+        images[0],
+        ManifestImage {
+            rid: "rId20".to_string(),
+            target: "media/rId20.jpg".to_string(),
+            path: None,
+            alt: Some("testimg".to_string()),
+            width: Some(200),
+            height: Some(200),
+        }
+    );
+    assert_eq!((images[3].width, images[3].height), (Some(560), Some(280)));
+}
+
+#[test]
+fn cli_writes_the_manifest_as_json() {
+    let dir = std::env::temp_dir().join(format!("dm2xcod_manifest_{}", std::process::id()));
+    create_dir_all(&dir).unwrap();
+    let manifest = dir.join("images.json");
+    let status = Command::new(env!("CARGO_BIN_EXE_dm2xcod"))
+        .arg("./tests/pandoc/image.docx")
+        .arg(dir.join("image.md"))
+        .arg("--images-dir")
+        .arg(dir.join("assets"))
+        .arg("--manifest")
+        .arg(&manifest)
+        .status()
+        .expect("Failed to execute command");
+    assert!(status.success());
+
+    let json = std::fs::read_to_string(&manifest).unwrap();
+    assert!(json.starts_with("{\n  \"images\": [\n    {\n      \"rid\": \"rId20\",\n"));
+    assert!(json.contains("\"alt\": \"testimg\",\n      \"width\": 200,\n      \"height\": 200\n"));
+    assert!(json.contains(&format!(
+        "\"path\": {:?}",
+        dir.join("assets").join("image_1.jpg").display().to_string()
+    )));
+    assert!(json.ends_with("\"warnings\": []\n}"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn legacy_form_fields_render_their_values() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let markdown = converter.convert("./tests/aaa/form_fields.docx").unwrap();
+
+    let paragraphs: Vec<&str> = markdown.split("\n\n").map(str::trim_end).collect();
+    assert_eq!(
+        paragraphs[..7],
+        [
+            "Name: Jane Doe",
+            "Empty:",
+            "[x] Subscribed",
+            "[ ] Newsletter",
+            "[x] Terms accepted",
+            "Size: Large & tall",
+            "Color: Red",
+        ]
+    );
+}
+
+#[test]
+fn inline_up_to_saves_only_large_images() {
+    let output_dir = "./tests/output/inline_up_to";
+    let convert = |max_bytes| {
+        DocxToMarkdown::new(ConvertOptions {
+            image_handling: ImageHandling::InlineUpTo {
+                max_bytes,
+                dir: output_dir.into(),
+            },
+            ..Default::default()
+        })
+        .convert("./tests/pandoc/image.docx")
+        .expect("conversion failed")
+    };
+
+    let inlined = convert(usize::MAX);
+    assert!(inlined.contains("<img src=\"data:image/jpeg;base64,"));
+
+    let saved = convert(1024);
+    assert!(!saved.contains("data:image"));
+    let link = saved
+        .split("![image](")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .expect("image link");
+    assert!(link.ends_with("image_1.jpg"));
+    assert!(std::path::Path::new(link).is_file());
+}
+
+#[test]
+fn list_spacing_decides_tight_or_loose_lists() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let markdown = converter.convert("./tests/aaa/list_spacing.docx").unwrap();
+
+    // List Paragraph items have contextual spacing; Normal items keep the
+    // default space after each paragraph.
+    assert_eq!(
+        markdown.trim_end(),
+        "Tight list:\n\n1. Apples\n2. Pears\n3. Plums\n\n\
+         Loose list:\n\n1. Red\n\n2. Green\n\n3. Blue\n\n\
+         Mixed list:\n\n1. First\n2. Second\n\n3. Third"
+    );
+}
+
+#[test]
+fn collapsed_list_item_breaks_keep_items_on_one_line() {
+    let convert = |collapse_list_item_breaks| {
+        DocxToMarkdown::new(ConvertOptions {
+            line_break: LineBreak::Backslash,
+            collapse_list_item_breaks,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/list_item_breaks.docx")
+        .unwrap()
+    };
+
+    assert_eq!(
+        convert(false).trim_end(),
+        "Steps:\n\n1. Open the lid\\\nand wait\n2. Pour the water\\\nslowly\n\nKeep\\\nthis break"
+    );
+    // Breaks outside lists are unaffected.
+    assert_eq!(
+        convert(true).trim_end(),
+        "Steps:\n\n1. Open the lid and wait\n2. Pour the water slowly\n\nKeep\\\nthis break"
+    );
+}
+
+#[test]
+fn mathtype_equations_follow_math_fallback() {
+    let convert = |math_fallback, image_handling| {
+        DocxToMarkdown::new(ConvertOptions {
+            math_fallback,
+            image_handling,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/mathtype.docx")
+        .unwrap()
+    };
+
+    assert_eq!(
+        convert(MathFallback::Image, ImageHandling::Inline).trim_end(),
+        "Energy is <img src=\"data:image/wmf;base64,d21m\" alt=\"E equals m c squared\" />."
+    );
+    // The OLE object has no text to fall back to.
+    assert_eq!(
+        convert(MathFallback::Image, ImageHandling::Skip).trim_end(),
+        "Energy is [equation: E equals m c squared]."
+    );
+    assert_eq!(
+        convert(MathFallback::Placeholder, ImageHandling::Inline).trim_end(),
+        "Energy is [equation: E equals m c squared]."
+    );
+}
+
+#[test]
+fn unsupported_math_constructs_are_reported() {
+    let path = "./tests/aaa/equations.docx";
+    let warnings = |warn_unsupported_math| {
+        DocxToMarkdown::new(ConvertOptions {
+            warn_unsupported_math,
+            ..Default::default()
+        })
+        .convert_with_manifest(path)
+        .unwrap()
+        .manifest
+        .warnings
+    };
+
+    assert!(warnings(false).is_empty());
+    // Each construct once, in document order; `x=1` loses nothing.
+    let unsupported = |construct: &str| ConversionWarning::UnsupportedMath {
+        construct: construct.to_string(),
+    };
+    assert_eq!(
+        warnings(true),
+        vec![
+            unsupported("m:sSup"),
+            unsupported("m:nary"),
+            unsupported("m:f")
+        ]
+    );
+}
+
+#[test]
+fn smart_tags_are_unwrapped_or_annotated() {
+    let convert = |smart_tags, no_html| {
+        DocxToMarkdown::new(ConvertOptions {
+            smart_tags,
+            no_html,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/smart_tags.docx")
+        .unwrap()
+    };
+
+    assert_eq!(
+        convert(SmartTags::Unwrap, false).trim_end(),
+        "Meet on <strong>March 14, 2024</strong> in Seoul."
+    );
+    assert_eq!(
+        convert(SmartTags::Annotate, false).trim_end(),
+        "Meet on <span data-smart-tag=\"date\"><strong>March 14, 2024</strong></span> in \
+         <span data-smart-tag=\"place\"><span data-smart-tag=\"City\">Seoul</span></span>."
+    );
+    assert_eq!(
+        convert(SmartTags::Annotate, true).trim_end(),
+        "Meet on **March 14, 2024** in Seoul."
+    );
+}
+
+#[test]
+fn resolved_comments_are_labelled_or_skipped() {
+    let convert = |resolved_comments| {
+        DocxToMarkdown::new(ConvertOptions {
+            resolved_comments,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/resolved_comments.docx")
+        .unwrap()
+    };
+
+    // The reply belongs to the resolved thread.
+    assert_eq!(
+        convert(ResolvedComments::Annotate).trim_end(),
+        "Typo fixed.[^c0][^c1]\n\nNeeds a source.[^c2]\n\n---\n\n\
+         [^c0]: (resolved) Spelling?\n[^c1]: (resolved) Done\n[^c2]: Cite this"
+    );
+    assert!(convert(ResolvedComments::Include).contains("[^c0]: Spelling?\n"));
+    assert_eq!(
+        convert(ResolvedComments::Skip).trim_end(),
+        "Typo fixed.\n\nNeeds a source.[^c2]\n\n---\n\n[^c2]: Cite this"
+    );
+}
+
+#[test]
+fn tables_and_formatting_in_footnotes_are_kept() {
+    let path = "./tests/aaa/footnote_table.docx";
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+
+    assert_eq!(
+        converter.convert(path).unwrap().trim_end(),
+        "Results vary by region.[^1]\n\n---\n\n\
+         [^1]: Figures from the <strong>2023</strong> survey:\n\n    \
+         <table>\n      <tr>\n        <td>Region</td>\n        <td>Share</td>\n      </tr>\n      \
+         <tr>\n        <td>North</td>\n        <td>40%</td>\n      </tr>\n    </table>\n\n    \
+         <em>Rounded.</em>"
+    );
+    assert!(converter
+        .to_plain_text(path)
+        .unwrap()
+        .ends_with("[1] Figures from the 2023 survey:\nRegion\tShare\nNorth\t40%\nRounded.\n"));
+}
+
+#[test]
+fn fragments_convert_against_supplied_parts() {
+    use hard_xml::XmlRead;
+
+    let docx_file = rs_docx::DocxFile::from_file("./tests/aaa/list_spacing.docx").unwrap();
+    let docx = docx_file.parse().unwrap();
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+
+    // The loose list, numbered with the document's own definitions.
+    let fragment = &docx.document.body.content[4..8];
+    assert_eq!(
+        converter
+            .convert_fragment(fragment, &FragmentContext::from(docx.clone()))
+            .unwrap()
+            .trim_end(),
+        "Loose list:\n\n1. Red\n\n2. Green\n\n3. Blue"
+    );
+
+    // Content from elsewhere, with only the relationship it links through.
+    let document = rs_docx::document::Document::from_str(
+        r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>
+            <w:p><w:r><w:t xml:space="preserve">See </w:t></w:r><w:hyperlink r:id="rId1"><w:r><w:t>the docs</w:t></w:r></w:hyperlink></w:p>
+        </w:body></w:document>"#,
+    )
+    .unwrap();
+    let context = FragmentContext::new().with_relationship("rId1", "https://example.com/docs");
+    assert_eq!(
+        converter
+            .convert_fragment(&document.body.content, &context)
+            .unwrap()
+            .trim_end(),
+        "See [the docs](https://example.com/docs)"
+    );
+}
+
+#[test]
+fn convert_parsed_matches_convert() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let path = std::path::Path::new("./tests/pandoc/image.docx");
+    let docx_file = rs_docx::DocxFile::from_file(path).unwrap();
+    let docx = docx_file.parse().unwrap();
+
+    assert_eq!(
+        converter.convert_parsed(&docx, Some(path)).unwrap(),
+        converter.convert(path).unwrap()
+    );
+    // Without the source file there is nothing to read images from.
+    let without_images = converter.convert_parsed(&docx, None).unwrap();
+    assert!(!without_images.contains("<img"));
+    assert_eq!(
+        without_images,
+        DocxToMarkdown::new(ConvertOptions {
+            image_handling: ImageHandling::Skip,
+            ..Default::default()
+        })
+        .convert(path)
+        .unwrap()
+    );
+}
+
+#[test]
+fn text_only_path_matches_full_parse() {
+    // `convert` parses text-only documents part by part; `convert_parsed`
+    // always goes through the full rs_docx parse.
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    for folder in ["./tests/aaa", "./tests/pandoc"] {
+        for entry in read_dir(folder).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|e| e.to_str()) != Some("docx") {
+                continue;
+            }
+            let Ok(docx_file) = rs_docx::DocxFile::from_file(&path) else {
+                continue;
+            };
+            let Ok(docx) = docx_file.parse() else {
+                continue;
+            };
+            let expected = converter.convert_parsed(&docx, Some(&path)).unwrap();
+            assert_eq!(converter.convert(&path).unwrap(), expected, "{:?}", path);
+            let bytes = std::fs::read(&path).unwrap();
+            assert_eq!(
+                converter.convert_from_bytes(&bytes).unwrap(),
+                expected,
+                "{:?}",
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn corrupt_media_becomes_a_placeholder_unless_strict() {
+    let path = "./tests/aaa/corrupt_media.docx";
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+
+    let converted = converter.convert_with_manifest(path).unwrap();
+    assert_eq!(
+        converted.markdown.trim_end(),
+        "Before the picture.\n\n[image]\n\nAfter the picture."
+    );
+    match converted.manifest.warnings.as_slice() {
+        [ConversionWarning::CorruptMedia { rid, target, .. }] => {
+            assert_eq!(
+                (rid.as_str(), target.as_str()),
+                ("rId5", "media/image1.png")
+            );
+        }
+        warnings => panic!("unexpected warnings: {:?}", warnings),
+    }
+
+    let strict = DocxToMarkdown::new(ConvertOptions {
+        strict_media: true,
+        ..Default::default()
+    });
+    match strict.convert(path) {
+        Err(Error::CorruptMedia(targets)) => assert_eq!(targets, "media/image1.png"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn ruby_text_is_written_as_html_or_in_parentheses() {
+    let path = "./tests/aaa/ruby.docx";
+    let convert = |options: ConvertOptions| DocxToMarkdown::new(options).convert(path).unwrap();
+
+    assert_eq!(
+        convert(ConvertOptions::default()).trim_end(),
+        "今日は<ruby>漢字<rt>かんじ</rt></ruby>を<ruby>勉強<rt>べんきょう</rt></ruby>します。\n\n\
+         Pinyin: <ruby>中文<rt>zhōngwén</rt></ruby>"
+    );
+
+    let parentheses = "今日は漢字(かんじ)を勉強(べんきょう)します。\n\nPinyin: 中文(zhōngwén)";
+    assert_eq!(
+        convert(ConvertOptions {
+            ruby: RubyText::Parentheses,
+            ..Default::default()
+        })
+        .trim_end(),
+        parentheses
+    );
+    assert_eq!(
+        convert(ConvertOptions {
+            no_html: true,
+            ..Default::default()
+        })
+        .trim_end(),
+        parentheses
+    );
+}
+
+#[test]
+fn generated_index_is_dropped_kept_or_regenerated() {
+    let path = "./tests/aaa/index.docx";
+    let convert = |index, no_html| {
+        let options = ConvertOptions {
+            index,
+            no_html,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    assert_eq!(
+        convert(IndexHandling::Drop, false).trim_end(),
+        "Pears ripen late.\n\nApples grow on trees.\n\nMore about apples.\n\nIndex\n\nThe end."
+    );
+    assert!(convert(IndexHandling::Keep, false).contains("Apple, 1, 2\n\nF\n\nFruit"));
+    assert_eq!(
+        convert(IndexHandling::Links, false).trim_end(),
+        "Pears ripen late.<a id=\"xe-1\"></a>\n\n\
+         Apples grow on trees.<a id=\"xe-2\"></a><a id=\"xe-3\"></a>\n\n\
+         More about apples.<a id=\"xe-4\"></a>\n\n\
+         Index\n\n\
+         - [Apple](#xe-2), [2](#xe-4)\n\
+         - Fruit\n\
+         \x20 - [Apple](#xe-3)\n\
+         - [Pear](#xe-1)\n\n\
+         The end."
+    );
+    assert!(convert(IndexHandling::Links, true)
+        .contains("Index\n\n- Apple\n- Fruit\n  - Apple\n- Pear\n\nThe end."));
+}
+
+#[test]
+fn presets_write_their_markdown_flavor() {
+    let convert =
+        |options: ConvertOptions, path: &str| DocxToMarkdown::new(options).convert(path).unwrap();
+
+    let headers = convert(ConvertOptions::pandoc(), "./tests/pandoc/headers.docx");
+    assert!(headers
+        .lines()
+        .any(|line| line.starts_with('#') && line.ends_with('}')));
+    assert!(!headers.contains("<a id="));
+
+    let notes = convert(ConvertOptions::plain(), "./tests/pandoc/notes.docx");
+    assert!(!notes.contains("[^") && !notes.contains('<'));
+}
+
+#[test]
+fn monospace_paragraphs_become_numbered_code_blocks() {
+    let path = "./tests/aaa/code_blocks.docx";
+    let convert = |code_blocks| {
+        let options = ConvertOptions {
+            code_blocks,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    let plain = convert(None);
+    assert!(
+        plain.contains("fn main() {\n\nprintln!(\"*hi*\");"),
+        "{plain}"
+    );
+
+    let markdown = convert(Some(CodeBlocks::default()));
+    assert_eq!(
+        markdown.trim_end(),
+        "Run it with:\n\n\
+         ```\nfn main() {\n    println!(\"*hi*\");\n\n}\n```\n\n\
+         Call main once."
+    );
+
+    let numbered = convert(Some(CodeBlocks {
+        language: Some("rust".to_string()),
+        line_numbers: CodeLineNumbers::InfoString,
+    }));
+    assert!(numbered.contains("```rust {.numberLines}\nfn main() {\n"));
+
+    let prefixed = convert(Some(CodeBlocks {
+        language: None,
+        line_numbers: CodeLineNumbers::Prefix,
+    }));
+    assert!(prefixed.contains("```\n1  fn main() {\n2      println!(\"*hi*\");\n3  \n4  }\n```"));
+}
+
+#[test]
+fn rtl_tables_keep_headers_above_their_columns() {
+    let path = "./tests/aaa/rtl_table.docx";
+    let convert = |rtl_table_order, no_html| {
+        let options = ConvertOptions {
+            rtl_table_order,
+            no_html,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    let logical = convert(RtlTableOrder::Logical, false);
+    assert!(logical.contains(
+        "  <tr>\n    <th scope=\"row\">الاسم</th>\n    <td>المدينة</td>\n    <td>العمر</td>\n  </tr>\n\
+         \x20 <tr>\n    <th scope=\"row\">سارة</th>\n    <td>القاهرة</td>\n    <td>٣٠</td>\n  </tr>"
+    ));
+
+    let visual = convert(RtlTableOrder::Visual, false);
+    assert!(visual.contains(
+        "  <tr>\n    <td>العمر</td>\n    <td>المدينة</td>\n    <th scope=\"row\">الاسم</th>\n  </tr>\n\
+         \x20 <tr>\n    <td>٣٠</td>\n    <td>القاهرة</td>\n    <th scope=\"row\">سارة</th>\n  </tr>"
+    ));
+    assert!(visual.contains("<td>٥٥</td>\n    <th scope=\"row\" colspan=\"2\">المجموع</th>"));
+
+    assert_eq!(
+        convert(RtlTableOrder::Logical, true).trim_end(),
+        "| الاسم | المدينة | العمر |\n| --- | --- | --- |\n\
+         | سارة | القاهرة | ٣٠ |\n| علي | دبي | ٢٥ |\n| المجموع |  | ٥٥ |"
+    );
+    assert_eq!(
+        convert(RtlTableOrder::Visual, true).trim_end(),
+        "| العمر | المدينة | الاسم |\n| --- | --- | --- |\n\
+         | ٣٠ | القاهرة | سارة |\n| ٢٥ | دبي | علي |\n| ٥٥ | المجموع |  |"
+    );
+}
+
+#[test]
+fn verbatim_styles_are_fenced_unchanged() {
+    let path = "./tests/aaa/verbatim_styles.docx";
+    let convert = |verbatim_styles: &[&str], no_html| {
+        let options = ConvertOptions {
+            verbatim_styles: verbatim_styles.iter().map(|s| s.to_string()).collect(),
+            no_html,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    let expected = "Terms:\n\n\
+                    ```\nTHE SOFTWARE IS PROVIDED *AS IS*,  WITHOUT\n   WARRANTY OF ANY KIND <EXPRESS>.\n```\n\n\
+                    See **above**.";
+    assert_eq!(convert(&["license text"], false).trim_end(), expected);
+    assert_eq!(convert(&["LicenseText"], true).trim_end(), expected);
+    assert!(!convert(&[], false).contains("```"));
+}
+
+#[test]
+fn kbd_styles_write_key_names_as_kbd() {
+    let path = "./tests/aaa/kbd_styles.docx";
+    let convert = |kbd_styles: &[&str], no_html| {
+        let options = ConvertOptions {
+            kbd_styles: kbd_styles.iter().map(|s| s.to_string()).collect(),
+            no_html,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    assert_eq!(
+        convert(&["keyboard key"], false).trim_end(),
+        "Press <strong><kbd>Ctrl</kbd></strong>+<strong><kbd>C</kbd></strong> to copy."
+    );
+    assert_eq!(
+        convert(&[], false).trim_end(),
+        "Press <strong>Ctrl</strong>+<strong>C</strong> to copy."
+    );
+    assert_eq!(
+        convert(&["Keyboard"], true).trim_end(),
+        "Press **Ctrl**+**C** to copy."
+    );
+}
+
+#[test]
+fn hyperlink_tooltips_become_link_titles() {
+    let path = "./tests/aaa/link_tooltips.docx";
+    let convert = |link_titles| {
+        let options = ConvertOptions {
+            link_titles,
+            ..Default::default()
+        };
+        DocxToMarkdown::new(options).convert(path).unwrap()
+    };
+
+    assert_eq!(
+        convert(true).trim_end(),
+        "See the [Rust site](https://www.rust-lang.org/ \"Opens the \\\"Rust\\\" home page\") \
+         and the [crate docs](https://docs.rs/)."
+    );
+    assert_eq!(
+        convert(false).trim_end(),
+        "See the [Rust site](https://www.rust-lang.org/) and the [crate docs](https://docs.rs/)."
+    );
+}
+
+#[test]
+fn rendered_page_breaks_become_page_boundary_blocks() {
+    let bytes = std::fs::read("./tests/aaa/page_boundaries.docx").unwrap();
+    let converter = DocxToMarkdown::new(ConvertOptions {
+        page_boundaries: true,
+        ..Default::default()
+    });
+
+    let blocks = converter.extract_from_bytes(&bytes).unwrap().blocks;
+    let pages: Vec<Vec<String>> = blocks
+        .split(|block| matches!(block, BlockNode::PageBreak))
+        .map(|page| {
+            page.iter()
+                .map(|block| match block {
+                    BlockNode::Paragraph(text) => text.clone(),
+                    other => panic!("unexpected block {:?}", other),
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        pages,
+        [
+            vec!["First page."],
+            vec!["Second page.", "More on page two."],
+            vec!["Third page."],
+        ]
+    );
+
+    let plain = PlainTextRenderer
+        .render(&converter.extract_from_bytes(&bytes).unwrap())
+        .unwrap();
+    assert_eq!(plain.matches('\u{000C}').count(), 2);
+
+    // Without the option the hints stay horizontal rules in the text.
+    let markdown = DocxToMarkdown::new(ConvertOptions::default())
+        .convert_from_bytes(&bytes)
+        .unwrap();
+    assert_eq!(markdown.matches("---").count(), 2);
+}
+
+#[test]
+fn nesting_beyond_the_limit_is_an_error() {
+    let convert = |max_nesting_depth| {
+        DocxToMarkdown::new(ConvertOptions {
+            max_nesting_depth,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/rtl_table.docx")
+    };
+
+    assert!(convert(1).is_ok());
+    assert!(matches!(convert(0), Err(Error::NestingTooDeep(_))));
+}
+
+#[test]
+fn outline_levels_become_headings_without_heading_styles() {
+    let convert = |outline_headings| {
+        DocxToMarkdown::new(ConvertOptions {
+            outline_headings,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/outline_headings.docx")
+        .unwrap()
+    };
+
+    // The level comes from the custom style or the paragraph; level 9 is body text.
+    assert_eq!(
+        convert(true).trim_end(),
+        "# Introduction\n\nSome text.\n\n## Background\n\nNot a heading.\n\nAlso not a heading."
+    );
+    assert_eq!(
+        convert(false).trim_end(),
+        "Introduction\n\nSome text.\n\nBackground\n\nNot a heading.\n\nAlso not a heading."
+    );
+}
+
+#[test]
+fn table_cell_blocks_keep_paragraphs_and_lists() {
+    let convert = |table_cell_blocks| {
+        DocxToMarkdown::new(ConvertOptions {
+            table_cell_blocks,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/cell_blocks.docx")
+        .unwrap()
+    };
+
+    let cells = |markdown: String| -> Vec<String> {
+        markdown
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("<td>"))
+            .map(|cell| cell.trim_end_matches("</td>").to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        cells(convert(false))[0],
+        "First paragraph.<br/>Second paragraph.<br/>• Apples<br/>o Green<br/>• Pears"
+    );
+    assert_eq!(
+        cells(convert(true)),
+        [
+            "<p>First paragraph.</p><p>Second paragraph.</p>\
+             <ul><li>Apples<ul><li>Green</li></ul></li><li>Pears</li></ul>",
+            "Only text.",
+            "<p>Steps:</p><ol><li>Mix</li><li>Bake</li></ol>",
+            "&nbsp;",
+        ]
+    );
+}
+
+#[test]
+fn validate_checks_parts_and_relationships() {
+    let converter = DocxToMarkdown::with_defaults();
+    converter.validate("./tests/aaa/aa.docx").unwrap();
+    // Corrupt image data is only found by converting.
+    converter
+        .validate("./tests/aaa/corrupt_media.docx")
+        .unwrap();
+
+    match converter.validate("./tests/aaa/missing_media.docx") {
+        Err(Error::RelationshipNotFound(missing)) => {
+            assert_eq!(missing, "rId5 (word/media/image1.png)")
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(
+        converter.validate("./README.md"),
+        Err(Error::Zip(_))
+    ));
+}
+
+#[test]
+fn identical_footnotes_share_a_definition_when_merged() {
+    let convert = |merge_identical_footnotes| {
+        DocxToMarkdown::new(ConvertOptions {
+            merge_identical_footnotes,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/repeated_footnotes.docx")
+        .unwrap()
+    };
+
+    assert_eq!(
+        convert(false).trim_end(),
+        "Revenue grew.[^1]\n\nStaff agreed.[^2]\n\nCosts fell.[^3]\n\n---\n\n\
+         [^1]: Annual report 2023, p. 4.\n\
+         [^2]: Interview with the author.\n\
+         [^3]: Annual report 2023, p. 4."
+    );
+    assert_eq!(
+        convert(true).trim_end(),
+        "Revenue grew.[^1]\n\nStaff agreed.[^2]\n\nCosts fell.[^1]\n\n---\n\n\
+         [^1]: Annual report 2023, p. 4.\n\
+         [^2]: Interview with the author."
+    );
+}
+
+#[test]
+fn repeated_headings_are_reported_or_merged() {
+    let convert = |repeated_headings| {
+        DocxToMarkdown::new(ConvertOptions {
+            repeated_headings,
+            ..Default::default()
+        })
+        .convert_with_manifest("./tests/aaa/repeated_headings.docx")
+        .unwrap()
+    };
+
+    let kept = convert(RepeatedHeadings::Keep);
+    assert!(kept.manifest.warnings.is_empty());
+
+    // The last "Overview" follows another heading, so it starts no run.
+    let runs = vec![
+        ConversionWarning::RepeatedHeading {
+            text: "Overview".to_string(),
+            level: 1,
+            paragraphs: vec![0, 2],
+        },
+        ConversionWarning::RepeatedHeading {
+            text: "Details".to_string(),
+            level: 2,
+            paragraphs: vec![4, 5],
+        },
+    ];
+    let warned = convert(RepeatedHeadings::Warn);
+    assert_eq!(warned.markdown, kept.markdown);
+    assert_eq!(warned.manifest.warnings, runs);
+
+    let merged = convert(RepeatedHeadings::Merge);
+    assert_eq!(merged.manifest.warnings, runs);
+    assert_eq!(
+        merged.markdown.trim_end(),
+        "# Overview\n\nFirst part.\n\nSecond part.\n\n## Details\n\n# Overview"
+    );
+}
+
+#[test]
+fn empty_content_controls_show_their_building_blocks() {
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let path = "./tests/aaa/building_blocks.docx";
+    let bytes = std::fs::read(path).unwrap();
+
+    // The last control holds content of its own and keeps it.
+    let expected = "Terms\n\nPayment is due within 30 days.\n\nLate payments accrue interest.\n\n\
+                    Signed by Jane Doe\n\nOwn content";
+    assert_eq!(converter.convert(path).unwrap().trim_end(), expected);
+    assert_eq!(
+        converter.convert_from_bytes(&bytes).unwrap().trim_end(),
+        expected
+    );
+}
+
+#[test]
+fn building_blocks_nesting_beyond_the_limit_is_an_error() {
+    let convert = |max_nesting_depth| {
+        DocxToMarkdown::new(ConvertOptions {
+            max_nesting_depth,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/nested_building_block.docx")
+    };
+
+    // The body nests one content control, the block it shows five tables.
+    assert!(matches!(convert(4), Err(Error::NestingTooDeep(_))));
+    assert!(convert(5).unwrap().contains("Deep"));
+}
+
+#[test]
+fn bibliography_sources_become_a_list() {
+    let convert = |bibliography_list| {
+        DocxToMarkdown::new(ConvertOptions {
+            bibliography_list,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/bibliography.docx")
+        .unwrap()
+    };
+
+    let kept = convert(false);
+    assert!(kept.contains("As shown (Smith, 2020)."));
+    assert!(kept.contains("Jones, B. (2019). <em>Field Notes</em>.\n\nSmith, A."));
+
+    let listed = convert(true);
+    assert_eq!(
+        listed.trim_end(),
+        "As shown (Smith, 2020).\n\nReferences\n\n\
+         - Jones, B. (2019). <em>Field Notes</em>.\n\n\
+         - Smith, A. (2020). Word Documents. Seoul: Press.\n\nAfter."
+    );
+}
+
+#[test]
+fn text_frames_become_blockquotes_or_asides() {
+    let convert = |text_frames, text_frame_position| {
+        DocxToMarkdown::new(ConvertOptions {
+            text_frames,
+            text_frame_position,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/text_frames.docx")
+        .unwrap()
+    };
+
+    // The drop cap is a frame too, but always joins its paragraph.
+    assert_eq!(
+        convert(TextFrames::Inline, TextFramePosition::InPlace).trim_end(),
+        "Before the story.\n\nDesign is how it works.\n\nSteve Jobs\n\n\
+         The story the quote floats beside.\n\nOnce more."
+    );
+    assert_eq!(
+        convert(TextFrames::Quote, TextFramePosition::InPlace).trim_end(),
+        "Before the story.\n\n> Design is how it works.\n>\n> Steve Jobs\n\n\
+         The story the quote floats beside.\n\nOnce more."
+    );
+    assert_eq!(
+        convert(TextFrames::Aside, TextFramePosition::AfterAnchor).trim_end(),
+        "Before the story.\n\nThe story the quote floats beside.\n\n\
+         <aside>\n\nDesign is how it works.\n\nSteve Jobs\n\n</aside>\n\nOnce more."
+    );
+}
+
+#[test]
+fn image_only_table_cells_hold_only_their_images() {
+    let output_dir = "./tests/output/image_cells";
+    let convert = |no_html| {
+        DocxToMarkdown::new(ConvertOptions {
+            image_handling: ImageHandling::SaveToDir(output_dir.into()),
+            no_html,
+            ..Default::default()
+        })
+        .convert("./tests/aaa/image_cells.docx")
+        .unwrap()
+    };
+
+    // Spaces around the second image and blank paragraphs are left out.
+    let html = convert(false);
+    assert!(html.contains(
+        "<td><img src=\"./tests/output/image_cells/image_1.png\" alt=\"image\" /></td>\n    \
+         <td><img src=\"./tests/output/image_cells/image_2.png\" alt=\"image\" /></td>"
+    ));
+    assert!(html.contains(
+        "<td><img src=\"./tests/output/image_cells/image_3.png\" alt=\"image\" /></td>\n    \
+         <td>Caption text</td>"
+    ));
+    // Images next to text in a cell are still written as `<img>`.
+    assert!(html.contains(
+        "<td>Logo: <img src=\"./tests/output/image_cells/image_4.png\" alt=\"image\" /></td>"
+    ));
+
+    let pipe = convert(true);
+    assert!(pipe.contains(
+        "| ![image](./tests/output/image_cells/image_1.png) \
+         | ![image](./tests/output/image_cells/image_2.png) |"
+    ));
+    assert!(pipe.contains("| Logo: ![image](./tests/output/image_cells/image_4.png) | Mixed |"));
+}
@@ -197,3 +197,79 @@ fn randomized_reference_validation_invariants() {
         }
     }
 }
+
+/// Copies a DOCX archive, replacing the content of the part `name`.
+fn replace_part(source: &[u8], name: &str, content: &[u8]) -> Vec<u8> {
+    use std::io::{Read, Write};
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(source)).unwrap();
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).unwrap();
+        writer
+            .start_file(file.name(), zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(if file.name() == name { content } else { &data })
+            .unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn randomized_malformed_parts_convert_leniently() {
+    let source = std::fs::read("./tests/aaa/verbatim_styles.docx").unwrap();
+    let read_part = |name: &str| {
+        use std::io::Read;
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&source)).unwrap();
+        let mut data = Vec::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        data
+    };
+    let parts = [
+        ("word/document.xml", read_part("word/document.xml")),
+        ("word/styles.xml", read_part("word/styles.xml")),
+    ];
+    let converter = DocxToMarkdown::new(ConvertOptions::default());
+    let mut rng = Lcg::new(0x1E41_E417_2026);
+
+    for i in 0..48 {
+        let (name, xml) = &parts[(rng.next_u64() % 2) as usize];
+        let mut mutated = xml.clone();
+        if i == 0 || rng.next_bool() {
+            mutated.truncate((rng.next_u64() % xml.len() as u64) as usize);
+        } else {
+            for _ in 0..rng.next_inclusive_i32(1, 4) {
+                let at = (rng.next_u64() % mutated.len() as u64) as usize;
+                mutated[at] = b"<>\"&/=\xff"[(rng.next_u64() % 7) as usize];
+            }
+        }
+
+        let path = temp_docx_path(&format!("lenient_{}", i));
+        std::fs::write(&path, replace_part(&source, name, &mutated)).unwrap();
+        let strict = converter.convert(&path);
+        let lenient = converter.convert_lenient(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let lenient = lenient
+            .unwrap_or_else(|e| panic!("lenient conversion failed on iteration {i}: {:?}", e));
+        if strict.is_err() {
+            assert!(
+                !lenient.manifest.warnings.is_empty(),
+                "a part that fails to parse is reported on iteration {i}"
+            );
+        }
+        if *name == "word/styles.xml" {
+            assert!(
+                lenient.markdown.contains("above"),
+                "the document survives malformed styles on iteration {i}"
+            );
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "log")]
+
+use dm2xcod::{ConvertOptions, DocxToMarkdown};
+use std::sync::Mutex;
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+struct Recorder;
+
+impl log::Log for Recorder {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == "dm2xcod"
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let mut records = RECORDS.lock().unwrap();
+            records.push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn conversion_phases_and_warnings_are_logged() {
+    log::set_logger(&Recorder).expect("logger already set");
+    log::set_max_level(log::LevelFilter::Debug);
+
+    DocxToMarkdown::new(ConvertOptions {
+        warn_unsupported_math: true,
+        ..Default::default()
+    })
+    .convert("./tests/aaa/equations.docx")
+    .expect("conversion failed");
+
+    let records = RECORDS.lock().unwrap();
+    let debug = |prefix: &str| {
+        records
+            .iter()
+            .any(|(level, text)| *level == log::Level::Debug && text.starts_with(prefix))
+    };
+    assert!(debug("converting ./tests/aaa/equations.docx"));
+    for phase in ["parse", "extract", "render"] {
+        assert!(debug(&format!("{} finished in", phase)), "{}", phase);
+    }
+    assert!(debug("converted ./tests/aaa/equations.docx: "));
+    assert!(records
+        .iter()
+        .any(|(level, text)| *level == log::Level::Warn && text.contains("m:sSup")));
+}